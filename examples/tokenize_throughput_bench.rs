@@ -0,0 +1,25 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Measures how many characters per second each segmentation mode tokenizes
+
+use chinese_dictionary::{ChineseDictionary, SegmentationMode};
+use std::time::Instant;
+
+static PARAGRAPH: &str = "中华人民共和国成立以后，北京成为全国的政治和文化中心。我们今天去天安门广场看升旗，然后去吃北京烤鸭。\
+	随着经济的快速发展，越来越多的年轻人选择在大城市工作和生活，但是房价和生活成本也在不断上涨。\
+	这本书的作者认为，学习一门外语最重要的是坚持每天练习，而不是一次花很多时间。";
+
+fn main() {
+	let document: String = PARAGRAPH.repeat(2_000);
+	let characters = document.chars().count();
+	let mut dictionary = ChineseDictionary::new();
+	for (name, mode) in [("longest match", SegmentationMode::LongestMatch), ("unigram", SegmentationMode::Unigram), ("classical", SegmentationMode::Classical), ("bigram", SegmentationMode::Bigram)] {
+		dictionary.set_segmentation_mode(mode);
+		// The first call loads the indexes, so it is left out of the measurement
+		dictionary.segment(PARAGRAPH);
+		let start = Instant::now();
+		let words = dictionary.segment(&document).len();
+		let seconds = start.elapsed().as_secs_f64();
+		println!("{:<14} {:>9} characters {:>8} words {:>8.1} ms {:>12.0} characters/s", name, characters, words, seconds * 1000.0, characters as f64 / seconds);
+	}
+}
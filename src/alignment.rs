@@ -0,0 +1,84 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Word alignment between a Chinese sentence and its English translation
+
+use crate::builder::english_key;
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+use std::ops::Range;
+
+/// Words at the start of a gloss that are usually left out of a translation, such as the `to` of `to like`.
+static LEADING_WORDS: [&str; 4] = ["to", "a", "an", "the"];
+/// Placeholders in glosses that stand in for other words, such as `to give sb sth`.
+static PLACEHOLDERS: [&str; 4] = ["sb", "sth", "one's", "oneself"];
+/// Endings that may be added to a gloss word in a translation, such as the `s` of `cats`.
+static INFLECTIONS: [&str; 6] = ["s", "es", "ed", "d", "ing", "ly"];
+
+/// The words of a gloss that are expected to appear in a translation.
+fn gloss_words(gloss: &str) -> Vec<String> {
+	let key = english_key(gloss);
+	let mut words: Vec<&str> = key.split("%20").filter(|word| !word.is_empty() && !PLACEHOLDERS.contains(word)).collect();
+	while words.len() > 1 && LEADING_WORDS.contains(&words[0]) {
+		words.remove(0);
+	}
+	words.into_iter().map(str::to_string).collect()
+}
+
+/// Whether a word of a translation is a gloss word, or the gloss word with an inflection.
+fn matches_word(word: &str, gloss_word: &str) -> bool {
+	word == gloss_word || word.strip_prefix(gloss_word).is_some_and(|ending| INFLECTIONS.contains(&ending))
+}
+
+impl Dictionary {
+	/// # Align Gloss
+	/// Propose word alignments between a Chinese sentence and its English translation, using the glosses of the words in the Chinese sentence.
+	/// Each alignment is the byte range of a word in `chinese_sentence`, the byte range of the words in `english_sentence` it translates to,
+	/// and the entry whose gloss matched. Alignments are ordered by their position in `chinese_sentence`, and each English word is aligned at most once.
+	///
+	/// Glosses are matched case-insensitively, ignoring a leading `to` or article and simple inflections, so 猫 `cat` is aligned with `cats`.
+	/// Words without a matching gloss in the translation are left out.
+	pub fn align_gloss(&self, chinese_sentence: &str, english_sentence: &str) -> Vec<(Range<usize>, Range<usize>, &WordEntry)> {
+		let mut english: Vec<(Range<usize>, String)> = Vec::new();
+		let mut start: Option<usize> = None;
+		for (index, character) in english_sentence.char_indices().chain(std::iter::once((english_sentence.len(), ' '))) {
+			match (start, character.is_alphabetic() || character == '\'') {
+				(None, true) => start = Some(index),
+				(Some(begin), false) => {
+					english.push((begin..index, english_sentence[begin..index].to_lowercase()));
+					start = None;
+				},
+				_ => {},
+			}
+		}
+
+		let mut used: Vec<bool> = vec![false; english.len()];
+		let mut alignments: Vec<(Range<usize>, Range<usize>, &WordEntry)> = Vec::new();
+		for range in self.scan(chinese_sentence) {
+			// The longest gloss found in the translation, as the English word positions it covers and its entry
+			let mut best: Option<(Range<usize>, &WordEntry)> = None;
+			for entry in self.entries_for_headword(&chinese_sentence[range.clone()]) {
+				for gloss in &entry.english {
+					let words = gloss_words(gloss);
+					if words.is_empty() || words.len() > english.len() || best.as_ref().is_some_and(|(best, _)| best.len() >= words.len()) {
+						continue;
+					}
+					let found = (0..=english.len() - words.len()).find(|position| {
+						words.iter().enumerate().all(|(offset, word)| !used[position + offset] && matches_word(&english[position + offset].1, word))
+					});
+					if let Some(position) = found {
+						best = Some((position..position + words.len(), entry));
+					}
+				}
+			}
+
+			if let Some((positions, entry)) = best {
+				for position in positions.clone() {
+					used[position] = true;
+				}
+				let english_range = english[positions.start].0.start..english[positions.end - 1].0.end;
+				alignments.push((range, english_range, entry));
+			}
+		}
+
+		alignments
+	}
+}
@@ -0,0 +1,54 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Regenerates the dictionary data files from a CC-CEDICT release
+
+extern crate chinese_dictionary;
+
+use chinese_dictionary::{build_bigrams, build_frequencies, build_from_cedict, build_from_data, read_metadata};
+use std::env;
+use std::path::Path;
+use std::process;
+
+fn main() {
+	let args: Vec<String> = env::args().skip(1).collect();
+	if args.len() != 2 && args.len() != 3 {
+		eprintln!("Usage: chinese_dictionary_builder <cedict_ts.u8 | data.dictionary> <out_dir> [zh.profile]");
+		process::exit(2);
+	}
+
+	let metadata_path = Path::new(&args[1]).join("metadata.dictionary");
+	let previous = read_metadata(&metadata_path).ok();
+
+	// A source ending in `.dictionary` is existing data, such as the embedded `data/data.dictionary`, rather than a CC-CEDICT release
+	let built = if args[0].ends_with(".dictionary") {
+		build_from_data(&args[0], &args[1])
+	} else {
+		build_from_cedict(&args[0], &args[1])
+	};
+	if let Err(error) = built {
+		eprintln!("Failed to build the dictionary: {}", error);
+		process::exit(1);
+	}
+
+	if let Some(profile) = args.get(2) {
+		if let Err(error) = build_frequencies(profile, &args[1]) {
+			eprintln!("Failed to build the word frequencies: {}", error);
+			process::exit(1);
+		}
+		if let Err(error) = build_bigrams(profile, &args[1]) {
+			eprintln!("Failed to build the word bigrams: {}", error);
+			process::exit(1);
+		}
+	}
+
+	// Report what changed since the last build into the same directory
+	if let (Some(previous), Ok(current)) = (previous, read_metadata(&metadata_path)) {
+		let diff = current.diff(&previous);
+		println!("{} added, {} removed, {} changed since {}", diff.added.len(), diff.removed.len(), diff.changed.len(), previous.version);
+		for (label, word_ids) in [("Added", &diff.added), ("Removed", &diff.removed), ("Changed", &diff.changed)] {
+			if !word_ids.is_empty() {
+				println!("{}: {}", label, word_ids.iter().map(|word_id| word_id.to_string()).collect::<Vec<String>>().join(" "));
+			}
+		}
+	}
+}
@@ -0,0 +1,236 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Serves the dictionary over HTTP with JSON responses
+
+extern crate chinese_dictionary;
+extern crate serde_json;
+
+use chinese_dictionary::{ChineseDictionary, Index, SharedDictionary, TokenKind, WordEntry};
+use serde_json::{json, Value};
+use std::env;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process;
+use std::sync::mpsc::{self, Receiver, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+static USAGE: &str = "Usage: chinese_dictionary_server [address]\n\n\
+	Endpoints:\n\
+	GET /query?q=<query>\n\
+	GET /tokenize?text=<text>\n\
+	GET /annotate?text=<text>\n\
+	GET /health";
+
+/// The number of threads handling requests.
+const WORKERS: usize = 8;
+/// Connections accepted while every worker is busy wait in a queue this long. Connections beyond it are turned away with a 503.
+const QUEUE_LENGTH: usize = 64;
+/// The most bytes read of a request line and its headers, so a client cannot make the server buffer without limit.
+const MAX_REQUEST_BYTES: u64 = 16 * 1024;
+/// How long a client has to send its request, or to read the response, before the connection is dropped.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+fn main() {
+	let args: Vec<String> = env::args().skip(1).collect();
+	if args.len() > 1 || args.first().is_some_and(|arg| arg == "-h" || arg == "--help") {
+		eprintln!("{}", USAGE);
+		process::exit(2);
+	}
+	let address = args.first().map(String::as_str).unwrap_or("127.0.0.1:8080");
+
+	// Load every index before accepting connections, so the first requests are as fast as the rest
+	let dictionary = SharedDictionary::from(ChineseDictionary::init_only(&[Index::Traditional, Index::Simplified, Index::Pinyin, Index::English]));
+	let listener = match TcpListener::bind(address) {
+		Ok(listener) => listener,
+		Err(error) => {
+			eprintln!("Failed to listen on {}: {}", address, error);
+			process::exit(1);
+		},
+	};
+	println!("Listening on {}", address);
+
+	let (sender, receiver) = mpsc::sync_channel::<TcpStream>(QUEUE_LENGTH);
+	let receiver = Arc::new(Mutex::new(receiver));
+	for _ in 0..WORKERS {
+		let dictionary = dictionary.clone();
+		let receiver = Arc::clone(&receiver);
+		thread::spawn(move || work(&dictionary, &receiver));
+	}
+
+	for stream in listener.incoming().flatten() {
+		if let Err(TrySendError::Full(stream)) = sender.try_send(stream) {
+			reject(stream);
+		}
+	}
+}
+
+/// Handles the connections sent by the listener, one at a time, until the listener stops.
+fn work(dictionary: &ChineseDictionary, receiver: &Mutex<Receiver<TcpStream>>) {
+	loop {
+		// The lock is only held while waiting for the next connection, not while handling it
+		let stream = match receiver.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).recv() {
+			Ok(stream) => stream,
+			Err(_) => return,
+		};
+		if let Err(error) = handle(dictionary, stream) {
+			eprintln!("Failed to handle a request: {}", error);
+		}
+	}
+}
+
+fn handle(dictionary: &ChineseDictionary, stream: TcpStream) -> std::io::Result<()> {
+	stream.set_read_timeout(Some(TIMEOUT))?;
+	stream.set_write_timeout(Some(TIMEOUT))?;
+	let mut reader = BufReader::new(stream.take(MAX_REQUEST_BYTES));
+	let mut request_line = String::new();
+	reader.read_line(&mut request_line)?;
+	// The headers are not needed, but are read so the client is not cut off mid request
+	let mut header = String::new();
+	while reader.read_line(&mut header)? > 2 {
+		header.clear();
+	}
+
+	let mut parts = request_line.split_whitespace();
+	let (status, body) = match (parts.next(), parts.next()) {
+		_ if !request_line.ends_with('\n') => ("400 Bad Request", error_body("malformed or oversized request")),
+		(Some("GET"), Some(target)) => route(dictionary, target),
+		(Some(_), Some(_)) => ("405 Method Not Allowed", error_body("only GET is supported")),
+		_ => ("400 Bad Request", error_body("malformed request")),
+	};
+
+	respond(reader.into_inner().into_inner(), status, &body)
+}
+
+/// Turns a connection away with a 503 without blocking the listener: the response is written without waiting,
+/// and dropped if the client is not reading.
+fn reject(stream: TcpStream) {
+	if stream.set_nonblocking(true).is_ok() {
+		let _ = respond(stream, "503 Service Unavailable", &error_body("the server is busy"));
+	}
+}
+
+fn respond(mut stream: TcpStream, status: &str, body: &Value) -> std::io::Result<()> {
+	let body = body.to_string();
+	write!(stream, "HTTP/1.1 {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body)?;
+	stream.flush()
+}
+
+fn route(dictionary: &ChineseDictionary, target: &str) -> (&'static str, Value) {
+	let (path, query) = target.split_once('?').unwrap_or((target, ""));
+	// `None` when the parameter is missing, and `Some(None)` when it is not valid percent-encoded UTF-8
+	let parameter = |name: &str| {
+		query.split('&')
+			.filter_map(|pair| pair.split_once('='))
+			.find(|(key, _)| *key == name)
+			.map(|(_, value)| percent_decode(value))
+	};
+
+	match path {
+		"/health" => ("200 OK", json!({ "status": "ok" })),
+		"/query" => match parameter("q") {
+			Some(Some(raw)) => ("200 OK", json!({ "results": entries_json(&dictionary.query(&raw).unwrap_or_default()) })),
+			Some(None) => ("400 Bad Request", error_body("invalid percent-encoding")),
+			None => ("400 Bad Request", error_body("missing the q parameter")),
+		},
+		"/tokenize" | "/annotate" => match parameter("text") {
+			Some(Some(text)) => match dictionary.tokenize_checked(text.as_bytes()) {
+				Ok(tokens) => {
+					let tokens: Vec<Value> = tokens.iter().map(|token| {
+						let kind = match token.kind {
+							TokenKind::Word => "word",
+							TokenKind::Foreign => "foreign",
+							TokenKind::Emoji => "emoji",
+							TokenKind::Kaomoji => "kaomoji",
+							TokenKind::Symbol => "symbol",
+							TokenKind::Unknown => "unknown",
+						};
+						let mut json = json!({ "text": token.text, "start": token.range.start, "end": token.range.end, "kind": kind });
+						if path == "/annotate" && token.kind == TokenKind::Word {
+							json["entries"] = entries_json(&dictionary.query_by_chinese(token.text));
+						}
+						json
+					}).collect();
+					("200 OK", json!({ "tokens": tokens }))
+				},
+				Err(error) => ("400 Bad Request", error_body(&error.to_string())),
+			},
+			Some(None) => ("400 Bad Request", error_body("invalid percent-encoding")),
+			None => ("400 Bad Request", error_body("missing the text parameter")),
+		},
+		_ => ("404 Not Found", error_body("unknown endpoint")),
+	}
+}
+
+fn error_body(message: &str) -> Value {
+	json!({ "error": message })
+}
+
+fn entries_json(entries: &[&WordEntry]) -> Value {
+	entries.iter().map(|entry| json!({
+		"word_id": entry.word_id,
+		"traditional": entry.traditional,
+		"simplified": entry.simplified,
+		"pinyin_marks": entry.pinyin_marks,
+		"pinyin_numbers": entry.pinyin_numbers,
+		"english": entry.english,
+		"hsk": entry.hsk,
+	})).collect()
+}
+
+/// Decodes a URL query value, where `+` is a space and `%XX` is a byte of UTF-8.
+fn percent_decode(value: &str) -> Option<String> {
+	let mut bytes: Vec<u8> = Vec::with_capacity(value.len());
+	let mut input = value.bytes();
+	while let Some(byte) = input.next() {
+		match byte {
+			b'+' => bytes.push(b' '),
+			b'%' => {
+				let high = (input.next()? as char).to_digit(16)?;
+				let low = (input.next()? as char).to_digit(16)?;
+				bytes.push((high * 16 + low) as u8);
+			},
+			byte => bytes.push(byte),
+		}
+	}
+	String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn percent_decode_values() {
+		assert_eq!(Some("hello world".to_string()), percent_decode("hello+world"));
+		assert_eq!(Some("你好".to_string()), percent_decode("%E4%BD%A0%e5%a5%bd"));
+		assert_eq!(Some("100%".to_string()), percent_decode("100%25"));
+		// Truncated or non-hex escapes, and bytes that are not UTF-8
+		assert_eq!(None, percent_decode("%E4%BD"));
+		assert_eq!(None, percent_decode("%4"));
+		assert_eq!(None, percent_decode("%zz"));
+		assert_eq!(None, percent_decode("%FF"));
+	}
+
+	#[test]
+	fn route_requests() {
+		let dictionary = ChineseDictionary::new();
+		assert_eq!(("200 OK", json!({ "status": "ok" })), route(&dictionary, "/health"));
+
+		let (status, body) = route(&dictionary, "/query?q=%E4%BD%A0%E5%A5%BD");
+		assert_eq!("200 OK", status);
+		assert!(body["results"].as_array().unwrap().iter().any(|entry| entry["simplified"] == "你好"));
+
+		let (status, body) = route(&dictionary, "/annotate?text=%E6%88%91%E4%BB%AC");
+		assert_eq!("200 OK", status);
+		assert_eq!("我们", body["tokens"][0]["text"]);
+		assert!(body["tokens"][0]["entries"].as_array().is_some_and(|entries| !entries.is_empty()));
+
+		assert_eq!(("400 Bad Request", error_body("missing the q parameter")), route(&dictionary, "/query?text=x"));
+		assert_eq!(("400 Bad Request", error_body("invalid percent-encoding")), route(&dictionary, "/query?q=%FF"));
+		assert_eq!(("400 Bad Request", error_body("missing the text parameter")), route(&dictionary, "/tokenize"));
+		assert_eq!(("400 Bad Request", error_body("invalid percent-encoding")), route(&dictionary, "/tokenize?text=%E4%B"));
+		assert_eq!("404 Not Found", route(&dictionary, "/missing").0);
+	}
+}
@@ -0,0 +1,380 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Builds the dictionary data files from a CC-CEDICT release
+
+use bincode::{deserialize_from, serialize_into};
+use crate::chinese_dictionary::{MeasureWord, WordEntry};
+use crate::hash::entry_hash;
+use crate::index::KeyIndex;
+use crate::metadata::DataMetadata;
+use crate::source::SourceId;
+use crate::trie::{HeadwordTrie, TrieBuilder};
+use crate::pinyin::{numbers_to_marks, split_tone_number};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Error, ErrorKind};
+use std::path::Path;
+
+/// The lookup indexes stored alongside the entry data.
+pub(crate) struct Indexes {
+	pub(crate) traditional: HashMap<String, Vec<u32>>,
+	pub(crate) simplified: HashMap<String, Vec<u32>>,
+	pub(crate) pinyin: HashMap<String, Vec<u32>>,
+	pub(crate) english: HashMap<String, Vec<u32>>,
+}
+
+/// # Build from CEDICT
+/// Parse a CC-CEDICT file and write the `traditional`, `simplified`, `pinyin`, `english`, `headwords`, `data`, and `metadata` dictionary files into `out_dir`.
+/// The index files hold finite state transducers, which are read without being expanded into maps.
+/// The `headwords` file holds the trie over the simplified and traditional headwords that segmentation scans text with.
+/// The `metadata` file holds a `DataMetadata`, which can be compared against the metadata of a later build to find which entries changed.
+/// Entries are assigned a `word_id` in the order they appear in the CC-CEDICT file.
+/// The output is byte for byte the same for the same input on every platform.
+///
+/// CC-CEDICT does not include HSK levels, so every generated entry has an `hsk` of `0`.
+pub fn build_from_cedict<P: AsRef<Path>, Q: AsRef<Path>>(path: P, out_dir: Q) -> io::Result<()> {
+	let source = fs::read_to_string(path)?;
+	let entries = parse_cedict(&source)?;
+	write_dictionary(entries, out_dir.as_ref())
+}
+
+/// # Build from Data
+/// Read the entries of an existing `data.dictionary`, such as the one embedded in the crate, and write the same files as `build_from_cedict` into `out_dir`.
+/// The entries are cleaned up the same way `build_from_cedict` cleans up the entries it parses, so data written by an older builder
+/// picks up changes such as measure words moving out of the glosses, and they keep their `word_id` and `hsk` level.
+/// `out_dir` may be the directory the data is read from.
+///
+/// Every embedded file but the word frequencies and bigram counts is built this way from the embedded `data.dictionary`,
+/// so rebuilding it reproduces the checksums returned by `data_checksums`.
+pub fn build_from_data<P: AsRef<Path>, Q: AsRef<Path>>(path: P, out_dir: Q) -> io::Result<()> {
+	let data: HashMap<u32, WordEntry> = read_file(path.as_ref())?;
+	let mut entries: Vec<WordEntry> = data.into_values().collect();
+	entries.sort_by_key(|entry| entry.word_id);
+	for entry in &mut entries {
+		extract_measure_words(entry);
+		entry.hash = entry_hash(entry);
+	}
+	write_dictionary(entries, out_dir.as_ref())
+}
+
+/// Writes the data, indexes, headword trie, and metadata for a set of entries sorted by `word_id`.
+fn write_dictionary(entries: Vec<WordEntry>, out_dir: &Path) -> io::Result<()> {
+	let indexes = build_indexes(&entries);
+	fs::create_dir_all(out_dir)?;
+
+	write_file(&out_dir.join("headwords.dictionary"), &build_headword_trie(&indexes))?;
+	write_file(&out_dir.join("traditional.dictionary"), &KeyIndex::new(indexes.traditional))?;
+	write_file(&out_dir.join("simplified.dictionary"), &KeyIndex::new(indexes.simplified))?;
+	write_file(&out_dir.join("pinyin.dictionary"), &KeyIndex::new(indexes.pinyin))?;
+	write_file(&out_dir.join("english.dictionary"), &KeyIndex::new(indexes.english))?;
+	write_file(&out_dir.join("metadata.dictionary"), &DataMetadata::from_entries(&entries))?;
+	let data: BTreeMap<u32, WordEntry> = entries.into_iter().map(|entry| (entry.word_id, entry)).collect();
+	write_file(&out_dir.join("data.dictionary"), &data)
+}
+
+/// # Build Frequencies
+/// Estimate word frequencies for `SegmentationMode::Unigram` from a table of character bigram counts,
+/// such as the `zh.profile` corpus profile of the `chinese_detection` crate, and write them to `frequency.dictionary` in `out_dir`.
+/// The words are the simplified headwords of the `simplified.dictionary` already in `out_dir`.
+///
+/// A single character is estimated by half the number of bigrams it appears in, and a longer word by its least common bigram.
+/// Words that never appear in the corpus are left out.
+pub fn build_frequencies<P: AsRef<Path>, Q: AsRef<Path>>(profile: P, out_dir: Q) -> io::Result<()> {
+	let bigrams: HashMap<String, u64> = read_file(profile.as_ref())?;
+	let out_dir = out_dir.as_ref();
+	let headwords = read_index(&out_dir.join("simplified.dictionary"))?;
+
+	let mut characters: HashMap<char, u64> = HashMap::new();
+	for (bigram, count) in &bigrams {
+		for c in bigram.chars() {
+			*characters.entry(c).or_default() += count;
+		}
+	}
+
+	let mut frequencies: HashMap<String, u32> = HashMap::new();
+	for headword in headwords.keys() {
+		let chars: Vec<char> = headword.chars().collect();
+		let frequency = match chars.len() {
+			0 => 0,
+			1 => characters.get(&chars[0]).cloned().unwrap_or(0) / 2,
+			_ => chars.windows(2).map(|pair| bigrams.get(&pair.iter().collect::<String>()).cloned().unwrap_or(0)).min().unwrap_or(0),
+		};
+		if frequency > 0 {
+			frequencies.insert(headword, frequency.min(u64::from(u32::MAX)) as u32);
+		}
+	}
+
+	write_file(&out_dir.join("frequency.dictionary"), &sorted(&frequencies))
+}
+
+/// Pairs of words seen fewer times than this are left out of `bigrams.dictionary`.
+const MIN_BIGRAM_COUNT: u64 = 3;
+
+/// # Build Bigrams
+/// Estimate how often pairs of words are adjacent, for `SegmentationMode::Bigram` and `segmentation_score`, from a table of character bigram counts,
+/// such as the `zh.profile` corpus profile of the `chinese_detection` crate, and write them to `bigrams.dictionary` in `out_dir`.
+/// The words are the simplified headwords of the `simplified.dictionary` already in `out_dir`.
+///
+/// Character bigrams can only count pairs of single character words, so two characters are counted as a pair of words when
+/// each is a word and together they are not, e.g. 的 人 but not 北 京. Pairs seen fewer than three times are left out to keep the model small.
+pub fn build_bigrams<P: AsRef<Path>, Q: AsRef<Path>>(profile: P, out_dir: Q) -> io::Result<()> {
+	let bigrams: HashMap<String, u64> = read_file(profile.as_ref())?;
+	let out_dir = out_dir.as_ref();
+	let headwords = read_index(&out_dir.join("simplified.dictionary"))?;
+
+	let mut pairs: HashMap<(String, String), u32> = HashMap::new();
+	for (bigram, count) in bigrams.iter().filter(|(bigram, count)| **count >= MIN_BIGRAM_COUNT && !headwords.contains_key(bigram)) {
+		let mut characters = bigram.chars().map(String::from);
+		if let (Some(first), Some(second), None) = (characters.next(), characters.next(), characters.next()) {
+			if headwords.contains_key(&first) && headwords.contains_key(&second) {
+				pairs.insert((first, second), (*count).min(u64::from(u32::MAX)) as u32);
+			}
+		}
+	}
+
+	write_file(&out_dir.join("bigrams.dictionary"), &sorted(&pairs))
+}
+
+fn read_file<T: serde::de::DeserializeOwned>(path: &Path) -> io::Result<T> {
+	let reader = BufReader::new(File::open(path)?);
+	deserialize_from(reader).map_err(|error| Error::new(ErrorKind::InvalidData, error))
+}
+
+fn read_index(path: &Path) -> io::Result<KeyIndex> {
+	KeyIndex::from_bytes(&fs::read(path)?).map_err(|error| Error::new(ErrorKind::InvalidData, error))
+}
+
+/// # Read Metadata
+/// Read the `metadata.dictionary` file written by `build_from_cedict`.
+pub fn read_metadata<P: AsRef<Path>>(path: P) -> io::Result<DataMetadata> {
+	read_file(path.as_ref())
+}
+
+/// The entries of a map in key order. Maps are written sorted so that building the same input always produces the same bytes,
+/// since the iteration order of a `HashMap` differs between runs. A `BTreeMap` is encoded the same way, so the files still read back into a `HashMap`.
+fn sorted<K: Ord, V>(map: &HashMap<K, V>) -> BTreeMap<&K, &V> {
+	map.iter().collect()
+}
+
+/// Builds the trie over the simplified and traditional headwords of the indexes, inserting them in the same order `HeadwordTrie::new` does.
+fn build_headword_trie(indexes: &Indexes) -> HeadwordTrie {
+	let mut builder = TrieBuilder::new();
+	for (key, ids) in sorted(&indexes.simplified).into_iter().chain(sorted(&indexes.traditional)) {
+		builder.insert(key, ids);
+	}
+
+	builder.build()
+}
+
+/// Writes a value with the default configuration of `bincode` 1: little endian fixed width integers on every platform.
+fn write_file<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+	let writer = BufWriter::new(File::create(path)?);
+	serialize_into(writer, value).map_err(Error::other)
+}
+
+/// Parses the lines of a CC-CEDICT file into entries. Comment and blank lines are skipped.
+pub(crate) fn parse_cedict(source: &str) -> io::Result<Vec<WordEntry>> {
+	let mut entries: Vec<WordEntry> = Vec::new();
+
+	for (number, line) in source.lines().enumerate() {
+		let line = line.trim();
+		if line.is_empty() || line.starts_with('#') {
+			continue;
+		}
+		match parse_line(line, entries.len() as u32) {
+			Some(entry) => entries.push(entry),
+			None => return Err(Error::new(ErrorKind::InvalidData, format!("Malformed CC-CEDICT entry on line {}", number + 1))),
+		}
+	}
+
+	Ok(entries)
+}
+
+fn parse_line(line: &str, word_id: u32) -> Option<WordEntry> {
+	let mut headwords = line.splitn(3, ' ');
+	let traditional = headwords.next()?.to_string();
+	let simplified = headwords.next()?.to_string();
+	let rest = headwords.next()?;
+	let pinyin_end = rest.find(']')?;
+	let pinyin_numbers = rest.get(1..pinyin_end)?.to_string();
+	let glosses = rest[pinyin_end + 1..].trim().trim_matches('/');
+
+	let mut entry = WordEntry {
+		hash: 0,
+		pinyin_marks: numbers_to_marks(&pinyin_numbers),
+		tone_marks: pinyin_numbers.split(' ').filter_map(|syllable| split_tone_number(syllable).1).collect(),
+		traditional,
+		simplified,
+		pinyin_numbers,
+		english: glosses.split('/').map(String::from).collect(),
+		measure_words: Vec::new(),
+		hsk: 0,
+		word_id,
+		domain: None,
+		year: None,
+		source: SourceId::CcCedict,
+	};
+	extract_measure_words(&mut entry);
+	entry.hash = entry_hash(&entry);
+
+	Some(entry)
+}
+
+/// Moves the measure words written in the glosses of an entry into `measure_words`, either as a gloss of their own, such as `CL:個|个[ge4]`,
+/// or at the end of a sense, such as `step in a procedure (CL:道[dao4])`.
+fn extract_measure_words(entry: &mut WordEntry) {
+	if !entry.english.iter().any(|gloss| gloss.contains("CL:")) {
+		return;
+	}
+
+	let mut english: Vec<String> = Vec::new();
+	let mut references: Vec<&str> = Vec::new();
+	for gloss in &entry.english {
+		if let Some(classifiers) = gloss.trim().strip_prefix("CL:") {
+			references.extend(classifiers.split(','));
+			continue;
+		}
+		match gloss.find("(CL:") {
+			Some(start) => {
+				let end = gloss[start..].find(')').map_or(gloss.len(), |end| start + end + 1);
+				references.extend(gloss[start + 4..end].trim_end_matches(')').split(','));
+				// A gloss that was nothing but the measure words is dropped rather than left empty
+				let rest = format!("{} {}", gloss[..start].trim_end(), gloss[end..].trim_start()).trim().to_string();
+				if !rest.is_empty() {
+					english.push(rest);
+				}
+			}
+			None => english.push(gloss.clone()),
+		}
+	}
+	let mut measure_words = entry.measure_words.clone();
+	for measure_word in references.into_iter().filter_map(|reference| parse_measure_word(reference.trim())) {
+		if !measure_words.contains(&measure_word) {
+			measure_words.push(measure_word);
+		}
+	}
+	entry.english = english;
+	entry.measure_words = measure_words;
+}
+
+/// Parses a measure word reference such as `個|个[ge4]` or `本[ben3]`.
+fn parse_measure_word(reference: &str) -> Option<MeasureWord> {
+	let open = reference.find('[')?;
+	let characters = &reference[..open];
+	let pinyin_numbers = reference[open + 1..].trim_end_matches(']').to_string();
+	let (traditional, simplified) = match characters.find('|') {
+		Some(bar) => (&characters[..bar], &characters[bar + 1..]),
+		None => (characters, characters),
+	};
+
+	Some(MeasureWord {
+		traditional: traditional.to_string(),
+		simplified: simplified.to_string(),
+		pinyin_marks: numbers_to_marks(&pinyin_numbers),
+		pinyin_numbers,
+	})
+}
+
+impl Indexes {
+	pub(crate) fn new() -> Indexes {
+		Indexes {
+			traditional: HashMap::new(),
+			simplified: HashMap::new(),
+			pinyin: HashMap::new(),
+			english: HashMap::new(),
+		}
+	}
+
+	/// Adds the index keys of an entry.
+	pub(crate) fn add(&mut self, entry: &WordEntry) {
+		let id = entry.word_id;
+		self.traditional.entry(entry.traditional.clone()).or_default().push(id);
+		self.simplified.entry(entry.simplified.clone()).or_default().push(id);
+		for key in pinyin_keys(entry) {
+			self.pinyin.entry(key).or_default().push(id);
+		}
+		for gloss in &entry.english {
+			let key = english_key(gloss);
+			if let Some(stripped) = key.strip_prefix("to%20") {
+				self.english.entry(stripped.to_string()).or_default().push(id);
+			}
+			self.english.entry(key).or_default().push(id);
+		}
+	}
+}
+
+/// Builds the lookup indexes for a set of entries.
+pub(crate) fn build_indexes<'a, I: IntoIterator<Item = &'a WordEntry>>(entries: I) -> Indexes {
+	let mut indexes = Indexes::new();
+	for entry in entries {
+		indexes.add(entry);
+	}
+
+	indexes
+}
+
+/// Adds entries to existing data and indexes, skipping any entry whose headwords and pinyin are already present.
+/// Added entries are given new ids, counting up from one past the largest id in use.
+/// Returns the id of each entry, which is the id of the entry already present for skipped entries.
+pub(crate) fn supplement(indexes: &mut Indexes, data: &mut HashMap<u32, WordEntry>, entries: Vec<WordEntry>) -> Vec<u32> {
+	if entries.is_empty() {
+		return Vec::new();
+	}
+
+	let mut existing: HashMap<(String, String, String), u32> = data.values()
+		.map(|entry| ((entry.traditional.clone(), entry.simplified.clone(), entry.pinyin_numbers.clone()), entry.word_id))
+		.collect();
+	let mut next_id = data.keys().max().map_or(0, |id| id + 1);
+	let mut ids: Vec<u32> = Vec::with_capacity(entries.len());
+
+	for mut entry in entries {
+		let key = (entry.traditional.clone(), entry.simplified.clone(), entry.pinyin_numbers.clone());
+		if let Some(id) = existing.get(&key) {
+			ids.push(*id);
+			continue;
+		}
+		entry.word_id = next_id;
+		next_id += 1;
+		existing.insert(key, entry.word_id);
+		ids.push(entry.word_id);
+		indexes.add(&entry);
+		data.insert(entry.word_id, entry);
+	}
+
+	ids
+}
+
+/// The pinyin index keys for an entry: tone numbers, no tones, and tone marks, all lowercase without spaces.
+fn pinyin_keys(entry: &WordEntry) -> Vec<String> {
+	let numbers = entry.pinyin_numbers.to_lowercase().replace(' ', "");
+	let toneless = numbers.chars().filter(|c| !('1'..='5').contains(c)).collect();
+	let marks = entry.pinyin_marks.to_lowercase().replace(' ', "");
+	vec![numbers, toneless, marks]
+}
+
+/// The initials index key for an entry: the first letter of each syllable, lowercase, e.g. `"bj"` for `"Bei3 jing1"`.
+/// Syllables that do not start with a letter, such as the `·` in transliterated names, are skipped.
+pub(crate) fn initials_key(entry: &WordEntry) -> String {
+	entry.pinyin_numbers.split(' ')
+		.filter_map(|syllable| syllable.chars().next())
+		.filter(|c| c.is_ascii_alphabetic())
+		.map(|c| c.to_ascii_lowercase())
+		.collect()
+}
+
+/// Normalizes an English gloss into an index key.
+/// The first parenthetical note and the space before it are dropped, everything but letters and `|` is removed, and words are joined with `%20`.
+pub(crate) fn english_key(gloss: &str) -> String {
+	let without_note = match gloss.find('(').and_then(|open| gloss[open..].find(')').map(|close| (open, open + close))) {
+		Some((open, close)) => format!("{}{}", gloss[..open].strip_suffix(' ').unwrap_or(&gloss[..open]), &gloss[close + 1..]),
+		None => gloss.to_string(),
+	};
+
+	without_note.trim()
+		.to_lowercase()
+		.chars()
+		.filter(|c| c.is_alphabetic() || *c == ' ' || *c == '|')
+		.collect::<String>()
+		.replace(' ', "%20")
+}
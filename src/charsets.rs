@@ -0,0 +1,102 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Coverage of text by the legacy GB2312, GBK, and Big5 character sets
+
+use std::sync::OnceLock;
+
+/// The non-ASCII code points of each character set, as one hexadecimal code point or range such as `4E00-4E01` per line.
+/// They were generated from the codecs of the Python standard library, and leave out vendor specific Private Use Area mappings.
+static GB2312: &str = include_str!("../data/charsets/gb2312.txt");
+static GBK: &str = include_str!("../data/charsets/gbk.txt");
+static BIG5: &str = include_str!("../data/charsets/big5.txt");
+
+/// A legacy Chinese character set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Charset {
+	/// The 1980 Simplified Chinese standard, with 6,763 characters
+	Gb2312,
+	/// The extension of GB2312 to all of the CJK Unified Ideographs of Unicode 1.1, as used by Windows code page 936
+	Gbk,
+	/// The Traditional Chinese standard used in Taiwan and Hong Kong, without the Hong Kong Supplementary Character Set
+	Big5,
+}
+
+impl Charset {
+	/// # Contains
+	/// Whether a character can be encoded in this character set. ASCII characters always can.
+	pub fn contains(self, character: char) -> bool {
+		static RANGES: [OnceLock<Vec<(u32, u32)>>; 3] = [OnceLock::new(), OnceLock::new(), OnceLock::new()];
+		if character.is_ascii() {
+			return true;
+		}
+		let (ranges, source) = match self {
+			Charset::Gb2312 => (&RANGES[0], GB2312),
+			Charset::Gbk => (&RANGES[1], GBK),
+			Charset::Big5 => (&RANGES[2], BIG5),
+		};
+		let ranges = ranges.get_or_init(|| parse_ranges(source));
+		let code_point = character as u32;
+		let position = ranges.partition_point(|(_, end)| *end < code_point);
+
+		ranges.get(position).is_some_and(|(start, _)| *start <= code_point)
+	}
+}
+
+/// The characters of a text that cannot be encoded in each legacy character set, with their byte offsets.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CharsetReport {
+	/// The characters outside GB2312
+	pub gb2312: Vec<(usize, char)>,
+	/// The characters outside GBK
+	pub gbk: Vec<(usize, char)>,
+	/// The characters outside Big5
+	pub big5: Vec<(usize, char)>,
+}
+
+impl CharsetReport {
+	/// # Outside
+	/// The characters of the text that cannot be encoded in `charset`, with their byte offsets.
+	pub fn outside(&self, charset: Charset) -> &[(usize, char)] {
+		match charset {
+			Charset::Gb2312 => &self.gb2312,
+			Charset::Gbk => &self.gbk,
+			Charset::Big5 => &self.big5,
+		}
+	}
+
+	/// # Is Encodable
+	/// Whether every character of the text can be encoded in `charset`.
+	pub fn is_encodable(&self, charset: Charset) -> bool {
+		self.outside(charset).is_empty()
+	}
+}
+
+/// # Charset Report
+/// Find the characters of `text` that cannot be encoded in GB2312, GBK, or Big5, such as when preparing text for a legacy system or subsetting a font.
+/// For example, 镕 is in GBK but in neither GB2312 nor Big5, and 丟 is in Big5 and GBK but not GB2312.
+pub fn charset_report(text: &str) -> CharsetReport {
+	let mut report = CharsetReport::default();
+	for (offset, character) in text.char_indices().filter(|(_, character)| !character.is_ascii()) {
+		for charset in [Charset::Gb2312, Charset::Gbk, Charset::Big5] {
+			if !charset.contains(character) {
+				match charset {
+					Charset::Gb2312 => report.gb2312.push((offset, character)),
+					Charset::Gbk => report.gbk.push((offset, character)),
+					Charset::Big5 => report.big5.push((offset, character)),
+				}
+			}
+		}
+	}
+	report
+}
+
+fn parse_ranges(source: &str) -> Vec<(u32, u32)> {
+	source.lines()
+		.filter(|line| !line.is_empty() && !line.starts_with('#'))
+		.map(|line| {
+			let (start, end) = line.split_once('-').unwrap_or((line, line));
+			let parse = |hex: &str| u32::from_str_radix(hex, 16).expect("Bundled character sets are hexadecimal code points");
+			(parse(start), parse(end))
+		})
+		.collect()
+}
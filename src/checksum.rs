@@ -0,0 +1,32 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: SHA-256 checksums of the embedded dictionary data
+
+use crate::chinese_dictionary::embedded_files;
+use sha2::{Digest, Sha256};
+
+/// # Data Checksums
+/// The SHA-256 checksum of each data file embedded in the crate, in lowercase hexadecimal, with the file's name in `data/`.
+/// They match the output of `sha256sum` on the files.
+///
+/// Every embedded file is written by `chinese_dictionary_builder`, which always writes the same bytes for the same input, so the embedded data
+/// can be checked by rebuilding it. `data.dictionary` holds the CC-CEDICT entries of the 1.0.1 release along with their HSK levels,
+/// which CC-CEDICT does not include, so it cannot be reproduced from a CC-CEDICT release. The indexes, the headword trie, and `data.dictionary`
+/// itself are rebuilt from it with `build_from_data`, and the word frequencies and bigram counts from the `zh.profile` of `chinese_detection` 1.0.0.
+pub fn data_checksums() -> Vec<(&'static str, String)> {
+	embedded_files().iter().map(|(name, bytes)| (*name, sha256(bytes))).collect()
+}
+
+/// # Data Checksum
+/// A single SHA-256 checksum of all of the embedded data: the checksum of the `sha256sum` listing of the files in `data_checksums`,
+/// with two spaces between each checksum and file name and a newline after each line. From the `data/` directory, it is the output of
+/// `sha256sum traditional.dictionary simplified.dictionary pinyin.dictionary english.dictionary headwords.dictionary data.dictionary frequency.dictionary bigrams.dictionary | sha256sum`.
+pub fn data_checksum() -> String {
+	let listing: String = data_checksums().iter().map(|(name, checksum)| format!("{}  {}\n", checksum, name)).collect();
+	sha256(listing.as_bytes())
+}
+
+/// The SHA-256 digest of a message in lowercase hexadecimal.
+pub(crate) fn sha256(message: &[u8]) -> String {
+	Sha256::digest(message).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
@@ -5,18 +5,65 @@
 use bincode::deserialize_from;
 use character_converter::CharacterConverter;
 use chinese_detection::ChineseDetection;
-use serde_derive::Deserialize;
-use std::collections::HashMap;
+use crate::builder::{build_indexes, english_key, initials_key, supplement, Indexes};
+use crate::classical;
+use crate::classification::ClassificationOptions;
+use crate::collocations::Collocations;
+use crate::confusables::is_confusable;
+use crate::explain::{self, TraceEvent};
+use crate::gazetteer;
+use crate::hash::entry_hash;
+use crate::index::{IndexKeys, KeyIndex};
+use crate::input_codes::InputCodes;
+use crate::language_model::Bigrams;
+use crate::limits::InputLimits;
+use crate::metadata::{DataDiff, DataMetadata};
+use crate::neologisms;
+use crate::normalizer::Normalizer;
+use crate::pinyin::{edit_distance, normalize_neutral_tone, normalize_umlaut, UmlautStyle};
+use crate::query_language::{self, Clause, BOOLEAN_OPERATORS};
+use crate::query_options::QueryOptions;
+use crate::ranking::{self, QueryContext, Ranker};
+use crate::rhymes::rhyme_key;
+use crate::segmentation_filter::SegmentationFilter;
+use crate::source::{Source, SourceId};
+use crate::terminology::{self, Domain};
+use crate::trie::{HeadwordTrie, TrieBuilder};
+use crate::sentences::{is_closing, is_opening, is_trailing};
+use crate::vocabulary::{Region, Vocabulary};
+use serde_derive::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::{Range, RangeBounds};
+use std::sync::{Arc, OnceLock};
 pub use chinese_detection::ClassificationResult;
 
-static TRADITIONAL: &'static [u8] = include_bytes!("../data/traditional.dictionary");
-static SIMPLIFIED: &'static [u8] = include_bytes!("../data/simplified.dictionary");
-static PINYIN: &'static [u8] = include_bytes!("../data/pinyin.dictionary");
-static ENGLISH: &'static [u8] = include_bytes!("../data/english.dictionary");
-static DATA: &'static [u8] = include_bytes!("../data/data.dictionary");
+static TRADITIONAL: &[u8] = include_bytes!("../data/traditional.dictionary");
+static SIMPLIFIED: &[u8] = include_bytes!("../data/simplified.dictionary");
+static PINYIN: &[u8] = include_bytes!("../data/pinyin.dictionary");
+static ENGLISH: &[u8] = include_bytes!("../data/english.dictionary");
+static DATA: &[u8] = include_bytes!("../data/data.dictionary");
+static FREQUENCY: &[u8] = include_bytes!("../data/frequency.dictionary");
+static HEADWORDS: &[u8] = include_bytes!("../data/headwords.dictionary");
+static BIGRAMS: &[u8] = include_bytes!("../data/bigrams.dictionary");
 static ENGLISH_MAX_LENGTH: usize = 4;
+static DEFAULT_MAX_WORD_LENGTH: usize = 20;
 
-#[derive(Deserialize, Debug)]
+/// The data files embedded in the crate, with their names in `data/`.
+pub(crate) fn embedded_files() -> [(&'static str, &'static [u8]); 8] {
+	[
+		("traditional.dictionary", TRADITIONAL),
+		("simplified.dictionary", SIMPLIFIED),
+		("pinyin.dictionary", PINYIN),
+		("english.dictionary", ENGLISH),
+		("headwords.dictionary", HEADWORDS),
+		("data.dictionary", DATA),
+		("frequency.dictionary", FREQUENCY),
+		("bigrams.dictionary", BIGRAMS),
+	]
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct MeasureWord {
 	pub traditional: String,
 	pub simplified: String, 
@@ -24,7 +71,7 @@ pub struct MeasureWord {
 	pub pinyin_numbers: String
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct WordEntry {
 	pub traditional: String,
 	pub simplified: String,
@@ -32,40 +79,631 @@ pub struct WordEntry {
 	pub pinyin_numbers: String,
 	pub english: Vec<String>,
 	pub tone_marks: Vec<u8>, 
+	/// A stable identifier for the word: the 64-bit FNV-1a hash of `traditional`, `simplified`, and `pinyin_numbers`,
+	/// each followed by a `0xFF` byte. It does not change between releases unless the headwords or pinyin change,
+	/// so unlike `word_id` it can be stored and looked up again with `entry_by_hash`.
 	pub hash: u64,
 	pub measure_words: Vec<MeasureWord>,
 	pub hsk: u8,
-	pub word_id: u32
+	pub word_id: u32,
+	/// The professional domain of the word, for entries from a terminology pack. This is not stored in the data files.
+	#[serde(skip)]
+	pub domain: Option<Domain>,
+	/// The year the word came into wide use, for neologisms and Internet slang tagged by the `neologisms` feature.
+	/// This is not stored in the data files.
+	#[serde(skip)]
+	pub year: Option<u16>,
+	/// Where the entry came from. This is not stored in the data files.
+	#[serde(skip)]
+	pub source: SourceId,
+}
+
+/// The kind of a `Span` produced by `highlight_known_words`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpanKind {
+	/// A dictionary word the user already knows
+	Known,
+	/// A dictionary word the user has not learned yet
+	New,
+	/// Text that did not match any dictionary word, such as punctuation or Latin characters
+	Unmatched,
+}
+
+/// A byte range of the original text along with its `SpanKind`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+	pub range: Range<usize>,
+	pub kind: SpanKind,
+}
+
+/// How `Dictionary::merge` decides that entries from different dictionaries describe the same word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+	/// Entries with the same `word_id` are the same word
+	WordId,
+	/// Entries with the same traditional headword, simplified headword, and `pinyin_numbers` are the same word
+	HeadwordPinyin,
+}
+
+/// How `segment` and the other segmentation based methods choose between overlapping words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SegmentationMode {
+	/// Take the longest dictionary word at each position, from left to right
+	#[default]
+	LongestMatch,
+	/// Choose the most probable sequence of words according to their frequencies.
+	/// This resolves ambiguities the longest match gets wrong, such as 结婚的和尚未结婚的, which is split as 和 尚未 rather than 和尚 未.
+	Unigram,
+	/// Prefer single characters, as Classical Chinese (文言) is mostly written with single character words.
+	/// Longer words are only taken when they are proper nouns, such as 孔子, or are in the glossary of the `classical` feature, such as 君子 and 何以.
+	Classical,
+	/// Choose the most probable sequence of words according to how often each word follows the one before it, falling back to their frequencies.
+	/// The built-in counts only cover single character words, e.g. 的 人, so with them it mostly agrees with `Unigram`. Set counts from a segmented corpus with `set_bigrams`.
+	Bigram,
+}
+
+/// One of the indexes of the dictionary, which are each loaded the first time they are needed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Index {
+	/// Traditional headwords, used to query and segment traditional characters
+	Traditional,
+	/// Simplified headwords, used to query and segment simplified characters
+	Simplified,
+	/// Pinyin, used to query by pinyin
+	Pinyin,
+	/// English glosses, used to query by English
+	English,
+}
+
+/// A Chinese writing system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script {
+	Simplified,
+	Traditional,
+}
+
+/// Which headword of an entry matched a word in `Dictionary::query_by_chinese_both`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScriptMatch {
+	/// The simplified headword matched, and the traditional one differs
+	Simplified,
+	/// The traditional headword matched, and the simplified one differs
+	Traditional,
+	/// Both headwords matched, since the word is written the same in both scripts
+	Both,
+}
+
+/// A single sense of a `WordEntry`, one of its English glosses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sense<'a> {
+	/// The position of the gloss within `WordEntry::english`
+	pub index: usize,
+	pub gloss: &'a str,
+}
+
+impl WordEntry {
+	/// # Senses
+	/// Iterate over the senses of this entry, in the order they appear in `english`.
+	pub fn senses(&self) -> impl Iterator<Item = Sense<'_>> {
+		self.english.iter().enumerate().map(|(index, gloss)| Sense { index, gloss })
+	}
+
+	/// # Is Neologism
+	/// Whether this entry is a neologism or piece of Internet slang with a known `year`.
+	pub fn is_neologism(&self) -> bool {
+		self.year.is_some()
+	}
+}
+
+/// The entries matching one phrase of an English query, as returned by `query_by_english_grouped`.
+#[derive(Debug)]
+pub struct EnglishGroup<'a> {
+	/// The words of the query that make up the phrase, joined with spaces
+	pub phrase: String,
+	/// The positions of the phrase's words among the words of the query
+	pub words: Range<usize>,
+	pub entries: Vec<&'a WordEntry>,
+}
+
+/// An entry returned by `query_by_english_senses`, along with the indices of the senses that matched the query.
+#[derive(Debug)]
+pub struct SenseMatch<'a> {
+	pub entry: &'a WordEntry,
+	pub senses: Vec<usize>,
 }
 
 pub struct Dictionary {
-	traditional: HashMap<String, Vec<u32>>,
-	simplified: HashMap<String, Vec<u32>>,
-	pinyin: HashMap<String, Vec<u32>>,
-	english: HashMap<String, Vec<u32>>,
-	data: HashMap<u32, WordEntry>,
-	character_util: CharacterConverter,
-	language_util: ChineseDetection,
+	traditional: OnceLock<KeyIndex>,
+	simplified: OnceLock<KeyIndex>,
+	pinyin: OnceLock<KeyIndex>,
+	english: OnceLock<KeyIndex>,
+	/// Each word of the English index keys, with the ids of the entries that have a gloss containing it, for boolean English queries
+	english_words: OnceLock<KeyIndex>,
+	initials: OnceLock<KeyIndex>,
+	rhymes: OnceLock<KeyIndex>,
+	headwords: OnceLock<HeadwordTrie>,
+	classical_headwords: OnceLock<HeadwordTrie>,
+	/// Whether the indexes that have not been loaded yet come from the built-in data
+	bundled: bool,
+	data: HashMap<u32, Arc<WordEntry>>,
+	hashes: HashMap<u64, u32>,
+	/// Shared with the dictionaries created from this one by `merge`, since loading the conversion and detection data takes seconds
+	character_util: Arc<CharacterConverter>,
+	language_util: Arc<ChineseDetection>,
+	classification_options: ClassificationOptions,
+	normalizer: Normalizer,
+	query_options: QueryOptions,
+	input_limits: InputLimits,
+	vocabularies: HashMap<Region, Vocabulary>,
+	variant_preference: Option<Region>,
+	input_codes: InputCodes,
+	collocations: Collocations,
+	segmentation_mode: SegmentationMode,
+	/// The longest word, in characters, that segmentation considers
+	max_word_length: usize,
+	segmentation_filter: SegmentationFilter,
+	frequencies: HashMap<String, u32>,
+	frequency_total: f64,
+	bigrams: Bigrams,
+	ranker: Option<Ranker>,
+	/// The ids of the classical glossary entries, keyed by their simplified and traditional headwords
+	classical: HashMap<String, Vec<u32>>,
+}
+
+impl Default for Dictionary {
+	fn default() -> Dictionary {
+		Dictionary::new_builtin()
+	}
 }
 
 impl Dictionary {
+	/// # New
+	/// Load the built-in CC-CEDICT dictionary. The same as `new_builtin`.
 	pub fn new() -> Dictionary {
-		Dictionary {
-			traditional: deserialize_from(TRADITIONAL).unwrap(),
-			simplified: deserialize_from(SIMPLIFIED).unwrap(),
-			pinyin: deserialize_from(PINYIN).unwrap(),
-			english: deserialize_from(ENGLISH).unwrap(),
-			data: deserialize_from(DATA).unwrap(),
-			character_util: CharacterConverter::new(), // This operation takes 1.5 seconds to complete
-			language_util: ChineseDetection::new(), // This operation takes 2 seconds to complete
+		Dictionary::new_builtin()
+	}
+
+	/// # New Builtin
+	/// Load the built-in CC-CEDICT dictionary.
+	/// When the `gazetteer-places` or `gazetteer-brands` features are enabled, their entries are added to the dictionary
+	/// and are available to both queries and segmentation. The same goes for the `terms-medical`, `terms-legal`, and `terms-it`
+	/// terminology packs, whose entries are also tagged with their `domain`, and the `neologisms` list, whose entries are
+	/// tagged with their `year` whether or not CC-CEDICT already has them. The entries of the `classical` glossary are kept
+	/// apart from the rest, and are only used by `query_classical` and `SegmentationMode::Classical`.
+	///
+	/// Each index is only loaded the first time it is used, so a program that only segments simplified text never pays
+	/// to load the pinyin or English indexes. Use `init_only` to load the indexes a program needs up front instead.
+	pub fn new_builtin() -> Dictionary {
+		// The indexes are loaded lazily, and pick up the supplemented entries when they are
+		let mut indexes = Indexes::new();
+		let mut data: HashMap<u32, WordEntry> = deserialize_from(DATA).unwrap();
+		for entry in data.values_mut() {
+			entry.source = SourceId::CcCedict;
+		}
+		supplement(&mut indexes, &mut data, gazetteer::entries());
+		for (domain, entries) in terminology::packs() {
+			for id in supplement(&mut indexes, &mut data, entries) {
+				data.get_mut(&id).unwrap().domain.get_or_insert(domain);
+			}
+		}
+		let (years, entries): (Vec<u16>, Vec<WordEntry>) = neologisms::entries().into_iter().unzip();
+		for (id, year) in supplement(&mut indexes, &mut data, entries).into_iter().zip(years) {
+			data.get_mut(&id).unwrap().year = Some(year);
+		}
+		// Classical senses are separate entries even when CC-CEDICT has the same headword and pinyin
+		let next_id = data.keys().max().map_or(0, |id| id + 1);
+		for (word_id, mut entry) in (next_id..).zip(classical::entries()) {
+			entry.word_id = word_id;
+			data.insert(word_id, entry);
+		}
+
+		let mut dictionary = Dictionary::from_indexes(None, data, None);
+		dictionary.set_frequencies(deserialize_from(FREQUENCY).unwrap());
+		dictionary.set_bigrams(deserialize_from(BIGRAMS).unwrap());
+		dictionary.set_collocation_data(Collocations::bundled());
+		dictionary
+	}
+
+	/// # Init Only
+	/// Load the built-in dictionary, the same as `new_builtin`, along with the given indexes up front,
+	/// e.g. `Dictionary::init_only(&[Index::Simplified, Index::English])`.
+	/// Loading the indexes a program needs during startup keeps the first query fast. Any other index is still loaded the first time it is used.
+	pub fn init_only(indexes: &[Index]) -> Dictionary {
+		let dictionary = Dictionary::new_builtin();
+		for index in indexes {
+			dictionary.index(*index);
+		}
+		dictionary
+	}
+
+	/// # From Entries
+	/// Create an isolated dictionary containing only the given entries, indexed the same way as the built-in data.
+	/// Each entry should have a unique `word_id`; when several entries share one, the last is kept.
+	/// Entries keep their `source`, so entries copied from the built-in data and edited should be given `SourceId::User`.
+	pub fn from_entries(entries: Vec<WordEntry>) -> Dictionary {
+		Dictionary::indexed(entries, None)
+	}
+
+	/// Indexes the given entries the same way as the built-in data, with the configuration of `base` when it is given.
+	fn indexed(entries: Vec<WordEntry>, base: Option<&Dictionary>) -> Dictionary {
+		let data: HashMap<u32, WordEntry> = entries.into_iter().map(|entry| (entry.word_id, entry)).collect();
+		let mut ordered: Vec<&WordEntry> = data.values().filter(|entry| entry.source != SourceId::ClassicalGlossary).collect();
+		ordered.sort_by_key(|entry| entry.word_id);
+		let indexes = build_indexes(ordered);
+
+		Dictionary::from_indexes(Some(indexes), data, base)
+	}
+
+	/// # Merge
+	/// Combine several dictionaries into a new one. Dictionaries earlier in the slice take priority:
+	/// when two entries conflict according to `strategy`, the entry from the earlier dictionary is kept.
+	/// Put a user dictionary first to shadow the built-in data, or last to only supplement it.
+	///
+	/// With `MergeStrategy::HeadwordPinyin`, an entry whose `word_id` is already used by a different word
+	/// is given a new id greater than every id in use.
+	///
+	/// The merged dictionary takes its configuration from the first dictionary in the slice: its normalizer, query and classification options,
+	/// input limits, segmentation settings, ranker, collocations, input codes, variant preference, and vocabulary additions.
+	/// Word frequencies and bigram counts are combined from every dictionary, preferring the counts of earlier ones.
+	pub fn merge(dictionaries: &[&Dictionary], strategy: MergeStrategy) -> Dictionary {
+		let sources: Vec<Vec<&WordEntry>> = dictionaries.iter().map(|dictionary| dictionary.entries().collect()).collect();
+		let mut dictionary = Dictionary::indexed(merge_entries(sources, strategy), dictionaries.first().copied());
+
+		let mut frequencies: HashMap<String, u32> = HashMap::new();
+		let mut bigrams = Bigrams::default();
+		for source in dictionaries.iter().rev() {
+			frequencies.extend(source.frequencies.iter().map(|(word, frequency)| (word.clone(), *frequency)));
+			for (previous, word, count) in source.bigrams.iter() {
+				bigrams.insert(previous, word, count);
+			}
+		}
+		dictionary.set_frequencies(frequencies);
+		dictionary.bigrams = bigrams;
+		dictionary
+	}
+
+	/// A copy of this dictionary with `entries` layered over its own, as if they were merged in front of it with `merge`.
+	/// The copy keeps the configuration, word frequencies, and bigram counts of this dictionary, and shares its character data.
+	pub(crate) fn overlay(&self, entries: &[WordEntry], strategy: MergeStrategy) -> Dictionary {
+		let merged = merge_entries(vec![entries.iter().collect(), self.entries().collect()], strategy);
+		Dictionary::indexed(merged, Some(self))
+	}
+
+	/// # Entries
+	/// Iterate over every entry in the dictionary, in no particular order.
+	pub fn entries(&self) -> impl Iterator<Item = &WordEntry> {
+		self.data.values().map(Arc::as_ref)
+	}
+
+	/// # Sources
+	/// Attribution details for every source with entries in this dictionary, such as CC-CEDICT and any enabled gazetteers.
+	/// Applications displaying entries should credit these sources according to their licenses.
+	pub fn sources(&self) -> Vec<Source> {
+		let present: HashSet<SourceId> = self.data.values().map(|entry| entry.source).collect();
+		SourceId::all().iter().filter(|id| present.contains(id)).map(|id| id.source()).collect()
+	}
+
+	/// # Entry by ID
+	/// Look up an entry by its `word_id`.
+	pub fn entry_by_id(&self, word_id: u32) -> Option<&WordEntry> {
+		self.data.get(&word_id).map(Arc::as_ref)
+	}
+
+	/// # Entry by Hash
+	/// Look up an entry by its `hash`. If more than one entry shares the same headwords and pinyin, the one with the lowest `word_id` is returned.
+	pub fn entry_by_hash(&self, hash: u64) -> Option<&WordEntry> {
+		self.hashes.get(&hash).and_then(|word_id| self.data.get(word_id)).map(Arc::as_ref)
+	}
+
+	/// # Metadata
+	/// A content hash of every entry in this dictionary, which can be stored and later passed to `diff_data`.
+	pub fn metadata(&self) -> DataMetadata {
+		DataMetadata::from_entries(self.entries())
+	}
+
+	/// # Diff Data
+	/// The `word_id`s added, removed, or changed in this dictionary since the version described by `other`.
+	/// Apps that cache entries locally can use this to fetch only the entries that changed.
+	pub fn diff_data(&self, other: &DataMetadata) -> DataDiff {
+		self.metadata().diff(other)
+	}
+
+	/// Creates a dictionary from its data and indexes. Without indexes, they are loaded from the built-in data when first used.
+	/// With a `base` dictionary, its configuration and character data are reused instead of the defaults.
+	fn from_indexes(indexes: Option<Indexes>, mut data: HashMap<u32, WordEntry>, base: Option<&Dictionary>) -> Dictionary {
+		let mut hashes: HashMap<u64, u32> = HashMap::with_capacity(data.len());
+		let mut classical: HashMap<String, Vec<u32>> = HashMap::new();
+		for entry in data.values_mut() {
+			entry.hash = entry_hash(entry);
+			let word_id = hashes.entry(entry.hash).or_insert(entry.word_id);
+			*word_id = (*word_id).min(entry.word_id);
+			if entry.source == SourceId::ClassicalGlossary {
+				classical.entry(entry.simplified.clone()).or_default().push(entry.word_id);
+				if entry.traditional != entry.simplified {
+					classical.entry(entry.traditional.clone()).or_default().push(entry.word_id);
+				}
+			}
+		}
+		for ids in classical.values_mut() {
+			ids.sort_unstable();
+		}
+		let bundled = indexes.is_none();
+		let (traditional, simplified, pinyin, english) = match indexes {
+			Some(indexes) => (
+				OnceLock::from(KeyIndex::new(indexes.traditional)),
+				OnceLock::from(KeyIndex::new(indexes.simplified)),
+				OnceLock::from(KeyIndex::new(indexes.pinyin)),
+				OnceLock::from(KeyIndex::new(indexes.english)),
+			),
+			None => (OnceLock::new(), OnceLock::new(), OnceLock::new(), OnceLock::new()),
+		};
+
+		let (character_util, language_util) = match base {
+			Some(base) => (Arc::clone(&base.character_util), Arc::clone(&base.language_util)),
+			None => (
+				Arc::new(CharacterConverter::new()), // This operation takes 1.5 seconds to complete
+				Arc::new(ChineseDetection::new()), // This operation takes 2 seconds to complete
+			),
+		};
+
+		let mut dictionary = Dictionary {
+			traditional,
+			simplified,
+			pinyin,
+			english,
+			english_words: OnceLock::new(),
+			initials: OnceLock::new(),
+			rhymes: OnceLock::new(),
+			headwords: OnceLock::new(),
+			classical_headwords: OnceLock::new(),
+			bundled,
+			data: data.into_iter().map(|(word_id, entry)| (word_id, Arc::new(entry))).collect(),
+			hashes,
+			character_util,
+			language_util,
+			classification_options: ClassificationOptions::default(),
+			normalizer: Normalizer::default(),
+			query_options: QueryOptions::default(),
+			input_limits: InputLimits::default(),
+			segmentation_mode: SegmentationMode::default(),
+			max_word_length: DEFAULT_MAX_WORD_LENGTH,
+			segmentation_filter: SegmentationFilter::default(),
+			frequencies: HashMap::new(),
+			frequency_total: 0.0,
+			bigrams: Bigrams::default(),
+			ranker: None,
+			classical,
+			input_codes: InputCodes::default(),
+			collocations: Collocations::default(),
+			variant_preference: None,
+			vocabularies: HashMap::new(),
+		};
+		match base {
+			Some(base) => dictionary.copy_configuration(base),
+			None => dictionary.vocabularies = [Region::Taiwan, Region::HongKong].iter().map(|region| (*region, Vocabulary::bundled(*region))).collect(),
+		}
+		dictionary
+	}
+
+	/// Copies every setting that is not derived from the entries from another dictionary, along with its word frequencies and bigram counts.
+	fn copy_configuration(&mut self, base: &Dictionary) {
+		self.classification_options = base.classification_options.clone();
+		self.normalizer = base.normalizer.clone();
+		self.query_options = base.query_options.clone();
+		self.input_limits = base.input_limits;
+		self.vocabularies = base.vocabularies.clone();
+		self.variant_preference = base.variant_preference;
+		self.input_codes = base.input_codes.clone();
+		self.collocations = base.collocations.clone();
+		self.segmentation_mode = base.segmentation_mode;
+		self.max_word_length = base.max_word_length;
+		self.segmentation_filter = base.segmentation_filter.clone();
+		self.frequencies = base.frequencies.clone();
+		self.frequency_total = base.frequency_total;
+		self.bigrams = base.bigrams.clone();
+		self.ranker = base.ranker.clone();
+	}
+
+	/// An index, loading it first if it has not been used yet.
+	fn index(&self, index: Index) -> &KeyIndex {
+		let cell = match index {
+			Index::Traditional => &self.traditional,
+			Index::Simplified => &self.simplified,
+			Index::Pinyin => &self.pinyin,
+			Index::English => &self.english,
+		};
+		cell.get_or_init(|| self.load_index(index))
+	}
+
+	/// Loads an index from the built-in data, along with the entries added to it by the gazetteers and terminology packs.
+	/// The classical glossary is left out of every index.
+	fn load_index(&self, index: Index) -> KeyIndex {
+		debug_assert!(self.bundled);
+		let bundled = KeyIndex::from_static(match index {
+			Index::Traditional => TRADITIONAL,
+			Index::Simplified => SIMPLIFIED,
+			Index::Pinyin => PINYIN,
+			Index::English => ENGLISH,
+		}).unwrap();
+		let mut supplemented: Vec<&WordEntry> = self.entries()
+			.filter(|entry| entry.source != SourceId::CcCedict && entry.source != SourceId::ClassicalGlossary)
+			.collect();
+		if supplemented.is_empty() {
+			return bundled;
+		}
+		supplemented.sort_by_key(|entry| entry.word_id);
+		let indexes = build_indexes(supplemented);
+
+		bundled.with_additions(match index {
+			Index::Traditional => indexes.traditional,
+			Index::Simplified => indexes.simplified,
+			Index::Pinyin => indexes.pinyin,
+			Index::English => indexes.english,
+		})
+	}
+
+	/// The initials index, which is built from the entries the first time it is used.
+	fn initials(&self) -> &KeyIndex {
+		self.initials.get_or_init(|| {
+			let mut initials: HashMap<String, Vec<u32>> = HashMap::new();
+			for entry in self.data.values() {
+				initials.entry(initials_key(entry)).or_default().push(entry.word_id);
+			}
+			for ids in initials.values_mut() {
+				ids.sort_unstable();
+			}
+			KeyIndex::new(initials)
+		})
+	}
+
+	/// The index of entries by the final and tone of their last syllable, which is built the first time it is used.
+	pub(crate) fn rhymes(&self) -> &KeyIndex {
+		self.rhymes.get_or_init(|| {
+			let mut rhymes: HashMap<String, Vec<u32>> = HashMap::new();
+			for entry in self.data.values() {
+				if let Some(key) = rhyme_key(entry) {
+					rhymes.entry(key).or_default().push(entry.word_id);
+				}
+			}
+			for ids in rhymes.values_mut() {
+				ids.sort_unstable();
+			}
+			KeyIndex::new(rhymes)
+		})
+	}
+
+	/// The trie over the simplified and traditional headwords, which is loaded the first time it is used.
+	/// The built-in trie only covers CC-CEDICT, so it is built from the indexes instead when the gazetteers, terminology packs,
+	/// or neologisms add entries, or for dictionaries that are not built-in.
+	pub(crate) fn headword_trie(&self) -> &HeadwordTrie {
+		self.headwords.get_or_init(|| {
+			let supplemented = self.entries().any(|entry| entry.source != SourceId::CcCedict && entry.source != SourceId::ClassicalGlossary);
+			if self.bundled && !supplemented {
+				deserialize_from(HEADWORDS).unwrap()
+			} else {
+				HeadwordTrie::new(&[self.index(Index::Simplified), self.index(Index::Traditional)])
+			}
+		})
+	}
+
+	/// The trie over the headwords of the classical glossary, which is built the first time `SegmentationMode::Classical` is used.
+	fn classical_trie(&self) -> &HeadwordTrie {
+		self.classical_headwords.get_or_init(|| {
+			let mut builder = TrieBuilder::new();
+			let mut headwords: Vec<(&String, &Vec<u32>)> = self.classical.iter().collect();
+			headwords.sort_unstable();
+			for (headword, items) in headwords {
+				builder.insert(headword, items);
+			}
+			builder.build()
+		})
+	}
+
+	/// # Normalizer
+	/// The preprocessing steps applied to queries passed to `query` and `query_into`.
+	pub fn normalizer(&self) -> &Normalizer {
+		&self.normalizer
+	}
+
+	/// # Set Normalizer
+	/// Replace the preprocessing steps applied to queries passed to `query` and `query_into`.
+	pub fn set_normalizer(&mut self, normalizer: Normalizer) {
+		self.normalizer = normalizer;
+	}
+
+	/// # Input Codes
+	/// The Cangjie and four-corner codes used by `query_by_cangjie` and `query_by_four_corner`.
+	pub fn input_codes(&self) -> &InputCodes {
+		&self.input_codes
+	}
+
+	/// # Set Input Codes
+	/// Replace the Cangjie and four-corner codes used by `query_by_cangjie` and `query_by_four_corner`, which are empty by default.
+	pub fn set_input_codes(&mut self, input_codes: InputCodes) {
+		self.input_codes = input_codes;
+	}
+
+	/// # Collocation Data
+	/// The collocations used by `collocations`.
+	pub fn collocation_data(&self) -> &Collocations {
+		&self.collocations
+	}
+
+	/// # Set Collocation Data
+	/// Replace the collocations used by `collocations`, such as with ones counted in a corpus by `Collocations::from_corpus`.
+	/// The built-in dictionary has the bundled list of the `collocations` feature, and dictionaries created with `from_entries` have none.
+	pub fn set_collocation_data(&mut self, collocations: Collocations) {
+		self.collocations = collocations;
+	}
+
+	/// # Query Options
+	/// The options controlling how queries are interpreted.
+	pub fn query_options(&self) -> &QueryOptions {
+		&self.query_options
+	}
+
+	/// # Set Query Options
+	/// Replace the options controlling how queries are interpreted.
+	pub fn set_query_options(&mut self, query_options: QueryOptions) {
+		self.query_options = query_options;
+	}
+
+	/// # Normalize
+	/// Apply the configured `Normalizer` to a string of text, returning the text `query` will look up.
+	/// The boolean operators `AND`, `OR`, and `NOT` of English queries are kept as they are.
+	pub fn normalize(&self, raw: &str) -> String {
+		self.normalize_query(raw).into_owned()
+	}
+
+	/// Like `normalize`, but borrows the query when normalizing leaves it unchanged.
+	fn normalize_query<'a>(&self, raw: &'a str) -> Cow<'a, str> {
+		// The operators are found before normalizing, since lowercasing would turn them into ordinary words
+		let text = if self.normalizer.lowercase && query_language::has_boolean_operator(raw) {
+			let words: Vec<Cow<str>> = raw.split(' ')
+				.map(|word| if BOOLEAN_OPERATORS.contains(&word) { Cow::Borrowed(word) } else { self.normalizer.normalize_text(word) })
+				.filter(|word| !word.is_empty())
+				.collect();
+			Cow::Owned(words.join(" "))
+		} else {
+			self.normalizer.normalize_text(raw)
+		};
+		match self.normalizer.fold_script {
+			Some(Script::Simplified) => Cow::Owned(self.character_util.traditional_to_simplified(&text)),
+			Some(Script::Traditional) => Cow::Owned(self.character_util.simplified_to_traditional(&text)),
+			None => text,
 		}
 	}
 
 	/// # Classify
 	/// Classify a string of text as either Pinyin, English, or Chinese characters.
 	/// For more information on the possible `ClassificationResult` enum values refer to the README.
+	/// The rules in `classification_options` are applied first, and the `chinese_detection` crate classifies whatever they leave open.
 	pub fn classify(&self, raw: &str) -> ClassificationResult {
-		self.language_util.classify(raw)
+		self.classification_options.classify(&self.language_util, raw)
+	}
+
+	/// # Classification Options
+	/// The rules `classify` and `query` use to tell English, pinyin, and Chinese queries apart.
+	pub fn classification_options(&self) -> &ClassificationOptions {
+		&self.classification_options
+	}
+
+	/// # Set Classification Options
+	/// Set the rules `classify` and `query` use to tell English, pinyin, and Chinese queries apart, e.g. to always read `me` as English.
+	pub fn set_classification_options(&mut self, classification_options: ClassificationOptions) {
+		self.classification_options = classification_options;
+	}
+
+	/// # Input Limits
+	/// The largest inputs accepted by `query` and `tokenize_checked`.
+	pub fn input_limits(&self) -> InputLimits {
+		self.input_limits
+	}
+
+	/// # Set Input Limits
+	/// Change the largest inputs accepted by `query` and `tokenize_checked`, e.g. to raise the limit for a batch job that tokenizes whole books.
+	pub fn set_input_limits(&mut self, input_limits: InputLimits) {
+		self.input_limits = input_limits;
 	}
 
 	/// # Convert to Simplified
@@ -76,8 +714,54 @@ impl Dictionary {
 
 	/// # Convert to Traditional
 	/// Convert a string of Simplified Chinese characters to their Traditional form.
+	/// The output follows the variant preference set with `set_variant_preference`, if any.
 	pub fn convert_to_traditional(&self, raw: &str) -> String {
-		self.character_util.simplified_to_traditional(raw)
+		let converted = self.character_util.simplified_to_traditional(raw);
+		match self.variant_preference {
+			Some(region) => region.apply_variants(&converted),
+			None => converted,
+		}
+	}
+
+	/// # Variant Preference
+	/// The region whose character variants traditional output follows, or `None` to keep the forms from the converter and the dictionary data.
+	pub fn variant_preference(&self) -> Option<Region> {
+		self.variant_preference
+	}
+
+	/// # Set Variant Preference
+	/// Make `convert_to_traditional` and `display_headword` follow the character variants of a region, e.g. 臺 for Taiwan and 台 for Hong Kong.
+	pub fn set_variant_preference(&mut self, variant_preference: Option<Region>) {
+		self.variant_preference = variant_preference;
+	}
+
+	/// # Display Headword
+	/// The headword of an entry in a single script for display, with traditional headwords following the variant preference.
+	/// The headword is borrowed from the entry unless the preference changes one of its characters.
+	pub fn display_headword<'a>(&self, entry: &'a WordEntry, script: Script) -> Cow<'a, str> {
+		match (script, self.variant_preference) {
+			(Script::Simplified, _) => Cow::Borrowed(&entry.simplified),
+			(Script::Traditional, None) => Cow::Borrowed(&entry.traditional),
+			(Script::Traditional, Some(region)) => {
+				let preferred = region.apply_variants(&entry.traditional);
+				if preferred == entry.traditional { Cow::Borrowed(&entry.traditional) } else { Cow::Owned(preferred) }
+			},
+		}
+	}
+
+	/// # Convert with Vocabulary
+	/// Convert simplified text to traditional using the vocabulary of a region, e.g. 软件 becomes 軟體 for Taiwan and 軟件 for Hong Kong.
+	/// The longest known phrase is replaced at each position, and the rest of the text is converted character by character like `convert_to_traditional`.
+	pub fn convert_with_vocabulary(&self, raw: &str, region: Region) -> String {
+		self.vocabularies[&region].convert(raw, |text| self.convert_to_traditional(text))
+	}
+
+	/// # Add Vocabulary
+	/// Add a phrase to the vocabulary of a region, or replace the bundled word for it.
+	pub fn add_vocabulary(&mut self, region: Region, simplified: &str, traditional: &str) {
+		if let Some(vocabulary) = self.vocabularies.get_mut(&region) {
+			vocabulary.add(simplified, traditional);
+		}
 	}
 
 	/// # Is Traditional
@@ -92,113 +776,814 @@ impl Dictionary {
 		self.character_util.is_simplified(raw)
 	}
 
+	/// # Segmentation Mode
+	/// How segmentation chooses between overlapping words.
+	pub fn segmentation_mode(&self) -> SegmentationMode {
+		self.segmentation_mode
+	}
+
+	/// # Set Segmentation Mode
+	/// Change how `segment`, `highlight_known_words`, and `break_opportunities` choose between overlapping words.
+	pub fn set_segmentation_mode(&mut self, segmentation_mode: SegmentationMode) {
+		self.segmentation_mode = segmentation_mode;
+	}
+
+	/// # Max Word Length
+	/// The longest word, in characters, that segmentation considers, which is 20 by default.
+	pub fn max_word_length(&self) -> usize {
+		self.max_word_length
+	}
+
+	/// # Set Max Word Length
+	/// Change the longest word, in characters, that `segment` and the other segmentation based methods consider. Lengths below 1 are treated as 1.
+	/// Every word is tried from the longest length down at each position, so a lower cap such as 4 makes segmentation faster
+	/// at the cost of splitting longer words such as 中华人民共和国, and a custom dictionary with longer terms needs a higher cap.
+	pub fn set_max_word_length(&mut self, max_word_length: usize) {
+		self.max_word_length = max_word_length.max(1);
+	}
+
+	/// # Segmentation Filter
+	/// The entries left out of the segmentation lexicon.
+	pub fn segmentation_filter(&self) -> &SegmentationFilter {
+		&self.segmentation_filter
+	}
+
+	/// # Set Segmentation Filter
+	/// Leave entries out of the lexicon used by `segment`, `highlight_known_words`, `break_opportunities`, and `tokenize`, while keeping them queryable.
+	pub fn set_segmentation_filter(&mut self, segmentation_filter: SegmentationFilter) {
+		self.segmentation_filter = segmentation_filter;
+	}
+
+	/// # Set Frequencies
+	/// Replace the word frequencies used by `SegmentationMode::Unigram`, keyed by simplified headword.
+	/// The built-in dictionary includes frequencies estimated from a corpus of simplified Chinese, and dictionaries created with `from_entries` have none.
+	pub fn set_frequencies(&mut self, frequencies: HashMap<String, u32>) {
+		self.frequency_total = frequencies.values().map(|frequency| f64::from(*frequency)).sum();
+		self.frequencies = frequencies;
+	}
+
+	/// The frequency of a simplified word, or `0` if it is unknown.
+	pub(crate) fn frequency(&self, word: &str) -> u32 {
+		self.frequencies.get(word).cloned().unwrap_or(0)
+	}
+
+	/// The sum of all word frequencies.
+	pub(crate) fn frequency_total(&self) -> f64 {
+		self.frequency_total
+	}
+
+	/// # Set Bigrams
+	/// Replace the counts of how often one word follows another, used by `SegmentationMode::Bigram` and `segmentation_score`, keyed by simplified headwords.
+	/// The counts should come from the same corpus as the frequencies set with `set_frequencies`.
+	/// The built-in dictionary includes counts estimated with `build_bigrams`, and dictionaries created with `from_entries` have none.
+	pub fn set_bigrams(&mut self, bigrams: HashMap<(String, String), u32>) {
+		self.bigrams = Bigrams::new(bigrams);
+	}
+
+	/// How often the simplified word `word` follows `previous`, or `0` if it is unknown.
+	pub(crate) fn bigram(&self, previous: &str, word: &str) -> u32 {
+		self.bigrams.get(previous, word)
+	}
+
 	/// # Segment
 	/// Segment a string of either Traditional or Simplified Chinese characters into constituent words.
-	/// Uses a largest first matching dictionary driven approach.
+	/// Uses a largest first matching dictionary driven approach by default, or a unigram model with `SegmentationMode::Unigram`.
 	pub fn segment(&self, raw: &str) -> Vec<String> {
-		let mut tokens: Vec<String> = Vec::new();
-		let default_take = if raw.len() < 20 { raw.len() } else { 20 };
-		let mut skip = 0;
-		let mut take = default_take;
-		let dictionary = if self.character_util.is_simplified(raw) { &self.simplified } else { &self.traditional };
-		
-		while skip < raw.chars().count() {
-			let substring: String = raw.chars().skip(skip).take(take).collect();
-			if !dictionary.contains_key(&substring) {
-				if take > 1 {
-					take -= 1;
+		self.scan(raw).into_iter().map(|range| raw[range].to_string()).collect()
+	}
+
+	/// # Break Opportunities
+	/// The byte offsets in a string of text where a line may be wrapped without splitting a word.
+	/// Lines are not broken inside a dictionary word, inside a run of Latin letters or digits, before whitespace or closing punctuation, or after opening punctuation.
+	pub fn break_opportunities(&self, raw: &str) -> Vec<usize> {
+		let inside_word = self.word_interiors(raw);
+
+		raw.char_indices().zip(raw.char_indices().skip(1)).filter_map(|((_, previous), (index, next))| {
+			let is_cjk = |c: char| c >= '\u{2E80}' && !is_opening(c) && !is_closing(c);
+			let allowed = !inside_word.contains(&index)
+				&& !next.is_whitespace()
+				&& !is_closing(next)
+				&& !is_trailing(next)
+				&& !is_opening(previous)
+				&& (previous.is_whitespace() || is_cjk(previous) || is_cjk(next));
+			if allowed { Some(index) } else { None }
+		}).collect()
+	}
+
+	/// # Replace Word
+	/// Replace every occurrence of `from` in `text` with `to`, but only where the occurrence starts and ends on word boundaries,
+	/// so replacing 天 with 日 changes 今天 天 but leaves 天气 alone. Useful for enforcing terminology without corrupting longer words.
+	pub fn replace_word(&self, text: &str, from: &str, to: &str) -> String {
+		if from.is_empty() {
+			return text.to_string();
+		}
+		let inside_word = self.word_interiors(text);
+		let mut replaced = String::with_capacity(text.len());
+		let mut position = 0;
+		for (start, _) in text.match_indices(from) {
+			let end = start + from.len();
+			if start >= position && !inside_word.contains(&start) && !inside_word.contains(&end) {
+				replaced.push_str(&text[position..start]);
+				replaced.push_str(to);
+				position = end;
+			}
+		}
+		replaced.push_str(&text[position..]);
+
+		replaced
+	}
+
+	/// The byte offsets that fall inside a word of the segmentation of `raw`, between two of its characters.
+	pub(crate) fn word_interiors(&self, raw: &str) -> HashSet<usize> {
+		let mut inside_word: HashSet<usize> = HashSet::new();
+		for range in self.scan(raw) {
+			inside_word.extend(raw[range.clone()].char_indices().skip(1).map(|(index, _)| range.start + index));
+		}
+		inside_word
+	}
+
+	/// # Highlight Known Words
+	/// Split a string of text into spans marking the words a user already knows versus new vocabulary.
+	/// A word is known when any of its entries has a `word_id` in `known`.
+	/// The returned spans cover the whole string, so text that is not in the dictionary is reported as `SpanKind::Unmatched`.
+	pub fn highlight_known_words(&self, raw: &str, known: &HashSet<u32>) -> Vec<Span> {
+		let dictionary = self.segmentation_index(raw);
+		let mut spans: Vec<Span> = Vec::new();
+		let mut position = 0;
+
+		for range in self.scan(raw) {
+			if position < range.start {
+				spans.push(Span { range: position..range.start, kind: SpanKind::Unmatched });
+			}
+			let is_known = dictionary.get(&raw[range.clone()]).is_some_and(|ids| ids.iter().any(|id| known.contains(id)));
+			position = range.end;
+			spans.push(Span { range, kind: if is_known { SpanKind::Known } else { SpanKind::New } });
+		}
+		if position < raw.len() {
+			spans.push(Span { range: position..raw.len(), kind: SpanKind::Unmatched });
+		}
+
+		spans
+	}
+
+	fn segmentation_index(&self, raw: &str) -> &KeyIndex {
+		self.index(if self.character_util.is_simplified(raw) { Index::Simplified } else { Index::Traditional })
+	}
+
+	/// The ids of `word` in a segmentation index, unless the segmentation filter excludes every one of its entries.
+	fn segmentation_word<'a>(&self, dictionary: &'a KeyIndex, word: &str) -> Option<&'a [u32]> {
+		let items = dictionary.get(word)?;
+		if self.segmentation_filter.is_empty() {
+			return Some(items);
+		}
+		let allowed = items.iter().any(|item| {
+			let entry = &self.data[item];
+			self.segmentation_filter.allows(entry, self.frequency(&entry.simplified))
+		});
+		if allowed { Some(items) } else { None }
+	}
+
+	/// Scans the string for dictionary words according to the segmentation mode and returns the byte range of each word found.
+	/// Characters that do not begin any dictionary word are skipped.
+	pub(crate) fn scan(&self, raw: &str) -> Vec<Range<usize>> {
+		match self.segmentation_mode {
+			SegmentationMode::LongestMatch => self.scan_longest(raw),
+			SegmentationMode::Unigram => self.scan_unigram(raw),
+			SegmentationMode::Classical => self.scan_classical(raw),
+			SegmentationMode::Bigram => self.scan_bigram(raw),
+		}
+	}
+
+	fn scan_longest(&self, raw: &str) -> Vec<Range<usize>> {
+		let mut ranges: Vec<Range<usize>> = Vec::new();
+		let dictionary = self.segmentation_index(raw);
+		let trie = self.headword_trie();
+		let mut position = 0;
+
+		while let Some(first) = raw[position..].chars().next() {
+			// Only the words the trie finds at this position are checked against the segmentation filter, from the longest down
+			let rest = &raw[position..];
+			let longest = trie.prefixes(rest, self.max_word_length).into_iter().rev()
+				.find(|length| self.segmentation_word(dictionary, &rest[..*length]).is_some());
+			match longest {
+				Some(length) => {
+					ranges.push(position..position + length);
+					position += length;
+				},
+				None => position += first.len_utf8(),
+			}
+		}
+
+		ranges
+	}
+
+	/// Like `scan_longest`, but only takes words longer than one character when they are in the classical glossary or are proper nouns.
+	fn scan_classical(&self, raw: &str) -> Vec<Range<usize>> {
+		let mut ranges: Vec<Range<usize>> = Vec::new();
+		let dictionary = self.segmentation_index(raw);
+		let trie = self.headword_trie();
+		let classical = self.classical_trie();
+		let mut position = 0;
+
+		while let Some(first) = raw[position..].chars().next() {
+			let rest = &raw[position..];
+			let mut lengths = trie.prefixes(rest, self.max_word_length);
+			lengths.extend(classical.prefixes(rest, self.max_word_length));
+			lengths.sort_unstable();
+			lengths.dedup();
+			let longest = lengths.into_iter().rev().find(|length| {
+				let word = &rest[..*length];
+				if *length == first.len_utf8() {
+					self.segmentation_word(dictionary, word).is_some() || self.classical.contains_key(word)
 				} else {
-					skip +=1;
-					take = default_take;
+					self.classical.contains_key(word) || self.segmentation_word(dictionary, word).is_some_and(|items| {
+						items.iter().any(|item| self.data[item].pinyin_numbers.starts_with(char::is_uppercase))
+					})
 				}
-			} else {
-				tokens.push(substring);
-				skip += take;
-				take = default_take;
+			});
+			match longest {
+				Some(length) => {
+					ranges.push(position..position + length);
+					position += length;
+				},
+				None => position += first.len_utf8(),
+			}
+		}
+
+		ranges
+	}
+
+	/// Finds the sequence of words with the lowest total cost, where the cost of a word is its negative log probability.
+	/// Characters that are not dictionary words are kept as single characters with the cost of an unseen word.
+	fn scan_unigram(&self, raw: &str) -> Vec<Range<usize>> {
+		let dictionary = self.segmentation_index(raw);
+		let trie = self.headword_trie();
+		let boundaries: Vec<usize> = raw.char_indices().map(|(index, _)| index).chain(std::iter::once(raw.len())).collect();
+		let char_count = boundaries.len() - 1;
+		let total = self.frequency_total.max(1.0);
+		let cost = |frequency: u32| -((f64::from(frequency) + 0.5) / total).ln();
+		// The lowest cost of segmenting the first `end` characters, and where the last word of that segmentation starts
+		let mut best: Vec<(f64, usize)> = vec![(f64::INFINITY, 0); char_count + 1];
+		best[0] = (0.0, 0);
+
+		// Every word starting at `start` is found with one walk down the trie, so only the dictionary's words are ever looked up
+		for start in 0..char_count {
+			let rest = &raw[boundaries[start]..];
+			let mut words: Vec<(usize, u32)> = trie.prefixes(rest, self.max_word_length).into_iter().filter_map(|length| {
+				let items = self.segmentation_word(dictionary, &rest[..length])?;
+				let frequency = items.iter().map(|item| self.frequency(&self.data[item].simplified)).max().unwrap_or(0);
+				Some((start + rest[..length].chars().count(), frequency))
+			}).collect();
+			if words.first().is_none_or(|(end, _)| *end != start + 1) {
+				words.insert(0, (start + 1, 0));
+			}
+			for (end, frequency) in words {
+				let total_cost = best[start].0 + cost(frequency);
+				if total_cost < best[end].0 {
+					best[end] = (total_cost, start);
+				}
+			}
+		}
+
+		let mut ranges: Vec<Range<usize>> = Vec::new();
+		let mut end = char_count;
+		while end > 0 {
+			let start = best[end].1;
+			let range = boundaries[start]..boundaries[end];
+			if self.segmentation_word(dictionary, &raw[range.clone()]).is_some() {
+				ranges.push(range);
+			}
+			end = start;
+		}
+		ranges.reverse();
+
+		ranges
+	}
+
+	fn scan_bigram(&self, raw: &str) -> Vec<Range<usize>> {
+		let dictionary = self.segmentation_index(raw);
+		let trie = self.headword_trie();
+		let boundaries: Vec<usize> = raw.char_indices().map(|(index, _)| index).chain(std::iter::once(raw.len())).collect();
+		let char_count = boundaries.len() - 1;
+		// The words starting at each character: where they end, and their most frequent simplified form, or the character itself when no word starts there
+		let words: Vec<Vec<(usize, String)>> = (0..char_count).map(|start| {
+			let rest = &raw[boundaries[start]..];
+			let mut words: Vec<(usize, String)> = trie.prefixes(rest, self.max_word_length).into_iter().filter_map(|length| {
+				let items = self.segmentation_word(dictionary, &rest[..length])?;
+				let simplified = items.iter().map(|item| &self.data[item].simplified).max_by_key(|simplified| self.frequency(simplified))?;
+				Some((start + rest[..length].chars().count(), simplified.clone()))
+			}).collect();
+			if words.first().is_none_or(|(end, _)| *end != start + 1) {
+				words.insert(0, (start + 1, raw[boundaries[start]..boundaries[start + 1]].to_string()));
+			}
+			words
+		}).collect();
+
+		// For every word ending at `end`, keyed by where it starts: the lowest cost of segmenting the first `end` characters with that word last,
+		// and where the word before it starts. The previous word matters to the cost of the next one, so each is kept rather than only the best.
+		let mut best: Vec<BTreeMap<usize, (f64, usize)>> = vec![BTreeMap::new(); char_count + 1];
+		for start in 0..char_count {
+			// The words that end at `start`, with where they start and the lowest cost of segmenting the text up to them
+			let states: Vec<(usize, Option<&str>, f64)> = match start {
+				0 => vec![(0, None, 0.0)],
+				_ => best[start].iter().map(|(previous_start, (cost, _))| {
+					let word = words[*previous_start].iter().find(|(end, _)| *end == start).map(|(_, word)| word.as_str());
+					(*previous_start, word, *cost)
+				}).collect(),
+			};
+			for (previous_start, previous_word, cost) in states {
+				for (end, word) in &words[start] {
+					let total_cost = cost + self.bigram_cost(previous_word, word);
+					if best[*end].get(&start).is_none_or(|(best_cost, _)| total_cost < *best_cost) {
+						best[*end].insert(start, (total_cost, previous_start));
+					}
+				}
+			}
+		}
+
+		let mut ranges: Vec<Range<usize>> = Vec::new();
+		let mut end = char_count;
+		let mut start = best[end].iter().min_by(|a, b| a.1.0.total_cmp(&b.1.0)).map_or(0, |(start, _)| *start);
+		while end > 0 {
+			let range = boundaries[start]..boundaries[end];
+			if self.segmentation_word(dictionary, &raw[range.clone()]).is_some() {
+				ranges.push(range);
 			}
+			let previous_start = best[end][&start].1;
+			end = start;
+			start = previous_start;
 		}
-		
-		return tokens;
+		ranges.reverse();
+
+		ranges
 	}
 
 	/// # Query by English
 	/// Query the dictionary specifically with English.
 	/// Uses a largest first matching approach to look for compound words within the provided string.
 	/// Will attempt to take the shortest of four tokens or the total number of tokens in the string to match against.
+	/// A query wrapped in double quotes, e.g. `"\"traditional chinese character\""`, only matches entries with exactly that gloss.
+	///
+	/// The uppercase operators `AND`, `OR`, and `NOT` combine searches, e.g. `"fruit AND tropical"`, `"watermelon OR melon"`, or `"fruit NOT dried"`,
+	/// with `AND` binding tighter than `OR`. With operators, a term matches the entries with a gloss containing it as whole words, or exactly that gloss when it is quoted,
+	/// and an alternative with only `NOT` terms matches nothing.
+	/// `query` recognizes the operators even when the normalizer lowercases queries.
 	pub fn query_by_english(&self, raw: &str) -> Vec<&WordEntry> {
 		let mut entries: Vec<&WordEntry> = Vec::new();
-		let default_take = if raw.split(" ").count() < ENGLISH_MAX_LENGTH { raw.split(" ").count() } else { ENGLISH_MAX_LENGTH };
+		self.query_by_english_into(raw, &mut entries);
+		entries
+	}
+
+	fn query_by_english_into<'a>(&'a self, raw: &str, entries: &mut Vec<&'a WordEntry>) {
+		if let Some(alternatives) = query_language::parse_boolean(raw) {
+			self.query_by_english_boolean_into(alternatives, entries);
+			return;
+		}
+		match exact_phrase(raw) {
+			Some(phrase) => {
+				let key = english_key(phrase);
+				let items = self.index(Index::English).get(&key);
+				explain::probe(Index::English, &key, items);
+				if let Some(items) = items {
+					entries.extend(items.iter()
+						.map(|item| &*self.data[item])
+						.filter(|entry| entry.english.iter().any(|gloss| english_key(gloss) == key)));
+				}
+			},
+			None => self.scan_english(raw, |_, items| entries.extend(items.iter().map(|item| &*self.data[item]))),
+		}
+	}
+
+	/// Walks the words of an English query with a largest first sliding window, calling `on_match` with each matching index key.
+	fn scan_english<'a, F: FnMut(&str, &'a [u32])>(&'a self, raw: &str, mut on_match: F) {
+		let words: Vec<&str> = raw.split(' ').collect();
+		let default_take = if words.len() < ENGLISH_MAX_LENGTH { words.len() } else { ENGLISH_MAX_LENGTH };
+		let mut substring = String::new();
 		let mut skip = 0;
 		let mut take = default_take;
 
-		while skip < raw.split(" ").count() {
-			let substring: String = raw.split(" ").skip(skip).take(take).collect::<Vec<&str>>().join("%20");
-			if !self.english.contains_key(&substring) {
-				if take > 1 {
-					take -= 1;
-				} else {
-					skip += 1;
-					take = default_take;	
+		while skip < words.len() {
+			substring.clear();
+			for (index, word) in words.iter().skip(skip).take(take).enumerate() {
+				if index > 0 {
+					substring.push_str("%20");
 				}
-			} else {
-				for item in self.english.get(&substring).unwrap() {
-					entries.push(self.data.get(item).unwrap());
+				substring.push_str(word);
+			}
+			let found = self.index(Index::English).get(&substring);
+			explain::probe(Index::English, &substring, found);
+			match found {
+				Some(items) => {
+					on_match(&substring, items);
+					skip += take;
+					take = default_take;
+				},
+				None => {
+					if take > 1 {
+						take -= 1;
+					} else {
+						skip += 1;
+						take = default_take;
+					}
+				}
+			}
+		}
+	}
+
+	/// Runs each alternative of a boolean English query, keeping the entries that every term matches or, for `NOT`, does not match.
+	/// Entries with a gloss that is exactly the first term come first, then the rest in `word_id` order.
+	/// Terms without any letters are ignored.
+	fn query_by_english_boolean_into<'a>(&'a self, alternatives: Vec<Vec<(bool, String)>>, entries: &mut Vec<&'a WordEntry>) {
+		let mut seen: HashSet<u32> = HashSet::new();
+		for terms in alternatives {
+			let terms: Vec<(bool, GlossTerm)> = terms.iter()
+				.map(|(negated, term)| (*negated, GlossTerm::new(term)))
+				.filter(|(_, term)| !term.key.is_empty())
+				.collect();
+			let Some((_, first)) = terms.iter().find(|(negated, _)| !negated) else {
+				continue;
+			};
+			// The posting lists of the terms are intersected, and the lists of the negated terms subtracted
+			let mut ids = self.gloss_term_ids(first);
+			for (negated, term) in &terms {
+				if !ids.is_empty() && !std::ptr::eq(term, first) {
+					let other = self.gloss_term_ids(term);
+					ids.retain(|id| other.binary_search(id).is_ok() != *negated);
 				}
-				skip += take;
-				take = default_take;
 			}
+			let mut matched: Vec<(bool, &'a WordEntry)> = ids.into_iter()
+				.map(|id| &*self.data[&id])
+				.map(|entry| (!entry.english.iter().any(|gloss| english_key(gloss) == first.key), entry))
+				.collect();
+			matched.sort_by_key(|(inexact, entry)| (*inexact, entry.word_id));
+			entries.extend(matched.into_iter().map(|(_, entry)| entry).filter(|entry| seen.insert(entry.word_id)));
 		}
+	}
+
+	/// The sorted ids of the entries a term of a boolean English query matches. Candidates are found in the English index for quoted terms,
+	/// and by intersecting the entries of each word of the term otherwise, so only those candidates have their glosses checked.
+	fn gloss_term_ids(&self, term: &GlossTerm) -> Vec<u32> {
+		let mut ids: Vec<u32> = if term.exact {
+			self.index(Index::English).get(&term.key).map(<[u32]>::to_vec).unwrap_or_default()
+		} else {
+			let mut words = term.key.split("%20").filter(|word| !word.is_empty());
+			let mut ids = words.next().and_then(|word| self.english_words().get(word)).map(<[u32]>::to_vec).unwrap_or_default();
+			for word in words {
+				let other = self.english_words().get(word).unwrap_or(&[]);
+				ids.retain(|id| other.binary_search(id).is_ok());
+			}
+			ids
+		};
+		ids.sort_unstable();
+		ids.dedup();
+		// A single word is matched by its posting list alone, but a quoted term or a phrase must appear in one gloss, with its words in order
+		if term.exact || term.key.contains("%20") {
+			ids.retain(|id| term.matches(&self.data[id]));
+		}
+
+		ids
+	}
 
-		return entries;
+	/// The index of the words of the English index keys, which is built from the English index the first time it is used.
+	fn english_words(&self) -> &KeyIndex {
+		self.english_words.get_or_init(|| {
+			let mut words: HashMap<String, Vec<u32>> = HashMap::new();
+			self.index(Index::English).for_each(|key, ids| {
+				for word in key.split("%20").filter(|word| !word.is_empty()) {
+					words.entry(word.to_string()).or_default().extend_from_slice(ids);
+				}
+			});
+			for ids in words.values_mut() {
+				ids.sort_unstable();
+				ids.dedup();
+			}
+			KeyIndex::new(words)
+		})
+	}
+
+	/// # Query by English Senses
+	/// Query the dictionary with English the same way as `query_by_english`,
+	/// reporting which senses (glosses) of each entry matched the query.
+	/// Entries matched by several parts of the query are only returned once.
+	pub fn query_by_english_senses(&self, raw: &str) -> Vec<SenseMatch<'_>> {
+		let mut matches: Vec<SenseMatch<'_>> = Vec::new();
+
+		self.scan_english(raw, |key, items| {
+			for item in items {
+				let entry = &*self.data[item];
+				let senses = entry.senses().filter(|sense| {
+					let gloss_key = english_key(sense.gloss);
+					gloss_key == key || gloss_key.strip_prefix("to%20") == Some(key)
+				}).map(|sense| sense.index);
+				match matches.iter_mut().find(|existing| existing.entry.word_id == entry.word_id) {
+					Some(existing) => {
+						for sense in senses {
+							if !existing.senses.contains(&sense) {
+								existing.senses.push(sense);
+							}
+						}
+					},
+					None => matches.push(SenseMatch { entry, senses: senses.collect() }),
+				}
+			}
+		});
+
+		matches
+	}
+
+	/// # Query by English Grouped
+	/// Query the dictionary with English, returning the matches for every phrase of up to four words in the query as a separate group,
+	/// instead of one flattened list. For `"bank account"` there is a group for the whole phrase, one for `"bank"`, and one for `"account"`.
+	/// Groups are ordered from the longest phrase to the shortest, then by their position in the query.
+	pub fn query_by_english_grouped(&self, raw: &str) -> Vec<EnglishGroup<'_>> {
+		let words: Vec<&str> = raw.split(' ').filter(|word| !word.is_empty()).collect();
+		let mut groups: Vec<EnglishGroup<'_>> = Vec::new();
+
+		for length in (1..=words.len().min(ENGLISH_MAX_LENGTH)).rev() {
+			for start in 0..=words.len() - length {
+				let phrase = words[start..start + length].join(" ");
+				if let Some(items) = self.index(Index::English).get(&words[start..start + length].join("%20")) {
+					groups.push(EnglishGroup {
+						phrase,
+						words: start..start + length,
+						entries: items.iter().map(|item| &*self.data[item]).collect(),
+					});
+				}
+			}
+		}
+
+		groups
+	}
+
+	/// # Query English Contains
+	/// Find every entry with an English gloss containing `substring`, ignoring case.
+	/// Results are ranked by the length of their shortest matching gloss, so the closest matches come first.
+	/// Unlike `query_by_english` this scans every gloss in the dictionary, and is much slower.
+	pub fn query_english_contains(&self, substring: &str) -> Vec<&WordEntry> {
+		let needle = substring.to_lowercase();
+		let mut matches: Vec<(usize, &WordEntry)> = self.entries().filter_map(|entry| {
+			entry.english.iter()
+				.filter(|gloss| gloss.to_lowercase().contains(&needle))
+				.map(|gloss| gloss.chars().count())
+				.min()
+				.map(|length| (length, entry))
+		}).collect();
+		matches.sort_by_key(|(length, entry)| (*length, entry.word_id));
+
+		matches.into_iter().map(|(_, entry)| entry).collect()
 	}
 
 	/// # Query by Pinyin
 	/// Query the dictionary specifically with Pinyin.
-	/// Uses space as a token delineator. Supports pinyin with no tones, tone marks, and tone numbers.
+	/// Uses space as a token delineator. Supports pinyin with no tones, tone marks, and tone numbers, with ü typed as `ü`, `v`, or `u:`
+	/// and the neutral tone written as tone 5, tone 0, or without a number after the last syllable, as in `"xie4xie"`,
+	/// and double pinyin when `QueryOptions::pinyin_scheme` is set.
 	pub fn query_by_pinyin(&self, raw: &str) -> Vec<&WordEntry> {
 		let mut entries: Vec<&WordEntry> = Vec::new();
-		
-		for word in raw.split(" ") {
-			if self.pinyin.contains_key(word) {
-				for item in self.pinyin.get(word).unwrap() {
-					entries.push(self.data.get(item).unwrap());
-				}
-			}	
+		self.query_by_pinyin_into(raw, &mut entries);
+		entries
+	}
+
+	fn query_by_pinyin_into<'a>(&'a self, raw: &str, entries: &mut Vec<&'a WordEntry>) {
+		for word in raw.split(' ') {
+			let items = self.expand_shuangpin(word).or_else(|| {
+				// The index spells ü and the neutral tone as CC-CEDICT does, so `lv4` is looked up as `lu:4` and `ma0` as `ma5`
+				let word = normalize_neutral_tone(&normalize_umlaut(word, UmlautStyle::UColon));
+				let items = self.index(Index::Pinyin).get(&word);
+				explain::probe(Index::Pinyin, &word, items);
+				items
+			});
+			if let Some(items) = items {
+				entries.extend(items.iter().map(|item| &*self.data[item]));
+			}
 		}
-		
-		return entries;
 	}
 
-	fn query_by_characters(&self, dictionary: &HashMap<String, Vec<u32>>, raw: &str) -> Vec<&WordEntry> {
-		let mut entries: Vec<&WordEntry> = Vec::new();
+	/// Looks up a word typed in the configured double pinyin scheme, if there is one.
+	fn expand_shuangpin(&self, word: &str) -> Option<&[u32]> {
+		let scheme = self.query_options.pinyin_scheme?;
+		let items = scheme.expand(word).and_then(|key| self.index(Index::Pinyin).get(&key));
+		if let Some(items) = items {
+			explain::record(|| TraceEvent::Shuangpin { word: word.to_string(), hits: items.len() });
+		}
+		items
+	}
 
-		for word in self.segment(raw) {
-			if dictionary.contains_key(&word) {
-				for item in dictionary.get(&word).unwrap() {
-					entries.push(self.data.get(item).unwrap());
-				}
+	/// # Query by Initials
+	/// Query the dictionary with the first letter of each pinyin syllable, the way abbreviations are typed in a Chinese IME,
+	/// e.g. `"bj"` for 北京 or `"nh"` for 你好. Spaces and case are ignored. Results are in `word_id` order.
+	pub fn query_by_initials(&self, raw: &str) -> Vec<&WordEntry> {
+		let key: String = raw.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_lowercase();
+		match self.initials().get(&key) {
+			Some(items) => items.iter().map(|item| &*self.data[item]).collect(),
+			None => Vec::new(),
+		}
+	}
+
+	fn query_by_characters_into<'a>(&'a self, index: Index, raw: &str, entries: &mut Vec<&'a WordEntry>) {
+		let dictionary = self.index(index);
+		// A headword left out of segmentation would otherwise be split into smaller words
+		if !self.segmentation_filter.is_empty() && self.segmentation_word(dictionary, raw).is_none() {
+			if let Some(items) = dictionary.get(raw) {
+				explain::probe(index, raw, Some(items));
+				entries.extend(items.iter().map(|item| &*self.data[item]));
+				return;
+			}
+		}
+		for range in self.scan(raw) {
+			let items = dictionary.get(&raw[range.clone()]);
+			explain::probe(index, &raw[range], items);
+			if let Some(items) = items {
+				entries.extend(items.iter().map(|item| &*self.data[item]));
+			}
+		}
+	}
+
+	/// The entries with `word` as their simplified or traditional headword.
+	pub(crate) fn entries_for_headword(&self, word: &str) -> Vec<&WordEntry> {
+		let mut ids: Vec<u32> = self.index(Index::Simplified).get(word).unwrap_or(&[]).to_vec();
+		for id in self.index(Index::Traditional).get(word).unwrap_or(&[]) {
+			if !ids.contains(id) {
+				ids.push(*id);
 			}
 		}
+		ids.iter().map(|id| &*self.data[id]).collect()
+	}
+
+	/// # Simplified Keys
+	/// Iterate over every simplified headword in the dictionary.
+	pub fn simplified_keys(&self) -> IndexKeys<'_> {
+		self.index(Index::Simplified).keys()
+	}
+
+	/// # Traditional Keys
+	/// Iterate over every traditional headword in the dictionary.
+	pub fn traditional_keys(&self) -> IndexKeys<'_> {
+		self.index(Index::Traditional).keys()
+	}
+
+	/// # Pinyin Keys
+	/// Iterate over every key of the pinyin index. Each word is indexed lowercase without spaces three times:
+	/// with tone numbers (`"ni3hao3"`), without tones (`"nihao"`), and with tone marks (`"nǐhǎo"`).
+	pub fn pinyin_keys(&self) -> IndexKeys<'_> {
+		self.index(Index::Pinyin).keys()
+	}
+
+	/// # English Keys
+	/// Iterate over every key of the English index. Keys are glosses lowercased, with everything but letters removed and
+	/// words joined with `%20`, e.g. `"to%20run"`. Glosses starting with "to" are also indexed without it.
+	pub fn english_keys(&self) -> IndexKeys<'_> {
+		self.index(Index::English).keys()
+	}
+
+	/// # Keys with Prefix
+	/// Iterate over the keys of an index that start with `prefix`, in lexicographic order of their UTF-8 bytes,
+	/// e.g. every pinyin key starting with `"zhong"` or every simplified headword starting with 中.
+	/// Only the matching part of the index is visited.
+	pub fn keys_with_prefix(&self, index: Index, prefix: &str) -> IndexKeys<'_> {
+		self.index(index).prefix(prefix)
+	}
+
+	/// # Keys in Range
+	/// Iterate over the keys of an index within `range`, in lexicographic order of their UTF-8 bytes,
+	/// e.g. `dictionary.keys_in_range(Index::English, "apple".."apricot")`.
+	pub fn keys_in_range<'r, R: RangeBounds<&'r str>>(&self, index: Index, range: R) -> IndexKeys<'_> {
+		self.index(index).range(range)
+	}
+
+	/// # Is Word
+	/// Check if `word` is exactly the simplified or traditional headword of an entry, e.g. for spell checking or validating slugs.
+	/// This only checks the headword indexes, without looking up any entries.
+	pub fn is_word(&self, word: &str) -> bool {
+		self.is_word_in_script(word, Script::Simplified) || self.is_word_in_script(word, Script::Traditional)
+	}
+
+	/// # Is Word in Script
+	/// Check if `word` is exactly the headword of an entry in the given script.
+	pub fn is_word_in_script(&self, word: &str, script: Script) -> bool {
+		match script {
+			Script::Simplified => self.index(Index::Simplified).contains_key(word),
+			Script::Traditional => self.index(Index::Traditional).contains_key(word),
+		}
+	}
 
-		return entries;
+	/// Whether `key` is in the pinyin index, e.g. `"ni3hao3"`.
+	pub(crate) fn is_pinyin_key(&self, key: &str) -> bool {
+		self.index(Index::Pinyin).contains_key(key)
 	}
-	
-	fn query_by_traditional(&self, raw: &str) -> Vec<&WordEntry> {
-		self.query_by_characters(&self.traditional, raw)
+
+	/// Whether the English text `phrase` is in the English index.
+	pub(crate) fn is_english_phrase(&self, phrase: &str) -> bool {
+		self.index(Index::English).contains_key(&english_key(phrase))
 	}
-	
-	fn query_by_simplified(&self, raw: &str) -> Vec<&WordEntry> {
-		self.query_by_characters(&self.simplified, raw)
+
+	fn query_by_chinese_into<'a>(&'a self, raw: &str, entries: &mut Vec<&'a WordEntry>) {
+		match self.character_util.is_traditional(raw) {
+			true => self.query_by_characters_into(Index::Traditional, raw, entries),
+			false => self.query_by_characters_into(Index::Simplified, raw, entries)
+		}
 	}
 
 	/// # Query by Chinese
 	/// Query the dictionary specifically with Chinese characters.
 	/// Supports both Traditional and Simplified Chinese characters.
 	pub fn query_by_chinese(&self, raw: &str) -> Vec<&WordEntry> {
-		match self.character_util.is_traditional(raw) {
-			true => self.query_by_traditional(raw),
-			false => self.query_by_simplified(raw)	
-		}	
+		let mut entries: Vec<&WordEntry> = Vec::new();
+		self.query_by_chinese_into(raw, &mut entries);
+		entries
+	}
+
+	/// # Query by Chinese Both
+	/// Query the dictionary with Chinese characters in either script, or a mix of both, such as 我们學習中文.
+	/// Unlike `query_by_chinese`, which picks one script for the whole string, the text is segmented by taking the longest word
+	/// at each position that is a headword in either script, so script specific characters do not break up the words around them.
+	/// Each entry is tagged with the headword that matched, and entries matching both are returned once.
+	pub fn query_by_chinese_both(&self, raw: &str) -> Vec<(&WordEntry, ScriptMatch)> {
+		let simplified = self.index(Index::Simplified);
+		let traditional = self.index(Index::Traditional);
+		let boundaries: Vec<usize> = raw.char_indices().map(|(index, _)| index).chain(std::iter::once(raw.len())).collect();
+		let char_count = boundaries.len() - 1;
+		let mut results: Vec<(&WordEntry, ScriptMatch)> = Vec::new();
+		let mut skip = 0;
+
+		while skip < char_count {
+			let found = (1..=(char_count - skip).min(self.max_word_length)).rev().find_map(|take| {
+				let word = &raw[boundaries[skip]..boundaries[skip + take]];
+				let simplified_items = self.segmentation_word(simplified, word);
+				let traditional_items = self.segmentation_word(traditional, word);
+				(simplified_items.is_some() || traditional_items.is_some()).then_some((take, simplified_items, traditional_items))
+			});
+			let Some((take, simplified_items, traditional_items)) = found else {
+				skip += 1;
+				continue;
+			};
+
+			let start = results.len();
+			for item in simplified_items.unwrap_or(&[]) {
+				results.push((&*self.data[item], ScriptMatch::Simplified));
+			}
+			for item in traditional_items.unwrap_or(&[]) {
+				match results[start..].iter_mut().find(|(entry, _)| entry.word_id == *item) {
+					Some(result) => result.1 = ScriptMatch::Both,
+					None => results.push((&*self.data[item], ScriptMatch::Traditional)),
+				}
+			}
+			skip += take;
+		}
+
+		results
+	}
+
+	/// # Query Classical
+	/// Look up each word of a passage of Classical Chinese (文言), such as 学而时习之, segmented with `SegmentationMode::Classical`.
+	/// For each word in order, its classical senses from the glossary of the `classical` feature come first, followed by its CC-CEDICT entries.
+	/// Without the `classical` feature, this still segments the passage the classical way but only returns CC-CEDICT entries.
+	pub fn query_classical(&self, raw: &str) -> Vec<&WordEntry> {
+		let mut entries: Vec<&WordEntry> = Vec::new();
+		for range in self.scan_classical(raw) {
+			let word = &raw[range];
+			for id in self.classical.get(word).map_or(&[][..], Vec::as_slice) {
+				entries.push(&*self.data[id]);
+			}
+			entries.extend(self.entries_for_headword(word));
+		}
+		entries
+	}
+
+	/// # Query Chinese Fuzzy
+	/// Query the dictionary for headwords within `max_distance` character edits of `raw`, where an edit inserts, removes, or replaces a single character, or swaps two adjacent ones.
+	/// Useful for looking up OCR'd or handwriting-recognized text where a character is garbled or missing.
+	/// Results are ordered by distance, with headwords that differ only by look-alike characters (see `similar_characters`) first, then by `word_id`.
+	pub fn query_chinese_fuzzy(&self, raw: &str, max_distance: usize) -> Vec<&WordEntry> {
+		let raw: Vec<char> = raw.trim().chars().collect();
+		if raw.is_empty() {
+			return Vec::new();
+		}
+		let index = match self.character_util.is_traditional(&raw.iter().collect::<String>()) {
+			true => self.index(Index::Traditional),
+			false => self.index(Index::Simplified),
+		};
+
+		let mut matches: Vec<(usize, bool, &WordEntry)> = Vec::new();
+		let mut seen: HashSet<u32> = HashSet::new();
+		index.for_each(|key, items| {
+			let key: Vec<char> = key.chars().collect();
+			if key.len().abs_diff(raw.len()) > max_distance {
+				return;
+			}
+			let distance = edit_distance(&raw, &key);
+			if distance <= max_distance {
+				let look_alike = key.len() == raw.len() && raw.iter().zip(&key).all(|(a, b)| a == b || is_confusable(*a, *b));
+				for item in items {
+					if seen.insert(*item) {
+						matches.push((distance, !look_alike, &*self.data[item]));
+					}
+				}
+			}
+		});
+
+		matches.sort_by_key(|(distance, unlike, entry)| (*distance, *unlike, entry.word_id));
+		matches.into_iter().map(|(_, _, entry)| entry).collect()
 	}
 
 	/// # Query
@@ -209,12 +1594,221 @@ impl Dictionary {
 	///
 	/// When querying using English, a largest first matching approached is used to look for compound words.
 	/// Will attempt to take the shortest of four tokens or the total number of tokens in the string to match against. 
+	///
+	/// The query is first preprocessed by the dictionary's `Normalizer`, see `set_normalizer`.
+	///
+	/// Operators can be used to say exactly how each part of the query should be searched:
+	/// `py:` for pinyin, `en:` for English, and `zh:` for Chinese characters, e.g. `py:shi4` or `en:to run`.
+	/// `hsk:` filters the results by HSK level, e.g. `hsk:<=3 en:eat`, and accepts `<`, `<=`, `=`, `>=`, and `>`. Entries without an HSK level never match it.
+	/// A query with several parts only returns entries that match all of them.
 	pub fn query(&self, raw: &str) -> Option<Vec<&WordEntry>> {
-		match self.language_util.classify(raw) {
-			ClassificationResult::EN => Some(self.query_by_english(raw)),
-			ClassificationResult::PY => Some(self.query_by_pinyin(raw)),
-			ClassificationResult::ZH => Some(self.query_by_chinese(raw)),
-			_ => None
+		let mut entries: Vec<&WordEntry> = Vec::new();
+		match self.query_into(raw, &mut entries) {
+			true => Some(entries),
+			false => None
+		}
+	}
+
+	/// # Query Owned
+	/// Query the dictionary the same way as `query`, returning shared handles to the entries instead of references.
+	/// Entries are stored behind an `Arc`, so the results can be moved into other threads, async tasks, or caches that outlive
+	/// the borrow of the dictionary without cloning any strings. Queries that cannot be classified return no results.
+	pub fn query_owned(&self, raw: &str) -> Vec<Arc<WordEntry>> {
+		let mut entries: Vec<&WordEntry> = Vec::new();
+		self.query_into(raw, &mut entries);
+		entries.into_iter().map(|entry| Arc::clone(&self.data[&entry.word_id])).collect()
+	}
+
+	/// # Query Into
+	/// Query the dictionary the same way as `query`, appending the results to a caller provided buffer.
+	/// Reusing the buffer between calls avoids allocating a new vector for every query.
+	/// Returns `false` when the query could not be classified or is longer than `InputLimits::max_query_bytes`, in which case nothing is appended.
+	/// When `QueryOptions::years` is set, only the results within those years are appended.
+	pub fn query_into<'a>(&'a self, raw: &str, entries: &mut Vec<&'a WordEntry>) -> bool {
+		if raw.len() > self.input_limits.max_query_bytes {
+			explain::record(|| TraceEvent::InputTooLarge { length: raw.len(), limit: self.input_limits.max_query_bytes });
+			return false;
+		}
+		let query = self.normalize_query(raw);
+		let start = entries.len();
+		let classified = match query_language::parse(&query) {
+			Some(clauses) => {
+				explain::record(|| TraceEvent::Operators(clauses.len()));
+				self.query_by_clauses_into(clauses, entries);
+				true
+			},
+			None => self.query_normalized_into(&query, entries),
+		};
+		if let Some(years) = &self.query_options.years {
+			// Only the appended entries are filtered, in place
+			let count = entries.len();
+			let mut position = 0;
+			entries.retain(|entry| {
+				position += 1;
+				position <= start || entry.year.is_some_and(|year| years.contains(&year))
+			});
+			explain::record(|| TraceEvent::YearsFilter { removed: count - entries.len() });
+		}
+		if let Some(ranker) = &self.ranker {
+			explain::record(|| TraceEvent::Ranked);
+			ranking::rank(ranker, &mut entries[start..], raw, &query);
+		}
+		classified
+	}
+
+	/// # Set Ranker
+	/// Order the results of `query`, `query_into`, and `query_owned` by a score computed for each entry, from highest to lowest.
+	/// Entries with the same score keep the order they would have had without a ranker, which is available as `QueryContext::rank`.
+	/// A score of NaN ranks the same as negative infinity, below every other score.
+	/// Apps can use this to boost the words in a user's study deck or to push archaic terms down, without sorting the results of every query themselves.
+	pub fn set_ranker<F>(&mut self, ranker: F) where F: Fn(&WordEntry, &QueryContext) -> f32 + Send + Sync + 'static {
+		self.ranker = Some(Arc::new(ranker));
+	}
+
+	/// # Clear Ranker
+	/// Remove the ranker set by `set_ranker`, returning results in their default order.
+	pub fn clear_ranker(&mut self) {
+		self.ranker = None;
+	}
+
+	fn query_normalized_into<'a>(&'a self, query: &str, entries: &mut Vec<&'a WordEntry>) -> bool {
+		if let Some(phrase) = exact_phrase(query) {
+			explain::record(|| TraceEvent::ExactPhrase(phrase.to_string()));
+			self.query_by_english_into(query, entries);
+			return true;
+		}
+		// Double pinyin is usually classified as English, so it is tried before classifying
+		let start = entries.len();
+		for word in query.split(' ') {
+			if let Some(items) = self.expand_shuangpin(word) {
+				entries.extend(items.iter().map(|item| &*self.data[item]));
+			}
+		}
+		if entries.len() > start {
+			return true;
+		}
+
+		explain::record(|| TraceEvent::Classified(self.classify(query)));
+		match self.classify(query) {
+			ClassificationResult::EN => self.query_by_english_into(query, entries),
+			ClassificationResult::PY => self.query_by_pinyin_into(query, entries),
+			ClassificationResult::ZH => self.query_by_chinese_into(query, entries),
+			_ => return false
 		}
+		true
 	}
+
+	/// Runs each clause of a query written with operators, keeping the entries found by every clause that matches the HSK filters.
+	fn query_by_clauses_into<'a>(&'a self, clauses: Vec<Clause>, entries: &mut Vec<&'a WordEntry>) {
+		let mut results: Option<Vec<&'a WordEntry>> = None;
+		let mut hsk_filters = Vec::new();
+
+		for clause in clauses {
+			let mut found: Vec<&'a WordEntry> = Vec::new();
+			match clause {
+				Clause::Pinyin(pinyin) => self.query_by_pinyin_into(&pinyin, &mut found),
+				Clause::English(english) => self.query_by_english_into(&english, &mut found),
+				Clause::Chinese(chinese) => self.query_by_chinese_into(&chinese, &mut found),
+				Clause::Text(text) => {
+					self.query_normalized_into(&text, &mut found);
+				},
+				Clause::Hsk(comparison, level) => {
+					hsk_filters.push((comparison, level));
+					continue;
+				},
+			}
+			results = Some(match results {
+				Some(previous) => {
+					let ids: HashSet<u32> = found.iter().map(|entry| entry.word_id).collect();
+					previous.into_iter().filter(|entry| ids.contains(&entry.word_id)).collect()
+				},
+				None => found,
+			});
+		}
+
+		let results = results.unwrap_or_else(|| {
+			let mut all: Vec<&'a WordEntry> = self.entries().collect();
+			all.sort_by_key(|entry| entry.word_id);
+			all
+		});
+		let count = results.len();
+		let start = entries.len();
+		entries.extend(results.into_iter().filter(|entry| {
+			hsk_filters.iter().all(|(comparison, level)| entry.hsk != 0 && comparison.matches(entry.hsk, *level))
+		}));
+		if !hsk_filters.is_empty() {
+			explain::record(|| TraceEvent::HskFilter { removed: count - (entries.len() - start) });
+		}
+	}
+}
+
+/// A term of a boolean English query.
+struct GlossTerm {
+	/// The index key of the term, as produced by `english_key`
+	key: String,
+	/// The key surrounded by word separators, for finding it as whole words within a gloss
+	padded: String,
+	/// Whether the term was quoted, so it only matches a whole gloss
+	exact: bool,
+}
+
+impl GlossTerm {
+	fn new(term: &str) -> GlossTerm {
+		let key = english_key(exact_phrase(term).unwrap_or(term));
+		GlossTerm { padded: format!("%20{}%20", key), exact: exact_phrase(term).is_some(), key }
+	}
+
+	fn matches(&self, entry: &WordEntry) -> bool {
+		entry.english.iter().any(|gloss| {
+			let gloss = english_key(gloss);
+			gloss == self.key || (!self.exact && format!("%20{}%20", gloss).contains(&self.padded))
+		})
+	}
+}
+
+/// The phrase inside a query wrapped in straight or curly double quotes.
+fn exact_phrase(raw: &str) -> Option<&str> {
+	let raw = raw.trim();
+	raw.strip_prefix('"').and_then(|rest| rest.strip_suffix('"'))
+		.or_else(|| raw.strip_prefix('“').and_then(|rest| rest.strip_suffix('”')))
+		.filter(|phrase| !phrase.trim().is_empty())
+}
+
+/// Combines the entries of several sources, after sorting each by `word_id`, as described for `Dictionary::merge`.
+fn merge_entries(sources: Vec<Vec<&WordEntry>>, strategy: MergeStrategy) -> Vec<WordEntry> {
+	let mut merged: Vec<WordEntry> = Vec::new();
+	let mut ids: HashSet<u32> = HashSet::new();
+	// Classical glossary senses share headwords and pinyin with CC-CEDICT entries, but are kept as separate entries
+	let mut headwords: HashSet<(&str, &str, &str, bool)> = HashSet::new();
+	let mut reassigned: Vec<WordEntry> = Vec::new();
+
+	for mut entries in sources {
+		entries.sort_by_key(|entry| entry.word_id);
+		for entry in entries {
+			match strategy {
+				MergeStrategy::WordId => {
+					if ids.insert(entry.word_id) {
+						merged.push(entry.clone());
+					}
+				},
+				MergeStrategy::HeadwordPinyin => {
+					if headwords.insert((&entry.traditional, &entry.simplified, &entry.pinyin_numbers, entry.source == SourceId::ClassicalGlossary)) {
+						if ids.insert(entry.word_id) {
+							merged.push(entry.clone());
+						} else {
+							reassigned.push(entry.clone());
+						}
+					}
+				},
+			}
+		}
+	}
+
+	let next_id = ids.iter().max().map_or(0, |id| id + 1);
+	for (word_id, mut entry) in (next_id..).zip(reassigned) {
+		entry.word_id = word_id;
+		merged.push(entry);
+	}
+
+	merged
 }
@@ -0,0 +1,22 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: An optional glossary of Classical Chinese (文言)
+
+use crate::builder::parse_cedict;
+use crate::chinese_dictionary::WordEntry;
+use crate::source::SourceId;
+
+/// The bundled glossary of classical senses, in the CC-CEDICT format, when the `classical` feature is enabled.
+#[cfg(feature = "classical")]
+static GLOSSARY: &str = include_str!("../data/classical/glossary.u8");
+#[cfg(not(feature = "classical"))]
+static GLOSSARY: &str = "";
+
+/// Parses the entries of the classical glossary, which is empty unless the `classical` feature is enabled.
+pub(crate) fn entries() -> Vec<WordEntry> {
+	let mut entries = parse_cedict(GLOSSARY).expect("The bundled classical glossary is valid CC-CEDICT");
+	for entry in &mut entries {
+		entry.source = SourceId::ClassicalGlossary;
+	}
+	entries
+}
@@ -0,0 +1,83 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Options for classifying queries as English, pinyin, or Chinese
+
+use crate::pinyin::{has_tones, is_pinyin_word};
+use crate::punctuation::is_han;
+use chinese_detection::{ChineseDetection, ClassificationResult};
+use std::collections::HashSet;
+
+/// # Ambiguous Query
+/// How `ClassificationOptions` classifies a query that reads as both English and pinyin, such as `me` or `ma`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AmbiguousQuery {
+	/// Use the statistical classifier of the `chinese_detection` crate, which compares how common the letter pairs of the query are in each language
+	#[default]
+	Detect,
+	/// Classify it as English
+	English,
+	/// Classify it as pinyin
+	Pinyin,
+}
+
+/// # Classification Options
+/// Rules applied by `Dictionary::classify`, and so by `query`, before falling back to the statistical classifier of the `chinese_detection` crate,
+/// set with `Dictionary::set_classification_options`. The statistical classifier often misroutes short queries, such as `ma5` to English,
+/// so these rules settle the queries that can only be one or the other, and let apps choose how to read the rest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassificationOptions {
+	/// Classify queries with a Chinese character as Chinese. On by default
+	pub han_means_chinese: bool,
+	/// Classify queries written with tone numbers or tone marks, such as `ma5` or `mā`, as pinyin. On by default
+	pub tones_mean_pinyin: bool,
+	/// Classify queries with a word that cannot be split into pinyin syllables, such as `test`, as English. On by default
+	pub require_valid_pinyin: bool,
+	/// How to classify queries that pass every other rule and read as both English and pinyin
+	pub ambiguous: AmbiguousQuery,
+	/// Queries that are always classified as English, in lowercase, such as `"you"` for an app whose users search in English far more than in pinyin
+	pub english_words: HashSet<String>,
+	/// Queries that are always classified as pinyin, in lowercase
+	pub pinyin_words: HashSet<String>,
+}
+
+impl Default for ClassificationOptions {
+	fn default() -> ClassificationOptions {
+		ClassificationOptions {
+			han_means_chinese: true,
+			tones_mean_pinyin: true,
+			require_valid_pinyin: true,
+			ambiguous: AmbiguousQuery::default(),
+			english_words: HashSet::new(),
+			pinyin_words: HashSet::new(),
+		}
+	}
+}
+
+impl ClassificationOptions {
+	/// Classifies a query with these rules, falling back to `detection`.
+	pub(crate) fn classify(&self, detection: &ChineseDetection, raw: &str) -> ClassificationResult {
+		let query = raw.trim().to_lowercase();
+		if self.english_words.contains(&query) {
+			return ClassificationResult::EN;
+		}
+		if self.pinyin_words.contains(&query) {
+			return ClassificationResult::PY;
+		}
+		if self.han_means_chinese && query.chars().any(is_han) {
+			return ClassificationResult::ZH;
+		}
+		if self.tones_mean_pinyin && has_tones(&query) {
+			return ClassificationResult::PY;
+		}
+		let words: Vec<&str> = query.split_whitespace().collect();
+		if self.require_valid_pinyin && !words.is_empty() && words.iter().any(|word| !is_pinyin_word(word)) {
+			return ClassificationResult::EN;
+		}
+
+		match self.ambiguous {
+			AmbiguousQuery::English if !words.is_empty() => ClassificationResult::EN,
+			AmbiguousQuery::Pinyin if !words.is_empty() => ClassificationResult::PY,
+			_ => detection.classify(raw),
+		}
+	}
+}
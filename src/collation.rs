@@ -0,0 +1,84 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Sorting Chinese strings by their pinyin
+
+use crate::chinese_dictionary::Dictionary;
+use crate::pinyin::split_tone_number;
+use crate::punctuation::is_han;
+use std::cmp::Ordering;
+
+/// # Pinyin Sort Key
+/// A key that sorts strings by their pinyin, returned by `PinyinCollator::sort_key`. Comparing keys gives the same order as `PinyinCollator::compare`,
+/// so the keys of a long list can be computed once and sorted with `sort_by_cached_key`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PinyinSortKey(Vec<(String, u8, char)>);
+
+/// # Pinyin Collator
+/// Compares Chinese strings by their pinyin, the way contact lists and glossaries are sorted for Chinese readers:
+/// syllable by syllable, first by the letters, then by the tone, and then by the character, so 张三 (Zhāng Sān) comes before 张山 (Zhāng Shān)
+/// and 妈 (mā) before 马 (mǎ). Text is segmented first, so each character is read the way it is pronounced in its word, such as the 长 of 长城 as cháng.
+/// Latin letters sort among the pinyin ignoring case, after digits, and other characters sort by their code point.
+pub struct PinyinCollator<'a> {
+	dictionary: &'a Dictionary,
+}
+
+impl<'a> PinyinCollator<'a> {
+	pub fn new(dictionary: &'a Dictionary) -> PinyinCollator<'a> {
+		PinyinCollator { dictionary }
+	}
+
+	/// Compares two strings by their pinyin.
+	pub fn compare(&self, a: &str, b: &str) -> Ordering {
+		self.sort_key(a).cmp(&self.sort_key(b))
+	}
+
+	/// Sorts strings by their pinyin, computing the key of each only once.
+	pub fn sort<S: AsRef<str>>(&self, items: &mut [S]) {
+		items.sort_by_cached_key(|item| self.sort_key(item.as_ref()));
+	}
+
+	/// The key that sorts a string by its pinyin: the letters and tone of each character's syllable, along with the character.
+	pub fn sort_key(&self, text: &str) -> PinyinSortKey {
+		let mut key: Vec<(String, u8, char)> = Vec::with_capacity(text.len() / 3);
+		let mut position = 0;
+		for range in self.dictionary.scan(text) {
+			self.push_characters(&text[position..range.start], &mut key);
+			let word = &text[range.clone()];
+			let characters: Vec<char> = word.chars().collect();
+			// The pinyin of a word is only used when it has one syllable per character, which leaves out words like 一点儿 read with erhua
+			let syllables = self.dictionary.entries_for_headword(word).into_iter()
+				.map(|entry| entry.pinyin_numbers.split(' ').collect::<Vec<&str>>())
+				.find(|syllables| syllables.len() == characters.len());
+			match syllables {
+				Some(syllables) => key.extend(characters.iter().zip(syllables).map(|(character, syllable)| syllable_key(syllable, *character))),
+				None => self.push_characters(word, &mut key),
+			}
+			position = range.end;
+		}
+		self.push_characters(&text[position..], &mut key);
+
+		PinyinSortKey(key)
+	}
+
+	/// Adds the keys of characters read on their own, with the pinyin of the first entry of each Chinese character.
+	fn push_characters(&self, text: &str, key: &mut Vec<(String, u8, char)>) {
+		let mut buffer = [0; 4];
+		for character in text.chars() {
+			let syllable = match is_han(character) {
+				true => self.dictionary.entries_for_headword(character.encode_utf8(&mut buffer)).into_iter()
+					.map(|entry| entry.pinyin_numbers.clone())
+					.find(|pinyin| !pinyin.contains(' ')),
+				false => None,
+			};
+			match syllable {
+				Some(syllable) => key.push(syllable_key(&syllable, character)),
+				None => key.push((character.to_lowercase().collect(), 0, character)),
+			}
+		}
+	}
+}
+
+fn syllable_key(syllable: &str, character: char) -> (String, u8, char) {
+	let (letters, tone) = split_tone_number(syllable);
+	(letters.to_lowercase().replace("u:", "v"), tone.unwrap_or(5), character)
+}
@@ -0,0 +1,81 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Words that typically appear next to each other
+
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// The bundled list of collocations when the `collocations` feature is enabled.
+/// Each line is a simplified word, a tab, and the words that typically appear next to it, most typical first.
+#[cfg(feature = "collocations")]
+static COLLOCATIONS: &str = include_str!("../data/collocations/collocations.u8");
+#[cfg(not(feature = "collocations"))]
+static COLLOCATIONS: &str = "";
+
+/// # Collocations
+/// The words that typically appear next to each word, such as 问题 and 困难 for 解决.
+/// The built-in dictionary has a curated list for common words with the `collocations` feature, and `from_corpus` counts them in a reference corpus.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Collocations {
+	partners: HashMap<String, Vec<String>>,
+}
+
+impl Collocations {
+	/// The bundled list, which is empty without the `collocations` feature.
+	pub(crate) fn bundled() -> Collocations {
+		let partners = COLLOCATIONS.lines()
+			.filter(|line| !line.is_empty() && !line.starts_with('#'))
+			.map(|line| {
+				let (word, partners) = line.split_once('\t').expect("Bundled collocations have a word and its partners");
+				(word.to_string(), partners.split(' ').map(str::to_string).collect())
+			})
+			.collect();
+		Collocations { partners }
+	}
+
+	/// # From Corpus
+	/// Segment every text in a reference corpus with `dictionary` and count how often each pair of dictionary words is adjacent, in either order.
+	/// Words are keyed by their simplified form, and partners adjacent fewer than `min_count` times are left out.
+	pub fn from_corpus<'s>(dictionary: &Dictionary, texts: impl Iterator<Item = &'s str>, min_count: u32) -> Collocations {
+		let mut counts: HashMap<String, HashMap<String, u32>> = HashMap::new();
+		for text in texts {
+			let ranges = dictionary.scan(text);
+			let words: Vec<Option<&str>> = ranges.iter()
+				.map(|range| dictionary.entries_for_headword(&text[range.clone()]).first().map(|entry| entry.simplified.as_str()))
+				.collect();
+			for (pair, range) in words.windows(2).zip(ranges.windows(2)) {
+				// Words separated by punctuation or other text are not adjacent
+				if let ([Some(first), Some(second)], true) = (pair, range[0].end == range[1].start) {
+					*counts.entry(first.to_string()).or_default().entry(second.to_string()).or_insert(0) += 1;
+					*counts.entry(second.to_string()).or_default().entry(first.to_string()).or_insert(0) += 1;
+				}
+			}
+		}
+
+		let partners = counts.into_iter().filter_map(|(word, partners)| {
+			let mut partners: Vec<(String, u32)> = partners.into_iter().filter(|(_, count)| *count >= min_count).collect();
+			partners.sort_by(|(a, a_count), (b, b_count)| (Reverse(a_count), a).cmp(&(Reverse(b_count), b)));
+			(!partners.is_empty()).then(|| (word, partners.into_iter().map(|(partner, _)| partner).collect()))
+		}).collect();
+		Collocations { partners }
+	}
+
+	/// # Partners
+	/// The simplified words that typically appear next to a simplified word, most typical or most frequent first.
+	pub fn partners(&self, word: &str) -> &[String] {
+		self.partners.get(word).map_or(&[], Vec::as_slice)
+	}
+}
+
+impl Dictionary {
+	/// # Collocations
+	/// Up to `n` words that naturally appear next to `entry`, such as 问题 for 解决, each as the first of its entries.
+	/// Uses the collocations set with `set_collocation_data`, which are the bundled list of the `collocations` feature for the built-in dictionary.
+	pub fn collocations(&self, entry: &WordEntry, n: usize) -> Vec<&WordEntry> {
+		self.collocation_data().partners(&entry.simplified).iter()
+			.filter_map(|partner| self.entries_for_headword(partner).first().copied())
+			.take(n)
+			.collect()
+	}
+}
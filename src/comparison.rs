@@ -0,0 +1,73 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Side-by-side comparison of near-synonyms
+
+use crate::builder::english_key;
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+use std::collections::HashMap;
+
+/// # Comparison Table
+/// Entries lined up side by side, as returned by `Dictionary::compare_entries`, for "what's the difference" panels such as 二 vs 两 or 会/能/可以.
+/// There is one column per entry, in the order the entries were given.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonTable<'a> {
+	pub columns: Vec<ComparisonColumn<'a>>,
+	/// The glosses that more than one of the entries have, in the order they first appear, e.g. `"two"` for 二 and 两
+	pub shared_glosses: Vec<&'a str>,
+}
+
+/// # Comparison Column
+/// One entry of a `ComparisonTable`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonColumn<'a> {
+	pub entry: &'a WordEntry,
+	pub pinyin: &'a str,
+	pub hsk: u8,
+	/// The frequency of the word as used by `SegmentationMode::Unigram`, or `0` if it is unknown
+	pub frequency: u32,
+	/// The simplified measure words of the entry
+	pub measure_words: Vec<&'a str>,
+	/// The glosses only this entry has, which are what sets it apart from the others
+	pub distinct_glosses: Vec<&'a str>,
+}
+
+impl Dictionary {
+	/// # Compare Entries
+	/// Line up the pinyin, HSK level, frequency, measure words, and glosses of several entries side by side.
+	/// Glosses are matched the way the English index matches them, ignoring case, punctuation, and parenthetical notes,
+	/// and are split into those shared by more than one entry and those distinct to each entry.
+	pub fn compare_entries<'a>(&self, entries: &[&'a WordEntry]) -> ComparisonTable<'a> {
+		let mut counts: HashMap<String, usize> = HashMap::new();
+		for entry in entries {
+			let mut keys: Vec<String> = entry.english.iter().map(|gloss| english_key(gloss)).collect();
+			keys.sort();
+			keys.dedup();
+			for key in keys {
+				*counts.entry(key).or_default() += 1;
+			}
+		}
+		let is_shared = |gloss: &str| counts.get(&english_key(gloss)).is_some_and(|count| *count > 1);
+
+		let mut shared_glosses: Vec<&'a str> = Vec::new();
+		let mut shared_keys: Vec<String> = Vec::new();
+		let columns = entries.iter().map(|entry| {
+			for gloss in entry.english.iter().filter(|gloss| is_shared(gloss)) {
+				let key = english_key(gloss);
+				if !shared_keys.contains(&key) {
+					shared_keys.push(key);
+					shared_glosses.push(gloss);
+				}
+			}
+			ComparisonColumn {
+				entry,
+				pinyin: &entry.pinyin_marks,
+				hsk: entry.hsk,
+				frequency: self.frequency(&entry.simplified),
+				measure_words: entry.measure_words.iter().map(|measure_word| measure_word.simplified.as_str()).collect(),
+				distinct_glosses: entry.english.iter().filter(|gloss| !is_shared(gloss)).map(String::as_str).collect(),
+			}
+		}).collect();
+
+		ComparisonTable { columns, shared_glosses }
+	}
+}
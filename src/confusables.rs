@@ -0,0 +1,30 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: A table of visually confusable Chinese characters
+
+static CONFUSABLES: &str = include_str!("../data/confusables.u8");
+
+/// # Similar Characters
+/// Find the characters that are easily mistaken for `character`, such as `'末'` for `'未'`, or `'已'` and `'巳'` for `'己'`.
+/// Useful for "did you mean" suggestions for learners, and for correcting OCR and handwriting recognition errors.
+/// Returns an empty `Vec` for characters without any known look-alikes.
+pub fn similar_characters(character: char) -> Vec<char> {
+	let mut similar: Vec<char> = Vec::new();
+	for group in CONFUSABLES.lines().filter(|line| !line.starts_with('#')) {
+		let members: Vec<char> = group.split_whitespace().filter_map(|member| member.chars().next()).collect();
+		if members.contains(&character) {
+			for member in members {
+				if member != character && !similar.contains(&member) {
+					similar.push(member);
+				}
+			}
+		}
+	}
+
+	similar
+}
+
+/// Whether `a` and `b` are listed together as look-alikes.
+pub(crate) fn is_confusable(a: char, b: char) -> bool {
+	similar_characters(a).contains(&b)
+}
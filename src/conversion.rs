@@ -0,0 +1,62 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Bulk conversion of entries to a single script for display
+
+use crate::chinese_dictionary::{Dictionary, Script, WordEntry};
+use std::collections::{BTreeMap, HashMap};
+
+/// # Converted Entry
+/// An entry along with its headword and measure words in a single script, as returned by `convert_entries`.
+/// The strings are borrowed from the entry, so nothing is allocated per field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvertedEntry<'a> {
+	pub entry: &'a WordEntry,
+	pub headword: &'a str,
+	pub measure_words: Vec<&'a str>,
+}
+
+/// # Convert Entries
+/// Show a collection of entries in a single script, e.g. for displaying a page of results to a user who reads traditional characters.
+/// Every entry already stores both scripts, so this picks the right field of each headword and measure word instead of converting characters,
+/// which is much faster than calling `convert_to_traditional` on every field and never guesses wrong for one-to-many conversions like 发 to 發 or 髮.
+pub fn convert_entries<'a>(entries: &[&'a WordEntry], script: Script) -> Vec<ConvertedEntry<'a>> {
+	entries.iter().map(|entry| {
+		let (headword, measure_words) = match script {
+			Script::Simplified => (&entry.simplified, entry.measure_words.iter().map(|measure_word| measure_word.simplified.as_str()).collect()),
+			Script::Traditional => (&entry.traditional, entry.measure_words.iter().map(|measure_word| measure_word.traditional.as_str()).collect()),
+		};
+		ConvertedEntry { entry, headword, measure_words }
+	}).collect()
+}
+
+impl Dictionary {
+	/// # Char Mapping
+	/// The character level mapping between the scripts, collected from the headwords of every entry, for tools such as font subsetters
+	/// or converters that want to reuse the dictionary's data. With `Script::Traditional` each simplified character is mapped to its
+	/// traditional forms, e.g. 发 to 發 and 髮, and with `Script::Simplified` each traditional character to its simplified forms.
+	///
+	/// Only characters with a form other than themselves are listed, in code point order. The forms of each character are ordered
+	/// by the number of headwords they appear in, so the most common comes first, and include the character itself when some headwords keep it, e.g. 干 to 乾, 幹, and 干.
+	pub fn char_mapping(&self, to: Script) -> impl Iterator<Item = (char, Vec<char>)> {
+		let mut counts: HashMap<char, HashMap<char, usize>> = HashMap::new();
+		for entry in self.entries() {
+			let (from_word, to_word) = match to {
+				Script::Traditional => (&entry.simplified, &entry.traditional),
+				Script::Simplified => (&entry.traditional, &entry.simplified),
+			};
+			if from_word.chars().count() != to_word.chars().count() {
+				continue;
+			}
+			for (from, to) in from_word.chars().zip(to_word.chars()) {
+				*counts.entry(from).or_default().entry(to).or_default() += 1;
+			}
+		}
+
+		let mapping: BTreeMap<char, Vec<char>> = counts.into_iter().filter(|(from, forms)| forms.keys().any(|form| form != from)).map(|(from, forms)| {
+			let mut forms: Vec<(char, usize)> = forms.into_iter().collect();
+			forms.sort_by_key(|(form, count)| (std::cmp::Reverse(*count), *form));
+			(from, forms.into_iter().map(|(form, _)| form).collect())
+		}).collect();
+		mapping.into_iter()
+	}
+}
@@ -0,0 +1,81 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Word frequency statistics over a corpus of text
+
+use crate::chinese_dictionary::Dictionary;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+
+/// How often a single word occurs in a corpus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordCount {
+	/// The word as it was written in the corpus
+	pub word: String,
+	pub count: usize,
+	/// The ids of every entry with this headword, e.g. both 行 xíng and 行 háng
+	pub word_ids: Vec<u32>,
+	/// The lowest HSK level among those entries, or `0` when none of them are in the HSK
+	pub hsk: u8,
+}
+
+/// # Corpus Stats
+/// Statistics about the dictionary words found in a corpus, as returned by `corpus_stats`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorpusStats {
+	/// The number of words in the corpus
+	pub tokens: usize,
+	/// The number of distinct words in the corpus
+	pub types: usize,
+	/// Every distinct word, from the most to the least frequent. Words with the same count are ordered by their first occurrence.
+	pub words: Vec<WordCount>,
+	/// The number of tokens at each HSK level, with `0` counting the tokens outside the HSK
+	pub hsk_tokens: BTreeMap<u8, usize>,
+}
+
+impl CorpusStats {
+	/// # HSK Coverage
+	/// The fraction of tokens that belong to HSK level `level` or below, between `0.0` and `1.0`.
+	/// This is how much of the corpus a learner who has studied up to `level` would recognize.
+	pub fn hsk_coverage(&self, level: u8) -> f64 {
+		if self.tokens == 0 {
+			return 0.0;
+		}
+		let covered: usize = self.hsk_tokens.range(1..=level.max(1)).map(|(_, count)| count).sum();
+		covered as f64 / self.tokens as f64
+	}
+}
+
+impl Dictionary {
+	/// # Corpus Stats
+	/// Segment every text in a corpus and count the dictionary words in it, including the number of tokens and types,
+	/// the frequency of each word along with its `word_id`s, and how many tokens belong to each HSK level.
+	/// Text that is not part of a dictionary word, such as punctuation, is not counted.
+	pub fn corpus_stats<'s>(&self, texts: impl Iterator<Item = &'s str>) -> CorpusStats {
+		let mut positions: HashMap<&'s str, usize> = HashMap::new();
+		let mut words: Vec<WordCount> = Vec::new();
+		let mut hsk_tokens: BTreeMap<u8, usize> = BTreeMap::new();
+		let mut tokens = 0;
+
+		for text in texts {
+			for range in self.scan(text) {
+				let word = &text[range];
+				let position = *positions.entry(word).or_insert_with(|| {
+					let entries = self.entries_for_headword(word);
+					words.push(WordCount {
+						word: word.to_string(),
+						count: 0,
+						word_ids: entries.iter().map(|entry| entry.word_id).collect(),
+						hsk: entries.iter().map(|entry| entry.hsk).filter(|hsk| *hsk != 0).min().unwrap_or(0),
+					});
+					words.len() - 1
+				});
+				words[position].count += 1;
+				*hsk_tokens.entry(words[position].hsk).or_insert(0) += 1;
+				tokens += 1;
+			}
+		}
+
+		words.sort_by_key(|word| Reverse(word.count));
+		CorpusStats { tokens, types: words.len(), words, hsk_tokens }
+	}
+}
@@ -0,0 +1,92 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Field by field differences between two versions of an entry
+
+use crate::chinese_dictionary::{MeasureWord, WordEntry};
+
+/// # Entry Field
+/// A field of a `WordEntry`, as listed by `EntryDiff::changed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntryField {
+	Traditional,
+	Simplified,
+	PinyinMarks,
+	PinyinNumbers,
+	English,
+	ToneMarks,
+	MeasureWords,
+	Hsk,
+	WordId,
+	Domain,
+	Year,
+	Source,
+}
+
+/// # Entry Diff
+/// The differences between two versions of an entry, as returned by `diff_entries`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EntryDiff {
+	/// The fields that differ, in the order they are declared in `WordEntry`. `hash` is left out, since it follows from the headwords and pinyin.
+	pub changed: Vec<EntryField>,
+	/// The glosses only the second entry has, in its order
+	pub glosses_added: Vec<String>,
+	/// The glosses only the first entry has, in its order
+	pub glosses_removed: Vec<String>,
+	/// The measure words only the second entry has, in its order
+	pub measure_words_added: Vec<MeasureWord>,
+	/// The measure words only the first entry has, in its order
+	pub measure_words_removed: Vec<MeasureWord>,
+}
+
+impl EntryDiff {
+	/// Checks if the two entries are the same.
+	pub fn is_empty(&self) -> bool {
+		self.changed.is_empty()
+	}
+
+	/// Checks if a field differs between the two entries.
+	pub fn is_changed(&self, field: EntryField) -> bool {
+		self.changed.contains(&field)
+	}
+
+	/// # Conflicts
+	/// The fields that both this diff and `other` change, e.g. with this diff going from an upstream entry to the user's edit of it
+	/// and `other` from the same upstream entry to its next release. The other fields can be merged without asking the user.
+	/// English glosses and measure words do not conflict when both diffs add and remove the same items.
+	pub fn conflicts(&self, other: &EntryDiff) -> Vec<EntryField> {
+		self.changed.iter().copied().filter(|field| other.is_changed(*field)).filter(|field| match field {
+			EntryField::English => self.glosses_added != other.glosses_added || self.glosses_removed != other.glosses_removed,
+			EntryField::MeasureWords => self.measure_words_added != other.measure_words_added || self.measure_words_removed != other.measure_words_removed,
+			_ => true,
+		}).collect()
+	}
+}
+
+/// # Diff Entries
+/// List the fields that differ between two versions of an entry, e.g. a user's edited copy and the upstream entry it was made from,
+/// along with the glosses and measure words added and removed. Glosses and measure words that only moved are reported as a change
+/// to the field without being added or removed.
+pub fn diff_entries(a: &WordEntry, b: &WordEntry) -> EntryDiff {
+	let mut diff = EntryDiff::default();
+	let fields = [
+		(EntryField::Traditional, a.traditional != b.traditional),
+		(EntryField::Simplified, a.simplified != b.simplified),
+		(EntryField::PinyinMarks, a.pinyin_marks != b.pinyin_marks),
+		(EntryField::PinyinNumbers, a.pinyin_numbers != b.pinyin_numbers),
+		(EntryField::English, a.english != b.english),
+		(EntryField::ToneMarks, a.tone_marks != b.tone_marks),
+		(EntryField::MeasureWords, a.measure_words != b.measure_words),
+		(EntryField::Hsk, a.hsk != b.hsk),
+		(EntryField::WordId, a.word_id != b.word_id),
+		(EntryField::Domain, a.domain != b.domain),
+		(EntryField::Year, a.year != b.year),
+		(EntryField::Source, a.source != b.source),
+	];
+	diff.changed = fields.iter().filter(|(_, changed)| *changed).map(|(field, _)| *field).collect();
+	diff.glosses_added = b.english.iter().filter(|gloss| !a.english.contains(gloss)).cloned().collect();
+	diff.glosses_removed = a.english.iter().filter(|gloss| !b.english.contains(gloss)).cloned().collect();
+	diff.measure_words_added = b.measure_words.iter().filter(|measure_word| !a.measure_words.contains(measure_word)).cloned().collect();
+	diff.measure_words_removed = a.measure_words.iter().filter(|measure_word| !b.measure_words.contains(measure_word)).cloned().collect();
+
+	diff
+}
@@ -0,0 +1,88 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Tracing how a query is classified and which index keys it looks up
+
+use crate::chinese_dictionary::{Dictionary, Index, WordEntry};
+use chinese_detection::ClassificationResult;
+use std::cell::RefCell;
+
+thread_local! {
+	/// The events of the `explain_query` running on this thread, if any.
+	static TRACE: RefCell<Option<Vec<TraceEvent>>> = const { RefCell::new(None) };
+}
+
+/// A step taken while running a query, recorded by `Dictionary::explain_query`.
+#[derive(Debug, PartialEq)]
+pub enum TraceEvent {
+	/// The query was longer than `InputLimits::max_query_bytes`, so nothing was searched
+	InputTooLarge { length: usize, limit: usize },
+	/// The query was written with operators such as `py:` and `hsk:`, and was split into this many clauses
+	Operators(usize),
+	/// The query, or a clause of it, was wrapped in double quotes and matched as an exact English gloss
+	ExactPhrase(String),
+	/// A word was expanded from the configured double pinyin scheme and found this many entries
+	Shuangpin { word: String, hits: usize },
+	/// The query, or a clause of it, was classified as English, pinyin, or Chinese. `UN` means it could not be classified and nothing was searched
+	Classified(ClassificationResult),
+	/// A key was looked up in an index, and found this many entries
+	Probe { index: Index, key: String, hits: usize },
+	/// The `hsk:` filters of a query with operators removed this many entries
+	HskFilter { removed: usize },
+	/// `QueryOptions::years` removed this many entries
+	YearsFilter { removed: usize },
+	/// The results were reordered by the ranker set with `set_ranker`
+	Ranked,
+}
+
+/// How a query was run, returned by `Dictionary::explain_query`.
+#[derive(Debug, PartialEq)]
+pub struct QueryTrace {
+	/// The query as given
+	pub raw: String,
+	/// The query after the normalization configured with `set_normalizer`
+	pub normalized: String,
+	/// Every step taken, in order
+	pub events: Vec<TraceEvent>,
+	/// The number of entries `query` returns
+	pub results: usize,
+}
+
+impl QueryTrace {
+	/// # Probes
+	/// The index lookups of the query, with the index, the key, and how many entries were found.
+	pub fn probes(&self) -> impl Iterator<Item = (Index, &str, usize)> {
+		self.events.iter().filter_map(|event| match event {
+			TraceEvent::Probe { index, key, hits } => Some((*index, key.as_str(), *hits)),
+			_ => None,
+		})
+	}
+}
+
+/// Records an event if an `explain_query` is running on this thread. The event is only built when it is recorded.
+pub(crate) fn record<F: FnOnce() -> TraceEvent>(event: F) {
+	TRACE.with(|trace| {
+		if let Some(events) = trace.borrow_mut().as_mut() {
+			events.push(event());
+		}
+	});
+}
+
+/// Records a lookup of `key` in `index` that found `items`.
+pub(crate) fn probe(index: Index, key: &str, items: Option<&[u32]>) {
+	record(|| TraceEvent::Probe { index, key: key.to_string(), hits: items.map_or(0, <[u32]>::len) });
+}
+
+impl Dictionary {
+	/// # Explain Query
+	/// Run a query the same way as `query` and record how it was run: the normalized query, how it was classified,
+	/// every key looked up in each index with the number of entries found, and the filters applied.
+	/// This is meant for debugging why a lookup returns nothing or the wrong entries. Segmentation is not traced, only the lookups of the words it produces.
+	pub fn explain_query(&self, raw: &str) -> QueryTrace {
+		TRACE.with(|trace| *trace.borrow_mut() = Some(Vec::new()));
+		let mut entries: Vec<&WordEntry> = Vec::new();
+		self.query_into(raw, &mut entries);
+		let events = TRACE.with(|trace| trace.borrow_mut().take()).unwrap_or_default();
+
+		QueryTrace { raw: raw.to_string(), normalized: self.normalize(raw), events, results: entries.len() }
+	}
+}
@@ -0,0 +1,33 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: The characters needed to subset a font for some text or entries
+
+use crate::chinese_dictionary::WordEntry;
+use std::collections::BTreeSet;
+
+/// # Unique Characters
+/// The distinct characters of `text` in code point order, without whitespace or control characters, for generating a font subset that can display it.
+pub fn unique_characters(text: &str) -> BTreeSet<char> {
+	text.chars().filter(|character| !character.is_whitespace() && !character.is_control()).collect()
+}
+
+/// # Characters for Entries
+/// The distinct characters needed to display a list of entries in code point order: both the traditional and simplified headwords, the pinyin with tone marks,
+/// the English glosses, and both scripts of the measure words.
+pub fn characters_for_entries<'a>(entries: impl IntoIterator<Item = &'a WordEntry>) -> BTreeSet<char> {
+	let mut characters = BTreeSet::new();
+	for entry in entries {
+		characters.extend(unique_characters(&entry.traditional));
+		characters.extend(unique_characters(&entry.simplified));
+		characters.extend(unique_characters(&entry.pinyin_marks));
+		for gloss in &entry.english {
+			characters.extend(unique_characters(gloss));
+		}
+		for measure_word in &entry.measure_words {
+			characters.extend(unique_characters(&measure_word.traditional));
+			characters.extend(unique_characters(&measure_word.simplified));
+			characters.extend(unique_characters(&measure_word.pinyin_marks));
+		}
+	}
+	characters
+}
@@ -0,0 +1,196 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Formatted rendering of dictionary entries
+
+use crate::chinese_dictionary::{MeasureWord, WordEntry};
+use std::error::Error;
+use std::fmt;
+
+/// An error encountered while parsing a `FormatSpec` template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatError {
+	/// The template referenced a field that does not exist on `WordEntry`
+	UnknownField(String),
+	/// The template used an option other than `join`
+	UnknownOption(String),
+	/// A `{` was opened without a matching `}`
+	UnclosedBrace,
+}
+
+impl fmt::Display for FormatError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			FormatError::UnknownField(field) => write!(f, "unknown field `{}`", field),
+			FormatError::UnknownOption(option) => write!(f, "unknown option `{}`", option),
+			FormatError::UnclosedBrace => write!(f, "unclosed `{{` in template"),
+		}
+	}
+}
+
+impl Error for FormatError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+	Traditional,
+	Simplified,
+	PinyinMarks,
+	PinyinNumbers,
+	English,
+	ToneMarks,
+	Hash,
+	MeasureWords,
+	Hsk,
+	WordId,
+}
+
+impl Field {
+	fn parse(name: &str) -> Option<Field> {
+		match name {
+			"traditional" => Some(Field::Traditional),
+			"simplified" => Some(Field::Simplified),
+			"pinyin_marks" => Some(Field::PinyinMarks),
+			"pinyin_numbers" => Some(Field::PinyinNumbers),
+			"english" => Some(Field::English),
+			"tone_marks" => Some(Field::ToneMarks),
+			"hash" => Some(Field::Hash),
+			"measure_words" => Some(Field::MeasureWords),
+			"hsk" => Some(Field::Hsk),
+			"word_id" => Some(Field::WordId),
+			_ => None,
+		}
+	}
+
+	fn default_separator(self) -> &'static str {
+		match self {
+			Field::English => "; ",
+			_ => ", ",
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+	Literal(String),
+	Field(Field, String),
+}
+
+/// # Format Spec
+/// A parsed template for rendering a `WordEntry` as text.
+///
+/// Fields of `WordEntry` are referenced by name inside braces, e.g. `"{traditional} [{pinyin_marks}]"`.
+/// List fields (`english`, `tone_marks`, and `measure_words`) accept a `join` option to choose the separator,
+/// e.g. `"{english;join=; }"`. English glosses are joined with `"; "` and other lists with `", "` by default.
+/// Measure words are rendered in the CC-CEDICT style, e.g. `個|个[ge4]`. Use `{{` and `}}` for literal braces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatSpec {
+	parts: Vec<Part>,
+}
+
+impl FormatSpec {
+	pub fn new(template: &str) -> Result<FormatSpec, FormatError> {
+		let mut parts: Vec<Part> = Vec::new();
+		let mut literal = String::new();
+		let mut chars = template.chars().peekable();
+
+		while let Some(c) = chars.next() {
+			match c {
+				'{' if chars.peek() == Some(&'{') => {
+					chars.next();
+					literal.push('{');
+				},
+				'}' if chars.peek() == Some(&'}') => {
+					chars.next();
+					literal.push('}');
+				},
+				'{' => {
+					let mut placeholder = String::new();
+					loop {
+						match chars.next() {
+							Some('}') => break,
+							Some(c) => placeholder.push(c),
+							None => return Err(FormatError::UnclosedBrace),
+						}
+					}
+					if !literal.is_empty() {
+						parts.push(Part::Literal(std::mem::take(&mut literal)));
+					}
+					parts.push(FormatSpec::parse_placeholder(&placeholder)?);
+				},
+				_ => literal.push(c),
+			}
+		}
+		if !literal.is_empty() {
+			parts.push(Part::Literal(literal));
+		}
+
+		Ok(FormatSpec { parts })
+	}
+
+	fn parse_placeholder(placeholder: &str) -> Result<Part, FormatError> {
+		let (name, options) = match placeholder.find(';') {
+			Some(index) => (&placeholder[..index], Some(&placeholder[index + 1..])),
+			None => (placeholder, None),
+		};
+		let field = Field::parse(name.trim()).ok_or_else(|| FormatError::UnknownField(name.trim().to_string()))?;
+		let separator = match options {
+			Some(option) => match option.strip_prefix("join=") {
+				Some(separator) => separator.to_string(),
+				None => return Err(FormatError::UnknownOption(option.to_string())),
+			},
+			None => field.default_separator().to_string(),
+		};
+
+		Ok(Part::Field(field, separator))
+	}
+
+	/// Render an entry using this template.
+	pub fn render(&self, entry: &WordEntry) -> String {
+		let mut output = String::new();
+
+		for part in &self.parts {
+			match part {
+				Part::Literal(text) => output.push_str(text),
+				Part::Field(field, separator) => output.push_str(&match field {
+					Field::Traditional => entry.traditional.clone(),
+					Field::Simplified => entry.simplified.clone(),
+					Field::PinyinMarks => entry.pinyin_marks.clone(),
+					Field::PinyinNumbers => entry.pinyin_numbers.clone(),
+					Field::English => entry.english.join(separator),
+					Field::ToneMarks => entry.tone_marks.iter().map(|tone| tone.to_string()).collect::<Vec<String>>().join(separator),
+					Field::Hash => entry.hash.to_string(),
+					Field::MeasureWords => entry.measure_words.iter().map(|measure_word| measure_word.to_string()).collect::<Vec<String>>().join(separator),
+					Field::Hsk => entry.hsk.to_string(),
+					Field::WordId => entry.word_id.to_string(),
+				}),
+			}
+		}
+
+		output
+	}
+}
+
+impl WordEntry {
+	/// # Format
+	/// Render this entry as text using a `FormatSpec` template.
+	pub fn format(&self, spec: &FormatSpec) -> String {
+		spec.render(self)
+	}
+}
+
+/// Renders the entry as `traditional simplified [pinyin_marks] english`, with glosses separated by `"; "`.
+impl fmt::Display for WordEntry {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} {} [{}] {}", self.traditional, self.simplified, self.pinyin_marks, self.english.join("; "))
+	}
+}
+
+/// Renders the measure word in the CC-CEDICT style, e.g. `個|个[ge4]`.
+impl fmt::Display for MeasureWord {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.traditional == self.simplified {
+			write!(f, "{}[{}]", self.traditional, self.pinyin_numbers)
+		} else {
+			write!(f, "{}|{}[{}]", self.traditional, self.simplified, self.pinyin_numbers)
+		}
+	}
+}
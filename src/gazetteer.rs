@@ -0,0 +1,30 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Optional gazetteers of proper nouns missing from CC-CEDICT
+
+use crate::builder::parse_cedict;
+use crate::chinese_dictionary::WordEntry;
+use crate::source::SourceId;
+
+/// The bundled gazetteers enabled through cargo features, in the CC-CEDICT format.
+static SOURCES: &[(SourceId, &str)] = &[
+	#[cfg(feature = "gazetteer-places")]
+	(SourceId::GazetteerPlaces, include_str!("../data/gazetteer/china.u8")),
+	#[cfg(feature = "gazetteer-places")]
+	(SourceId::GazetteerPlaces, include_str!("../data/gazetteer/world.u8")),
+	#[cfg(feature = "gazetteer-brands")]
+	(SourceId::GazetteerBrands, include_str!("../data/gazetteer/brands.u8")),
+];
+
+/// Parses the entries of every enabled gazetteer.
+pub(crate) fn entries() -> Vec<WordEntry> {
+	SOURCES.iter()
+		.flat_map(|(source_id, source)| {
+			let mut entries = parse_cedict(source).expect("Bundled gazetteers are valid CC-CEDICT");
+			for entry in &mut entries {
+				entry.source = *source_id;
+			}
+			entries
+		})
+		.collect()
+}
@@ -0,0 +1,99 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Cleanup of the CEDICT conventions in English glosses for display
+
+use crate::chinese_dictionary::{Script, WordEntry};
+use crate::punctuation::is_han;
+
+/// Labels for the register or region of a word, such as `(coll.)` or `(Tw)`, which say how a word is used rather than what it means.
+/// Subject labels, such as `(math.)`, are kept, since they tell senses apart.
+static LABELS: &[&str] = &[
+	"abbr.", "arch.", "archaic", "bound form", "Cantonese", "classical", "coll.", "colloquial", "derog.", "dialect", "euphemism", "fig.", "formal",
+	"HK", "honorific", "humble", "idiom", "Internet slang", "literary", "loanword", "neologism", "old", "onom.", "polite", "PRC", "slang", "Tw", "vulgar",
+];
+
+/// # Gloss Cleanup
+/// Which CEDICT conventions `clean` removes from an English gloss for display, e.g. `(coll.) see 彈幕|弹幕[dan4 mu4]` becomes `see 弹幕`.
+/// The glosses in `WordEntry::english` are left as they are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlossCleanup {
+	/// Remove register and region labels, such as `(coll.)`, `(Tw)`, or `(idiom)`. On by default
+	pub labels: bool,
+	/// Remove the pinyin in brackets after a word in Chinese characters, such as `[dan4 mu4]`. On by default
+	pub pinyin: bool,
+	/// Drop glosses that list measure words, such as `CL:個|个[ge4]`. On by default
+	pub measure_words: bool,
+	/// Keep only one script of words written as `traditional|simplified`, or both when `None`. Simplified by default
+	pub references: Option<Script>,
+}
+
+impl Default for GlossCleanup {
+	fn default() -> GlossCleanup {
+		GlossCleanup { labels: true, pinyin: true, measure_words: true, references: Some(Script::Simplified) }
+	}
+}
+
+fn is_delimiter(character: char) -> bool {
+	character.is_whitespace() || "()[],;:/.?!".contains(character)
+}
+
+impl GlossCleanup {
+	/// # Clean
+	/// The gloss without the conventions this cleanup removes, or an empty string when nothing is left to display.
+	pub fn clean(&self, gloss: &str) -> String {
+		if self.measure_words && gloss.trim_start().starts_with("CL:") {
+			return String::new();
+		}
+
+		let mut cleaned = String::with_capacity(gloss.len());
+		let mut rest = gloss;
+		while let Some(character) = rest.chars().next() {
+			if character == '(' && self.labels {
+				if let Some(end) = rest.find(')').filter(|end| rest[1..*end].split(',').all(|label| LABELS.contains(&label.trim()))) {
+					rest = &rest[end + 1..];
+					continue;
+				}
+			}
+			if is_delimiter(character) {
+				cleaned.push(character);
+				rest = &rest[character.len_utf8()..];
+				continue;
+			}
+
+			let end = rest.find(is_delimiter).unwrap_or(rest.len());
+			let word = &rest[..end];
+			rest = &rest[end..];
+			if !word.chars().any(is_han) {
+				cleaned.push_str(word);
+				continue;
+			}
+			match (self.references, word.split_once('|')) {
+				(Some(Script::Traditional), Some((traditional, _))) => cleaned.push_str(traditional),
+				(Some(Script::Simplified), Some((_, simplified))) => cleaned.push_str(simplified),
+				_ => cleaned.push_str(word),
+			}
+			if self.pinyin && rest.starts_with('[') {
+				if let Some(end) = rest.find(']') {
+					rest = &rest[end + 1..];
+				}
+			}
+		}
+
+		cleaned.split_whitespace().collect::<Vec<&str>>().join(" ").replace(" ,", ",").replace(" ;", ";").replace("( ", "(").replace(" )", ")")
+	}
+}
+
+/// # Clean Gloss
+/// Remove the CEDICT conventions in an English gloss for display with the default `GlossCleanup`,
+/// e.g. `(Tw) to cram` becomes `to cram` and `see 開金|开金[kai1 jin1]` becomes `see 开金`.
+pub fn clean_gloss(gloss: &str) -> String {
+	GlossCleanup::default().clean(gloss)
+}
+
+impl WordEntry {
+	/// # Clean English
+	/// The glosses of this entry cleaned for display with `cleanup`, leaving out any gloss with nothing left to display.
+	pub fn clean_english(&self, cleanup: &GlossCleanup) -> Vec<String> {
+		self.english.iter().map(|gloss| cleanup.clean(gloss)).filter(|gloss| !gloss.is_empty()).collect()
+	}
+}
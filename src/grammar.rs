@@ -0,0 +1,39 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Optional grammar patterns linked to entries
+
+use crate::chinese_dictionary::WordEntry;
+
+/// The bundled grammar patterns, when the `grammar-patterns` feature is enabled.
+#[cfg(feature = "grammar-patterns")]
+static PATTERNS: &str = include_str!("../data/grammar/patterns.u8");
+#[cfg(not(feature = "grammar-patterns"))]
+static PATTERNS: &str = "";
+
+/// # Grammar Pattern
+/// A grammar pattern a word is used in, such as `Subj + 把 + Obj + Verb + Complement` for 把.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrammarPattern {
+	/// The structure of the pattern, e.g. `越 + A + 越 + B`
+	pub pattern: &'static str,
+	/// What the pattern means, in English
+	pub meaning: &'static str,
+	/// An example sentence in simplified characters
+	pub example: &'static str,
+}
+
+/// # Grammar Patterns
+/// The grammar patterns an entry is used in, matched by its simplified headword, e.g. `越…越…` for 越.
+/// Requires the `grammar-patterns` feature, and returns an empty `Vec` without it.
+pub fn grammar_patterns(entry: &WordEntry) -> Vec<GrammarPattern> {
+	PATTERNS.lines()
+		.filter(|line| !line.starts_with('#'))
+		.filter_map(|line| {
+			let mut fields = line.split('\t');
+			match (fields.next(), fields.next(), fields.next(), fields.next()) {
+				(Some(headword), Some(pattern), Some(meaning), Some(example)) if headword == entry.simplified => Some(GrammarPattern { pattern, meaning, example }),
+				_ => None,
+			}
+		})
+		.collect()
+}
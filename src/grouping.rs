@@ -0,0 +1,59 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Grouping of query results by headword
+
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+use std::collections::{BTreeMap, HashMap};
+
+/// # Headword Result
+/// Every entry of a result list that shares the same headwords, such as the 行 xíng and 行 háng entries, for showing one card per word.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadwordResult<'a> {
+	pub simplified: &'a str,
+	pub traditional: &'a str,
+	/// The entries in the order they appeared in the results
+	pub entries: Vec<&'a WordEntry>,
+}
+
+/// # Group by Headword
+/// Merge results that share the same simplified and traditional headwords, in the order each headword first appears.
+/// Entries are kept separate by default because their `word_id`s differ; this groups them by their surface form instead.
+pub fn group_by_headword<'a>(entries: &[&'a WordEntry]) -> Vec<HeadwordResult<'a>> {
+	let mut groups: Vec<HeadwordResult<'a>> = Vec::new();
+	let mut positions: HashMap<(&str, &str), usize> = HashMap::new();
+	for entry in entries {
+		let position = *positions.entry((&entry.simplified, &entry.traditional)).or_insert_with(|| {
+			groups.push(HeadwordResult { simplified: &entry.simplified, traditional: &entry.traditional, entries: Vec::new() });
+			groups.len() - 1
+		});
+		groups[position].entries.push(entry);
+	}
+
+	groups
+}
+
+/// # Group by Pinyin Initial
+/// Split entries into sections by the first letter of their pinyin, in uppercase, for the A, B, C… section headers of vocabulary lists and paper dictionaries.
+/// Entries whose pinyin does not start with a letter, such as those starting with a digit, go in a `'#'` section, which sorts before `'A'`.
+/// Entries keep their order within each section, so sort them first, e.g. with `PinyinCollator`, to get a list in dictionary order.
+pub fn group_by_pinyin_initial<'a>(entries: &[&'a WordEntry]) -> BTreeMap<char, Vec<&'a WordEntry>> {
+	let mut sections: BTreeMap<char, Vec<&'a WordEntry>> = BTreeMap::new();
+	for entry in entries {
+		let initial = match entry.pinyin_numbers.chars().next() {
+			Some(letter) if letter.is_ascii_alphabetic() => letter.to_ascii_uppercase(),
+			_ => '#',
+		};
+		sections.entry(initial).or_default().push(entry);
+	}
+
+	sections
+}
+
+impl Dictionary {
+	/// # Query Grouped
+	/// Query the dictionary the same way as `query`, with the results grouped by headword using `group_by_headword`.
+	/// Returns an empty `Vec` when the query could not be classified.
+	pub fn query_grouped(&self, raw: &str) -> Vec<HeadwordResult<'_>> {
+		group_by_headword(&self.query(raw).unwrap_or_default())
+	}
+}
@@ -0,0 +1,57 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Stable hashing of dictionary entries
+
+use crate::chinese_dictionary::WordEntry;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The first `word_id` of the range reserved for entries added by applications, which `allocate_word_id` assigns from.
+/// The built-in dictionary and its optional features only use ids below it.
+pub const USER_WORD_ID_START: u32 = 0x8000_0000;
+
+/// A 64-bit FNV-1a hasher over a sequence of fields.
+/// Each field is followed by a `0xFF` byte, which cannot appear in UTF-8 text, so adjacent fields cannot run together.
+/// Unlike `std::collections::hash_map::DefaultHasher`, the output is the same on every platform and Rust release.
+pub(crate) struct FieldHasher(u64);
+
+impl FieldHasher {
+	pub(crate) fn new() -> FieldHasher {
+		FieldHasher(FNV_OFFSET_BASIS)
+	}
+
+	pub(crate) fn write(&mut self, field: &[u8]) {
+		for byte in field.iter().chain(std::iter::once(&0xFF)) {
+			self.0 ^= u64::from(*byte);
+			self.0 = self.0.wrapping_mul(FNV_PRIME);
+		}
+	}
+
+	pub(crate) fn finish(&self) -> u64 {
+		self.0
+	}
+}
+
+/// The value of `WordEntry::hash`: the FNV-1a hash of `traditional`, `simplified`, and `pinyin_numbers`.
+pub(crate) fn entry_hash(entry: &WordEntry) -> u64 {
+	let mut hasher = FieldHasher::new();
+	hasher.write(entry.traditional.as_bytes());
+	hasher.write(entry.simplified.as_bytes());
+	hasher.write(entry.pinyin_numbers.as_bytes());
+	hasher.finish()
+}
+
+/// # Allocate Word ID
+/// A `word_id` for a custom entry, derived from its traditional headword and `pinyin_numbers`, such as `allocate_word_id("電腦", "dian4 nao3")`.
+/// The id is always in the reserved range starting at `USER_WORD_ID_START`, so it never collides with a built-in entry,
+/// and it is the same on every machine, so custom dictionaries built separately agree on their ids. Whitespace in the pinyin is normalized.
+/// Two different custom words get the same id with a chance of about one in two billion.
+pub fn allocate_word_id(headword: &str, pinyin: &str) -> u32 {
+	let mut hasher = FieldHasher::new();
+	hasher.write(headword.as_bytes());
+	hasher.write(pinyin.split_whitespace().collect::<Vec<&str>>().join(" ").as_bytes());
+	let hash = hasher.finish();
+
+	USER_WORD_ID_START | ((hash ^ (hash >> 32)) as u32 & !USER_WORD_ID_START)
+}
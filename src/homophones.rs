@@ -0,0 +1,38 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Lookup of words with exactly the same pinyin and tones
+
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+use crate::pinyin::{normalize_neutral_tone, normalize_umlaut, UmlautStyle};
+use std::cmp::Reverse;
+
+/// Pinyin in the form homophones are compared in: lowercase, without spaces, with ü as `u:` and the neutral tone as tone 5.
+fn homophone_key(pinyin: &str) -> String {
+	normalize_neutral_tone(&normalize_umlaut(&pinyin.to_lowercase().replace(' ', ""), UmlautStyle::UColon))
+}
+
+impl Dictionary {
+	/// # Query Homophones
+	/// The words pronounced exactly like `pinyin`, with the same syllables and tones, e.g. 是, 事, and 市 for `"shi4"`,
+	/// for finding puns, choosing names, or telling homophones apart. The pinyin may be written with tone numbers or tone marks,
+	/// and must include the tones. Words are ordered from the most to the least frequent.
+	pub fn query_homophones(&self, pinyin: &str) -> Vec<&WordEntry> {
+		let key = homophone_key(pinyin);
+		let mut entries: Vec<&WordEntry> = self.query_by_pinyin(&key).into_iter()
+			.filter(|entry| homophone_key(&entry.pinyin_numbers) == key || homophone_key(&entry.pinyin_marks) == key)
+			.collect();
+		entries.sort_by_key(|entry| (Reverse(self.frequency(&entry.simplified)), entry.word_id));
+		entries.dedup_by_key(|entry| entry.word_id);
+
+		entries
+	}
+
+	/// # Homophones
+	/// The other words pronounced exactly like an entry, leaving out the entries with the same simplified headword, ordered as in `query_homophones`.
+	pub fn homophones(&self, entry: &WordEntry) -> Vec<&WordEntry> {
+		let mut homophones = self.query_homophones(&entry.pinyin_numbers);
+		homophones.retain(|homophone| homophone.simplified != entry.simplified);
+
+		homophones
+	}
+}
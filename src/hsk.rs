@@ -0,0 +1,51 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: The vocabulary lists of each HSK level
+
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+use crate::pinyin::split_tone_number;
+use crate::transliteration::preferred_entry;
+
+static HSK_2015: &str = include_str!("../data/hsk/hsk2015.u8");
+
+impl Dictionary {
+	/// # HSK Vocabulary
+	/// The vocabulary list of an HSK level, with one entry per word of the official 2015 lists, in their order: by pinyin, syllable by syllable, then by tone,
+	/// so 杯子 (bēizi) comes before 北京 (Běijīng). Levels 1 to 6 list 150, 150, 299, 600, 1300, and 2500 words, and any other level lists none.
+	///
+	/// Words of the lists without an entry of their own, which are patterns such as 因为……所以…… and phrases such as 打篮球, are left out.
+	/// A word is listed with the entries tagged with its level where there are any, such as 喂 (to feed) at level 6, and with its other entries otherwise,
+	/// such as 喂 (hello) at level 1. When a word has several entries, the one listed is the first of them that is not a proper noun or a variant,
+	/// then the one with the most glosses.
+	pub fn hsk_vocabulary(&self, level: u8) -> Vec<&WordEntry> {
+		let mut vocabulary: Vec<&WordEntry> = hsk_words(level).filter_map(|(word, pinyin)| {
+			let entries: Vec<&WordEntry> = self.entries_for_headword(word).into_iter()
+				.filter(|entry| entry.simplified == word && pinyin.is_none_or(|pinyin| entry.pinyin_numbers == pinyin))
+				.collect();
+			match entries.iter().any(|entry| entry.hsk == level) {
+				true => preferred_entry(entries.into_iter().filter(|entry| entry.hsk == level)),
+				false => preferred_entry(entries),
+			}
+		}).collect();
+		vocabulary.sort_by_cached_key(|entry| (pinyin_order(&entry.pinyin_numbers), entry.simplified.clone()));
+
+		vocabulary
+	}
+}
+
+/// The words of the official 2015 list of an HSK level, each with the pinyin of its entry where the list gives one.
+pub(crate) fn hsk_words(level: u8) -> impl Iterator<Item = (&'static str, Option<&'static str>)> {
+	HSK_2015.lines().filter(|line| !line.is_empty() && !line.starts_with('#')).filter_map(move |line| {
+		let mut fields = line.splitn(3, ' ');
+		let (word_level, word) = (fields.next()?, fields.next()?);
+		(word_level.parse() == Ok(level)).then(|| (word, fields.next()))
+	})
+}
+
+/// The order of the official HSK lists: each syllable without its tone, then its tone, ignoring case.
+fn pinyin_order(pinyin_numbers: &str) -> Vec<(String, u8)> {
+	pinyin_numbers.split(' ').map(|syllable| {
+		let (letters, tone) = split_tone_number(syllable);
+		(letters.to_lowercase(), tone.unwrap_or(5))
+	}).collect()
+}
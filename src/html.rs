@@ -0,0 +1,116 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Annotation of the text of HTML documents
+
+use crate::chinese_dictionary::Dictionary;
+use crate::punctuation::is_han;
+
+/// Elements whose contents are copied without being annotated: code, form fields, and existing ruby annotations.
+static SKIPPED_ELEMENTS: [&str; 6] = ["script", "style", "textarea", "ruby", "rt", "rp"];
+
+/// How `annotate_html` marks up each word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnnotationStyle {
+	/// Pinyin above the word, e.g. `<ruby>学生<rt>xué sheng</rt></ruby>`
+	#[default]
+	Ruby,
+	/// Pinyin and glosses shown on hover, e.g. `<span title="xué sheng: student; schoolchild">学生</span>`
+	Tooltip,
+}
+
+/// # Annotate Options
+/// Options controlling how `Dictionary::annotate_html` annotates words.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnnotateOptions {
+	pub style: AnnotationStyle,
+	/// The number of glosses in each tooltip with `AnnotationStyle::Tooltip`, or every gloss when `None`
+	pub max_glosses: Option<usize>,
+}
+
+impl Dictionary {
+	/// # Annotate HTML
+	/// Annotate every Chinese word in an HTML document or fragment, such as a chapter of an EPUB, with its pinyin, keeping all other markup as it is.
+	/// Only text between tags is segmented, so tags, attributes, comments, and entities are never touched, and the contents of
+	/// `script`, `style`, `textarea`, and existing `ruby` elements are left alone. Words are annotated with the pinyin of their first entry.
+	pub fn annotate_html(&self, html: &str, options: &AnnotateOptions) -> String {
+		let mut annotated = String::with_capacity(html.len() * 2);
+		let mut position = 0;
+
+		while position < html.len() {
+			let rest = &html[position..];
+			if !rest.starts_with('<') {
+				let end = rest.find('<').map_or(html.len(), |offset| position + offset);
+				self.annotate_text(&html[position..end], options, &mut annotated);
+				position = end;
+				continue;
+			}
+
+			let end = match rest.starts_with("<!--") {
+				true => rest.find("-->").map_or(html.len(), |offset| position + offset + 3),
+				false => position + tag_length(rest),
+			};
+			let tag = &html[position..end];
+			annotated.push_str(tag);
+			position = end;
+
+			let name = tag_name(tag);
+			if !tag.ends_with("/>") && SKIPPED_ELEMENTS.contains(&name.as_str()) {
+				let closing = format!("</{}", name);
+				let end = html[position..].to_ascii_lowercase().find(&closing).map_or(html.len(), |offset| position + offset);
+				annotated.push_str(&html[position..end]);
+				position = end;
+			}
+		}
+
+		annotated
+	}
+
+	/// Adds a text node to the output with each of its words annotated.
+	fn annotate_text(&self, text: &str, options: &AnnotateOptions, annotated: &mut String) {
+		let mut position = 0;
+		for range in self.scan(text) {
+			let word = &text[range.clone()];
+			let entry = match word.chars().any(is_han) {
+				true => self.entries_for_headword(word).into_iter().next(),
+				false => None,
+			};
+			if let Some(entry) = entry {
+				annotated.push_str(&text[position..range.start]);
+				match options.style {
+					AnnotationStyle::Ruby => annotated.push_str(&format!("<ruby>{}<rt>{}</rt></ruby>", word, escape(&entry.pinyin_marks))),
+					AnnotationStyle::Tooltip => {
+						let glosses: Vec<&str> = entry.english.iter().take(options.max_glosses.unwrap_or(usize::MAX)).map(String::as_str).collect();
+						let title = format!("{}: {}", entry.pinyin_marks, glosses.join("; "));
+						annotated.push_str(&format!("<span title=\"{}\">{}</span>", escape(&title), word));
+					},
+				}
+				position = range.end;
+			}
+		}
+		annotated.push_str(&text[position..]);
+	}
+}
+
+/// The length of the tag at the start of `html`, up to and including its `>`, skipping over any `>` inside quoted attribute values.
+fn tag_length(html: &str) -> usize {
+	let mut quote: Option<char> = None;
+	for (index, character) in html.char_indices() {
+		match (quote, character) {
+			(None, '"') | (None, '\'') => quote = Some(character),
+			(Some(open), _) if open == character => quote = None,
+			(None, '>') => return index + 1,
+			_ => {},
+		}
+	}
+	html.len()
+}
+
+/// The lowercase name of an opening tag, or an empty string for closing tags, comments, and declarations.
+fn tag_name(tag: &str) -> String {
+	tag[1..].chars().take_while(char::is_ascii_alphanumeric).collect::<String>().to_ascii_lowercase()
+}
+
+/// Escapes text for use in HTML text and attribute values.
+fn escape(text: &str) -> String {
+	text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
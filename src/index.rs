@@ -0,0 +1,199 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: A compact lookup index from strings to word ids
+
+use fst::map::Stream;
+use fst::{IntoStreamer, Map, MapBuilder, Streamer};
+use serde::{Serialize, Serializer};
+use serde_derive::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::{Bound, RangeBounds};
+
+/// Maps index keys to the ids of the entries they belong to.
+///
+/// Keys are stored in a finite state transducer whose values point into a single flat list of word ids,
+/// which takes up far less memory than a `HashMap<String, Vec<u32>>` with one allocation per key and per list.
+/// The data builder writes the transducer itself, so the built-in indexes are read straight from the embedded bytes.
+pub(crate) struct KeyIndex {
+	keys: Map<Cow<'static, [u8]>>,
+	postings: Vec<u32>,
+}
+
+/// How a `KeyIndex` is written to a data file: the bytes of the transducer, followed by the word ids it points into.
+#[derive(Serialize, Deserialize)]
+struct StoredIndex<'a> {
+	#[serde(borrow)]
+	keys: &'a [u8],
+	postings: Vec<u32>,
+}
+
+impl KeyIndex {
+	pub(crate) fn new(source: HashMap<String, Vec<u32>>) -> KeyIndex {
+		let mut entries: Vec<(String, Vec<u32>)> = source.into_iter().collect();
+		entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+		let mut builder = IndexBuilder::new();
+		for (key, ids) in entries {
+			builder.insert(&key, &ids);
+		}
+
+		builder.build()
+	}
+
+	/// Reads an index written by the data builder, borrowing the transducer from the embedded data instead of copying it.
+	pub(crate) fn from_static(bytes: &'static [u8]) -> bincode::Result<KeyIndex> {
+		let stored: StoredIndex<'static> = bincode::deserialize(bytes)?;
+		KeyIndex::from_stored(Cow::Borrowed(stored.keys), stored.postings)
+	}
+
+	/// Reads an index written by the data builder, such as from a file.
+	pub(crate) fn from_bytes(bytes: &[u8]) -> bincode::Result<KeyIndex> {
+		let stored: StoredIndex = bincode::deserialize(bytes)?;
+		KeyIndex::from_stored(Cow::Owned(stored.keys.to_vec()), stored.postings)
+	}
+
+	fn from_stored(keys: Cow<'static, [u8]>, postings: Vec<u32>) -> bincode::Result<KeyIndex> {
+		let keys = Map::new(keys).map_err(|error| Box::new(bincode::ErrorKind::Custom(error.to_string())))?;
+		Ok(KeyIndex { keys, postings })
+	}
+
+	/// A copy of this index with more ids added under their keys, after any ids already stored under them.
+	/// The keys are merged in order from both sides, so the index is never expanded into a map.
+	pub(crate) fn with_additions(&self, additions: HashMap<String, Vec<u32>>) -> KeyIndex {
+		let mut additions: Vec<(String, Vec<u32>)> = additions.into_iter().collect();
+		additions.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+		let mut additions = additions.into_iter().peekable();
+
+		let mut builder = IndexBuilder::new();
+		let mut stream = self.keys.stream();
+		while let Some((key, value)) = stream.next() {
+			while let Some((added, ids)) = additions.next_if(|(added, _)| added.as_bytes() < key) {
+				builder.insert(&added, &ids);
+			}
+			let mut ids = self.postings_at(value).to_vec();
+			if let Some((_, added)) = additions.next_if(|(added, _)| added.as_bytes() == key) {
+				ids.extend(added);
+			}
+			builder.insert_bytes(key, &ids);
+		}
+		for (added, ids) in additions {
+			builder.insert(&added, &ids);
+		}
+
+		builder.build()
+	}
+
+	/// Returns the word ids stored under `key`, if any.
+	pub(crate) fn get(&self, key: &str) -> Option<&[u32]> {
+		self.keys.get(key).map(|value| self.postings_at(value))
+	}
+
+	pub(crate) fn contains_key(&self, key: &str) -> bool {
+		self.keys.contains_key(key)
+	}
+
+	/// Calls `visit` with every key and its word ids, in lexicographic order.
+	pub(crate) fn for_each(&self, mut visit: impl FnMut(&str, &[u32])) {
+		let mut stream = self.keys.stream();
+		while let Some((key, value)) = stream.next() {
+			if let Ok(key) = std::str::from_utf8(key) {
+				visit(key, self.postings_at(value));
+			}
+		}
+	}
+
+	/// Iterates over every key in lexicographic order.
+	pub(crate) fn keys(&self) -> IndexKeys<'_> {
+		IndexKeys { stream: self.keys.stream() }
+	}
+
+	/// Iterates over the keys within `range`, in lexicographic order.
+	pub(crate) fn range<'r, R: RangeBounds<&'r str>>(&self, range: R) -> IndexKeys<'_> {
+		let mut builder = self.keys.range();
+		builder = match range.start_bound() {
+			Bound::Included(start) => builder.ge(start),
+			Bound::Excluded(start) => builder.gt(start),
+			Bound::Unbounded => builder,
+		};
+		builder = match range.end_bound() {
+			Bound::Included(end) => builder.le(end),
+			Bound::Excluded(end) => builder.lt(end),
+			Bound::Unbounded => builder,
+		};
+		IndexKeys { stream: builder.into_stream() }
+	}
+
+	/// Iterates over the keys starting with `prefix`, in lexicographic order.
+	pub(crate) fn prefix(&self, prefix: &str) -> IndexKeys<'_> {
+		let builder = self.keys.range().ge(prefix);
+		// Every key starting with the prefix is less than the prefix with its last byte below 0xFF incremented, and the rest dropped
+		let mut end = prefix.as_bytes().to_vec();
+		while end.last() == Some(&0xFF) {
+			end.pop();
+		}
+		let stream = match end.last_mut() {
+			Some(last) => {
+				*last += 1;
+				builder.lt(end).into_stream()
+			},
+			None => builder.into_stream(),
+		};
+		IndexKeys { stream }
+	}
+
+	fn postings_at(&self, value: u64) -> &[u32] {
+		let start = (value >> 32) as usize;
+		let length = (value & 0xFFFF_FFFF) as usize;
+		&self.postings[start..start + length]
+	}
+}
+
+impl Serialize for KeyIndex {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		StoredIndex { keys: self.keys.as_fst().as_bytes(), postings: self.postings.clone() }.serialize(serializer)
+	}
+}
+
+/// Collects keys, which must be inserted in lexicographic order, into a `KeyIndex`.
+struct IndexBuilder {
+	keys: MapBuilder<Vec<u8>>,
+	postings: Vec<u32>,
+}
+
+impl IndexBuilder {
+	fn new() -> IndexBuilder {
+		IndexBuilder { keys: MapBuilder::memory(), postings: Vec::new() }
+	}
+
+	fn insert(&mut self, key: &str, ids: &[u32]) {
+		self.insert_bytes(key.as_bytes(), ids);
+	}
+
+	fn insert_bytes(&mut self, key: &[u8], ids: &[u32]) {
+		let value = ((self.postings.len() as u64) << 32) | ids.len() as u64;
+		self.postings.extend_from_slice(ids);
+		self.keys.insert(key, value).unwrap();
+	}
+
+	fn build(self) -> KeyIndex {
+		KeyIndex {
+			keys: Map::new(Cow::Owned(self.keys.into_inner().unwrap())).unwrap(),
+			postings: self.postings,
+		}
+	}
+}
+
+/// # Index Keys
+/// An iterator over the keys of one of the dictionary's indexes, in lexicographic order of their UTF-8 bytes.
+pub struct IndexKeys<'a> {
+	stream: Stream<'a>,
+}
+
+impl Iterator for IndexKeys<'_> {
+	type Item = String;
+
+	fn next(&mut self) -> Option<String> {
+		self.stream.next().map(|(key, _)| String::from_utf8_lossy(key).into_owned())
+	}
+}
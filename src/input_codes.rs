@@ -0,0 +1,79 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Cangjie and four-corner input codes of characters
+
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+use std::collections::HashMap;
+
+/// # Input Codes
+/// Cangjie and four-corner codes of characters, loaded from the Unicode Han Database with `from_unihan` and set with `Dictionary::set_input_codes`.
+/// The crate does not bundle these codes; they are in the `Unihan_DictionaryLikeData.txt` file of the Unihan database from unicode.org.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputCodes {
+	cangjie: HashMap<char, Vec<String>>,
+	four_corner: HashMap<char, Vec<String>>,
+}
+
+impl InputCodes {
+	/// # From Unihan
+	/// Read the `kCangjie` and `kFourCornerCode` fields of a Unihan database file, with tab separated lines such as `U+4F60 kCangjie ONF`.
+	/// Comments and every other field are ignored.
+	pub fn from_unihan(source: &str) -> InputCodes {
+		let mut codes = InputCodes::default();
+		for line in source.lines().filter(|line| !line.starts_with('#')) {
+			let mut fields = line.split('\t');
+			let (Some(code_point), Some(field), Some(values)) = (fields.next(), fields.next(), fields.next()) else {
+				continue;
+			};
+			let Some(character) = code_point.strip_prefix("U+").and_then(|hex| u32::from_str_radix(hex, 16).ok()).and_then(char::from_u32) else {
+				continue;
+			};
+			let table = match field {
+				"kCangjie" => &mut codes.cangjie,
+				"kFourCornerCode" => &mut codes.four_corner,
+				_ => continue,
+			};
+			table.entry(character).or_default().extend(values.split(' ').filter(|value| !value.is_empty()).map(str::to_uppercase));
+		}
+		codes
+	}
+
+	/// # Cangjie
+	/// The Cangjie codes of a character, such as `["ONF"]` for 你.
+	pub fn cangjie(&self, character: char) -> &[String] {
+		self.cangjie.get(&character).map_or(&[], Vec::as_slice)
+	}
+
+	/// # Four Corner
+	/// The four-corner codes of a character, such as `["2729.2"]` for 你, where the digit after the `.` is the supplementary corner.
+	pub fn four_corner(&self, character: char) -> &[String] {
+		self.four_corner.get(&character).map_or(&[], Vec::as_slice)
+	}
+}
+
+impl Dictionary {
+	/// # Query by Cangjie
+	/// Look up the single character entries whose character has the Cangjie code `code`, such as `"ONF"` for 你. The code is not case sensitive.
+	/// Characters are in code point order. No codes are bundled, so nothing is found until they are set with `Dictionary::set_input_codes`.
+	pub fn query_by_cangjie(&self, code: &str) -> Vec<&WordEntry> {
+		let code = code.trim().to_uppercase();
+		self.characters_with_code(&self.input_codes().cangjie, |candidate| *candidate == code)
+	}
+
+	/// # Query by Four Corner
+	/// Look up the single character entries whose character has the four-corner code `code`, such as `"2729"` or `"2729.2"` for 你.
+	/// Without the supplementary digit after the `.`, every character with the same four corners matches. Characters are in code point order.
+	/// No codes are bundled, so nothing is found until they are set with `Dictionary::set_input_codes`.
+	pub fn query_by_four_corner(&self, code: &str) -> Vec<&WordEntry> {
+		let code = code.trim();
+		self.characters_with_code(&self.input_codes().four_corner, |candidate| {
+			candidate == code || (!code.contains('.') && candidate.split('.').next() == Some(code))
+		})
+	}
+
+	fn characters_with_code<F: Fn(&String) -> bool>(&self, table: &HashMap<char, Vec<String>>, matches: F) -> Vec<&WordEntry> {
+		let mut characters: Vec<char> = table.iter().filter(|(_, codes)| codes.iter().any(&matches)).map(|(character, _)| *character).collect();
+		characters.sort_unstable();
+		characters.into_iter().flat_map(|character| self.entries_for_headword(character.encode_utf8(&mut [0; 4]))).collect()
+	}
+}
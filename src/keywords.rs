@@ -0,0 +1,34 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Keyword extraction by TF-IDF against the built-in word frequencies
+
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+use std::collections::HashMap;
+
+impl Dictionary {
+	/// # Keywords
+	/// The `n` words that best characterize `text`, with their scores, highest first.
+	/// Each word is scored by TF-IDF: how often it occurs in `text`, weighted by how rare it is in general according to the dictionary's word frequencies,
+	/// so common words such as 的 and 是 rank low even when they occur often. Each word is returned as the first of its entries.
+	/// Dictionaries without frequencies, such as those created with `from_entries`, rank words by how often they occur.
+	pub fn keywords(&self, text: &str, n: usize) -> Vec<(&WordEntry, f32)> {
+		let mut counts: HashMap<u32, (&WordEntry, usize)> = HashMap::new();
+		let mut tokens = 0;
+		for range in self.scan(text) {
+			if let Some(entry) = self.entries_for_headword(&text[range]).first() {
+				counts.entry(entry.word_id).or_insert((entry, 0)).1 += 1;
+				tokens += 1;
+			}
+		}
+
+		let total = self.frequency_total().max(1.0);
+		let mut scored: Vec<(&WordEntry, f32)> = counts.into_iter().map(|(_, (entry, count))| {
+			let idf = -((f64::from(self.frequency(&entry.simplified)) + 0.5) / total).ln();
+			(entry, (count as f64 / tokens as f64 * idf.max(1.0)) as f32)
+		}).collect();
+		scored.sort_by(|(a, a_score), (b, b_score)| b_score.total_cmp(a_score).then(a.word_id.cmp(&b.word_id)));
+		scored.truncate(n);
+
+		scored
+	}
+}
@@ -0,0 +1,101 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: A bigram language model over words for scoring segmentations
+
+use crate::chinese_dictionary::Dictionary;
+use std::collections::HashMap;
+
+/// How much the probability of a word following another comes from how often the pair was seen, rather than from the frequency of the word alone.
+/// The built-in counts are estimated from character bigrams, which also count the characters of longer words, such as the 命 的 in 生命的,
+/// so they are given little weight.
+const BIGRAM_WEIGHT: f64 = 0.02;
+
+/// Counts of how often one word follows another. Each word is interned to an id, so a pair of borrowed words
+/// can be looked up without allocating, which matters since segmentation looks up a pair at every step.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Bigrams {
+	ids: HashMap<String, u32>,
+	words: Vec<String>,
+	counts: HashMap<(u32, u32), u32>,
+}
+
+impl Bigrams {
+	pub(crate) fn new(pairs: HashMap<(String, String), u32>) -> Bigrams {
+		let mut bigrams = Bigrams::default();
+		for ((previous, word), count) in pairs {
+			bigrams.insert(&previous, &word, count);
+		}
+
+		bigrams
+	}
+
+	/// Sets the count of `word` following `previous`, replacing any count already set.
+	pub(crate) fn insert(&mut self, previous: &str, word: &str, count: u32) {
+		let pair = (self.intern(previous), self.intern(word));
+		self.counts.insert(pair, count);
+	}
+
+	/// How often `word` follows `previous`, or `0` if the pair is unknown.
+	pub(crate) fn get(&self, previous: &str, word: &str) -> u32 {
+		match (self.ids.get(previous), self.ids.get(word)) {
+			(Some(previous), Some(word)) => self.counts.get(&(*previous, *word)).cloned().unwrap_or(0),
+			_ => 0,
+		}
+	}
+
+	/// Iterates over every pair of words with its count, in no particular order.
+	pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &str, u32)> {
+		self.counts.iter().map(move |((previous, word), count)| (self.words[*previous as usize].as_str(), self.words[*word as usize].as_str(), *count))
+	}
+
+	fn intern(&mut self, word: &str) -> u32 {
+		if let Some(id) = self.ids.get(word) {
+			return *id;
+		}
+		let id = self.words.len() as u32;
+		self.ids.insert(word.to_string(), id);
+		self.words.push(word.to_string());
+		id
+	}
+}
+
+impl Dictionary {
+	/// The cost, a negative log probability, of the simplified `word` following `previous`, or starting the text when there is no previous word.
+	/// How often the pair was seen is interpolated with the frequency of `word`, so pairs that were never seen still have a finite cost.
+	pub(crate) fn bigram_cost(&self, previous: Option<&str>, word: &str) -> f64 {
+		let unigram = (f64::from(self.frequency(word)) + 0.5) / self.frequency_total().max(1.0);
+		let Some(previous) = previous else {
+			return -unigram.ln();
+		};
+		let bigram = match (self.bigram(previous, word), self.frequency(previous)) {
+			(0, _) | (_, 0) => 0.0,
+			(count, frequency) => (f64::from(count) / f64::from(frequency)).min(1.0),
+		};
+
+		-(BIGRAM_WEIGHT * bigram + (1.0 - BIGRAM_WEIGHT) * unigram).ln()
+	}
+
+	/// The most frequent simplified form of a word, or the word itself when it is not in the dictionary.
+	fn simplified_form<'a>(&'a self, word: &'a str) -> &'a str {
+		self.entries_for_headword(word).into_iter()
+			.map(|entry| entry.simplified.as_str())
+			.max_by_key(|simplified| self.frequency(simplified))
+			.unwrap_or(word)
+	}
+
+	/// # Segmentation Score
+	/// The log probability of a sequence of words, such as the tokens of a sentence, according to the counts set with `set_bigrams` and the word frequencies.
+	/// Of several candidates for the same text, such as OCR hypotheses or alternative segmentations, the one with the highest score reads most like Chinese.
+	/// Every word lowers the score, so divide by the number of words to compare texts of different lengths. Words in traditional characters are scored by their simplified form.
+	pub fn segmentation_score(&self, tokens: &[&str]) -> f32 {
+		let mut previous: Option<&str> = None;
+		let mut cost = 0.0;
+		for token in tokens {
+			let word = self.simplified_form(token);
+			cost += self.bigram_cost(previous, word);
+			previous = Some(word);
+		}
+
+		-cost as f32
+	}
+}
@@ -11,6 +11,21 @@
 //! - Convert between Traditional and Simplified Chinese characters.
 //! - Segment strings of Chinese characters into tokens using a dictionary-driven segmentation approach.
 //!
+//! ### Optional Features
+//! - `gazetteer-places`: Adds every provincial- and prefecture-level division of China, every country with its capital, and major world cities,
+//!   such as 三沙市, 埃斯瓦蒂尼, and 金奈, along with the full names of the special administrative regions and new areas such as 雄安新区.
+//!   Names CC-CEDICT already has keep their CC-CEDICT entry.
+//! - `gazetteer-brands`: Adds brand and company names missing from CC-CEDICT, such as 京东 and 小红书.
+//! - `terms-medical`, `terms-legal`, and `terms-it`: Add medical, legal, and IT terminology, such as 靶向治疗, 举证责任, and 负载均衡.
+//!   Entries from these packs have their `domain` set.
+//! - `grammar-patterns`: Adds grammar patterns for common words, such as 把 + O + V and 越…越…, available through `grammar_patterns`.
+//! - `neologisms`: Adds recent neologisms and Internet slang missing from CC-CEDICT, such as 内卷 and 躺平, and tags them and established slang
+//!   such as 给力 with the `year` they came into wide use. Queries can be limited to a range of years with `QueryOptions::years`.
+//! - `classical`: Adds a glossary of Classical Chinese (文言) senses and compounds, such as 之, 焉, and 不亦, used by `query_classical` and `SegmentationMode::Classical`.
+//! - `collocations`: Adds a curated list of collocations for common words, such as 解决 + 问题, available through `collocations`.
+//! - `json` and `msgpack`: Add `Format::Json` and `Format::MessagePack` for `serialize_results`, encoded with `serde_json` and `rmp-serde`.
+//! - `server`: Builds the `chinese_dictionary_server` binary, which serves the dictionary over HTTP with JSON responses.
+//!
 //! ### Usage
 //! Querying the dictionary
 //! ```rust
@@ -64,12 +79,28 @@
 //! println!("{:?}", dictionary.segment("今天天气不错")); // --> ["今天", "天气", "不错"]
 //! ```
 //!
+//! Share a dictionary between threads
+//! ```rust
+//! extern crate chinese_dictionary;
+//!
+//! use chinese_dictionary::SharedDictionary;
+//! use std::thread;
+//!
+//! let dictionary = SharedDictionary::new(); // Instantiation may take a while
+//!
+//! // Cloning the handle is cheap, and queries never lock
+//! let handle = dictionary.clone();
+//! let worker = thread::spawn(move || handle.segment("今天天气不错").len());
+//! println!("{}", worker.join().unwrap()); // --> 3
+//! ```
+//!
 //! #### `WordEntry` struct
 //! ```rust
 //! extern crate chinese_dictionary;
 //! 
 //! use chinese_dictionary::WordEntry;
 //! use chinese_dictionary::MeasureWord;
+//! use chinese_dictionary::SourceId;
 //!
 //! let example_measure_word = MeasureWord {
 //!	traditional: "example_traditional".to_string(),
@@ -89,6 +120,9 @@
 //!	measure_words: vec![example_measure_word],
 //!	hsk: 6 as u8,
 //!	word_id: 11111111 as u32,
+//!	domain: None,
+//!	year: None,
+//!	source: SourceId::User,
 //! };
 //! ```
 //!
@@ -99,15 +133,208 @@
 //! - `ZH`: Represents Chinese
 //! - `UN`: Represents an uncertain classification result
 
+// The examples above are indented with tabs, like the rest of the crate
+#![allow(clippy::tabs_in_doc_comments)]
+
 extern crate bincode;
 extern crate character_converter;
 extern crate chinese_detection;
+extern crate fst;
+#[cfg(feature = "msgpack")]
+extern crate rmp_serde;
+#[cfg(feature = "json")]
+extern crate serde_json;
+extern crate sha2;
+extern crate unicode_normalization;
 
+mod alignment;
+mod builder;
 mod chinese_dictionary;
+mod comparison;
+mod charsets;
+mod checksum;
+mod classical;
+mod classification;
+mod collation;
+mod collocations;
+mod confusables;
+mod conversion;
+mod corpus;
+mod format;
+mod entry_diff;
+mod explain;
+mod font_subset;
+mod gazetteer;
+mod gloss;
+mod grammar;
+mod grouping;
+mod hash;
+mod homophones;
+mod hsk;
+mod html;
+mod index;
+mod input_codes;
+mod keywords;
+mod language_model;
+mod limits;
+mod measure_words;
+mod metadata;
+mod neologisms;
+mod normalizer;
+mod ocr;
+mod pinyin;
+mod pronunciations;
+mod punctuation;
+mod query_language;
+mod query_options;
+mod quiz;
+mod radicals;
+mod ranking;
+mod results;
+mod rhymes;
+mod sampling;
+mod sandhi;
+mod segmentation_filter;
+mod sentences;
+mod shared;
+mod simplification;
+mod source;
+mod stardict;
+mod suggest;
+mod subtitles;
+mod stream;
+mod stroke_order;
+mod shuangpin;
+mod terminology;
+mod terminology_check;
+mod tokenizer;
+mod transliteration;
+mod trie;
+mod unknown_words;
+mod validation;
+mod vocabulary;
 pub use self::chinese_dictionary::Dictionary as ChineseDictionary;
 pub use self::chinese_dictionary::ClassificationResult;
 pub use self::chinese_dictionary::WordEntry;
 pub use self::chinese_dictionary::MeasureWord;
+pub use self::chinese_dictionary::Span;
+pub use self::chinese_dictionary::SpanKind;
+pub use self::chinese_dictionary::Sense;
+pub use self::chinese_dictionary::SenseMatch;
+pub use self::chinese_dictionary::EnglishGroup;
+pub use self::chinese_dictionary::Script;
+pub use self::chinese_dictionary::ScriptMatch;
+pub use self::chinese_dictionary::Index;
+pub use self::chinese_dictionary::SegmentationMode;
+pub use self::chinese_dictionary::MergeStrategy;
+pub use self::classification::AmbiguousQuery;
+pub use self::classification::ClassificationOptions;
+pub use self::index::IndexKeys;
+pub use self::input_codes::InputCodes;
+pub use self::normalizer::Normalizer;
+pub use self::query_options::QueryOptions;
+pub use self::sampling::EntryFilter;
+pub use self::segmentation_filter::SegmentationFilter;
+pub use self::ranking::QueryContext;
+pub use self::shuangpin::ShuangpinScheme;
+pub use self::vocabulary::Region;
+pub use self::terminology::Domain;
+pub use self::terminology_check::Violation;
+pub use self::grammar::GrammarPattern;
+pub use self::grammar::grammar_patterns;
+pub use self::source::Source;
+pub use self::source::SourceId;
+pub use self::tokenizer::Token;
+pub use self::tokenizer::TokenKind;
+pub use self::tokenizer::TokenizeError;
+pub use self::tokenizer::MAX_TOKENIZE_BYTES;
+pub use self::limits::InputLimits;
+pub use self::limits::MAX_QUERY_BYTES;
+pub use self::collation::PinyinCollator;
+pub use self::collation::PinyinSortKey;
+pub use self::stream::AnnotatedLine;
+pub use self::stream::AnnotatedLines;
+pub use self::stream::AnnotatedWord;
+pub use self::unknown_words::UnknownSpan;
+pub use self::validation::ValidationIssue;
+pub use self::validation::WordEntryBuilder;
+pub use self::validation::validate_entry;
+pub use self::hash::USER_WORD_ID_START;
+pub use self::hash::allocate_word_id;
+pub use self::shared::SharedDictionary;
+pub use self::shared::ReloadableDictionary;
+pub use self::builder::build_from_cedict;
+pub use self::builder::build_from_data;
+pub use self::builder::build_frequencies;
+pub use self::builder::build_bigrams;
+pub use self::builder::read_metadata;
+pub use self::stardict::export_stardict;
+pub use self::format::FormatSpec;
+pub use self::gloss::GlossCleanup;
+pub use self::gloss::clean_gloss;
+pub use self::format::FormatError;
+pub use self::html::AnnotationStyle;
+pub use self::html::AnnotateOptions;
+pub use self::metadata::DataMetadata;
+pub use self::metadata::DataDiff;
+pub use self::results::Format;
+pub use self::results::DecodeError;
+pub use self::results::serialize_results;
+pub use self::results::deserialize_results;
+pub use self::sandhi::SandhiSyllable;
+pub use self::sandhi::apply_tone_sandhi;
+pub use self::sentences::split_sentences;
+pub use self::simplification::Suggestion;
+pub use self::stroke_order::StrokeData;
+pub use self::stroke_order::StrokeDataError;
+pub use self::stroke_order::StrokePath;
+pub use self::subtitles::Cue;
+pub use self::subtitles::SubtitleError;
+pub use self::subtitles::SubtitleWord;
+pub use self::subtitles::parse_srt;
+pub use self::subtitles::write_srt;
+pub use self::punctuation::Style;
+pub use self::punctuation::normalize_punctuation;
+pub use self::punctuation::has_mixed_punctuation;
+pub use self::pinyin::is_valid_pinyin_syllable;
+pub use self::pinyin::suggest_syllables;
+pub use self::pinyin::format_pinyin_words;
+pub use self::pinyin::add_tone_mark;
+pub use self::pinyin::tone_spans;
+pub use self::pinyin::normalize_umlaut;
+pub use self::pinyin::UmlautStyle;
+pub use self::pinyin::render_neutral_tone;
+pub use self::pinyin::NeutralTone;
+pub use self::transliteration::Target;
+pub use self::transliteration::pinyin_to_ipa;
+pub use self::confusables::similar_characters;
+pub use self::ocr::Correction;
+pub use self::radicals::Radical;
+pub use self::radicals::RadicalGroup;
+pub use self::radicals::radicals;
+pub use self::radicals::radical_of;
+pub use self::explain::QueryTrace;
+pub use self::explain::TraceEvent;
+pub use self::font_subset::characters_for_entries;
+pub use self::font_subset::unique_characters;
+pub use self::charsets::Charset;
+pub use self::checksum::data_checksum;
+pub use self::checksum::data_checksums;
+pub use self::charsets::CharsetReport;
+pub use self::charsets::charset_report;
+pub use self::collocations::Collocations;
+pub use self::comparison::ComparisonTable;
+pub use self::comparison::ComparisonColumn;
+pub use self::conversion::ConvertedEntry;
+pub use self::grouping::HeadwordResult;
+pub use self::grouping::group_by_headword;
+pub use self::grouping::group_by_pinyin_initial;
+pub use self::conversion::convert_entries;
+pub use self::entry_diff::EntryDiff;
+pub use self::entry_diff::EntryField;
+pub use self::entry_diff::diff_entries;
+pub use self::corpus::CorpusStats;
+pub use self::corpus::WordCount;
 
 #[cfg(test)]
 mod tests {
@@ -143,6 +370,225 @@ mod tests {
 		assert_eq!(expected, actual);
 	}
 
+	#[test]
+	fn search_by_english_senses() {
+		let dictionary = ChineseDictionary::new();
+		let results = dictionary.query_by_english_senses("to hit");
+		let da = results.iter().find(|result| result.entry.simplified == "打" && result.entry.pinyin_numbers == "da3").unwrap();
+		assert_eq!(vec![2], da.senses);
+		assert_eq!("to hit", da.entry.senses().nth(2).unwrap().gloss);
+	}
+
+	#[test]
+	fn search_with_operators() {
+		let dictionary = ChineseDictionary::new();
+		let results = dictionary.query("py:xing2 zh:行").unwrap();
+		assert!(!results.is_empty());
+		assert!(results.iter().all(|entry| entry.simplified == "行" && entry.pinyin_numbers == "xing2"));
+		assert!(dictionary.query("en:to walk py:xing2").unwrap().iter().all(|entry| entry.pinyin_numbers == "xing2"));
+		assert!(dictionary.query("hsk:<=3 en:eat").unwrap().iter().all(|entry| (1..=3).contains(&entry.hsk)));
+	}
+
+	#[test]
+	fn search_chinese_fuzzy() {
+		let dictionary = ChineseDictionary::new();
+		let results = dictionary.query_chinese_fuzzy("图书官", 1);
+		assert!(results.iter().any(|entry| entry.simplified == "图书馆"));
+		assert!(results.iter().all(|entry| entry.simplified.chars().count() >= 2));
+		assert_eq!("图书馆", dictionary.query_chinese_fuzzy("图书馆", 1).first().unwrap().simplified);
+		assert!(dictionary.query_chinese_fuzzy("图书官", 0).is_empty());
+	}
+
+	#[test]
+	fn confusable_characters() {
+		let dictionary = ChineseDictionary::new();
+		assert!(similar_characters('未').contains(&'末'));
+		assert_eq!(vec!['已', '巳'], similar_characters('己'));
+		assert!(similar_characters('的').is_empty());
+		assert_eq!("自己", dictionary.query_chinese_fuzzy("自已", 1).first().unwrap().simplified);
+	}
+
+	#[test]
+	fn corpus_statistics() {
+		let dictionary = ChineseDictionary::new();
+		let stats = dictionary.corpus_stats(vec!["我喜欢猫。", "我喜欢狗，你呢？"].into_iter());
+		assert_eq!(8, stats.tokens);
+		assert_eq!(6, stats.types);
+		let first = stats.words.first().unwrap();
+		assert_eq!(("我", 2), (first.word.as_str(), first.count));
+		assert!(first.word_ids.iter().all(|id| dictionary.query_by_chinese("我").iter().any(|entry| entry.word_id == *id)));
+		assert_eq!(1.0, stats.hsk_coverage(6));
+	}
+
+	#[test]
+	fn align_gloss() {
+		let dictionary = ChineseDictionary::new();
+		let chinese = "我喜欢猫";
+		let english = "I like cats";
+		let alignments: Vec<(&str, &str)> = dictionary.align_gloss(chinese, english).into_iter()
+			.map(|(chinese_range, english_range, _)| (&chinese[chinese_range], &english[english_range]))
+			.collect();
+		assert_eq!(vec![("我", "I"), ("喜欢", "like"), ("猫", "cats")], alignments);
+	}
+
+	#[test]
+	fn convert_entry_collections() {
+		let dictionary = ChineseDictionary::new();
+		let entries = dictionary.query_by_chinese("书本");
+		let converted = convert_entries(&entries, Script::Traditional);
+		assert_eq!("書本", converted[0].headword);
+		assert!(converted[0].measure_words.contains(&"本"));
+		assert_eq!("书本", convert_entries(&entries, Script::Simplified)[0].headword);
+	}
+
+	#[test]
+	fn suggest_queries() {
+		let dictionary = ChineseDictionary::new();
+		assert!(dictionary.suggest("ni3hao4", 5).contains(&"ni3hao3".to_string()));
+		assert_eq!("自己", dictionary.suggest("自已", 5).first().unwrap());
+		assert!(dictionary.suggest("watermelons", 5).contains(&"watermelon".to_string()));
+		assert!(dictionary.suggest("icecream", 5).contains(&"ice cream".to_string()));
+		assert_eq!(1, dictionary.suggest("自已", 1).len());
+	}
+
+	#[test]
+	fn index_keys() {
+		let dictionary = ChineseDictionary::new();
+		assert!(dictionary.simplified_keys().any(|key| key == "你好"));
+		assert!(dictionary.traditional_keys().any(|key| key == "體育"));
+		assert!(dictionary.pinyin_keys().any(|key| key == "ni3hao3"));
+		assert!(dictionary.english_keys().any(|key| key == "hello"));
+		let keys: Vec<String> = dictionary.simplified_keys().take(100).collect();
+		assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+	}
+
+	#[test]
+	fn index_key_prefix_and_range() {
+		let dictionary = ChineseDictionary::new();
+		let keys: Vec<String> = dictionary.keys_with_prefix(Index::Pinyin, "zhong1").collect();
+		assert!(keys.contains(&"zhong1guo2".to_string()));
+		assert!(keys.iter().all(|key| key.starts_with("zhong1")));
+		let keys: Vec<String> = dictionary.keys_in_range(Index::English, "apple".."apricot").collect();
+		assert!(keys.contains(&"apple".to_string()));
+		assert!(keys.iter().all(|key| key.as_str() >= "apple" && key.as_str() < "apricot"));
+		assert!(dictionary.keys_in_range(Index::English, "apricot"..).all(|key| key.as_str() >= "apricot"));
+	}
+
+	#[test]
+	fn init_only() {
+		let dictionary = ChineseDictionary::init_only(&[Index::Simplified, Index::English]);
+		assert_eq!(vec!["今天", "天气", "不错"], dictionary.segment("今天天气不错"));
+		assert_eq!("你好", dictionary.query_by_pinyin("ni3hao3").first().unwrap().simplified);
+	}
+
+	#[test]
+	fn query_owned() {
+		let dictionary = ChineseDictionary::new();
+		let results = dictionary.query_owned("你好");
+		let first = std::sync::Arc::clone(results.first().unwrap());
+		assert!(std::ptr::eq(first.as_ref(), dictionary.query("你好").unwrap()[0]));
+		let worker = std::thread::spawn(move || first.simplified.clone());
+		assert_eq!("你好", worker.join().unwrap());
+	}
+
+	#[test]
+	fn serialize_query_results() {
+		let dictionary = ChineseDictionary::new();
+		let results = dictionary.query("to run").unwrap();
+		let bytes = serialize_results(&results, Format::Bincode);
+		assert_eq!(results, dictionary.resolve_results(&bytes, Format::Bincode).unwrap());
+		assert!(deserialize_results(&[1, 0], Format::Bincode).is_err());
+	}
+
+	#[cfg(feature = "json")]
+	#[test]
+	fn serialize_query_results_json() {
+		let dictionary = ChineseDictionary::new();
+		let results = dictionary.query("to run").unwrap();
+		let bytes = serialize_results(&results, Format::Json);
+		assert_eq!(results, dictionary.resolve_results(&bytes, Format::Json).unwrap());
+		assert_eq!(vec![4561, 880], deserialize_results(b"[4561,880]", Format::Json).unwrap());
+		assert!(deserialize_results(b"[4561,", Format::Json).is_err());
+	}
+
+	#[cfg(feature = "msgpack")]
+	#[test]
+	fn serialize_query_results_msgpack() {
+		let dictionary = ChineseDictionary::new();
+		let results = dictionary.query("to run").unwrap();
+		let bytes = serialize_results(&results, Format::MessagePack);
+		assert_eq!(results, dictionary.resolve_results(&bytes, Format::MessagePack).unwrap());
+		assert_eq!(vec![0x90 | 2, 0x05, 0xcd, 0x11, 0xd1], serialize_results(&[dictionary.entry_by_id(5).unwrap(), dictionary.entry_by_id(4561).unwrap()], Format::MessagePack));
+		assert!(deserialize_results(&[0x92, 0x05], Format::MessagePack).is_err());
+	}
+
+	#[test]
+	fn search_english_exact_phrase() {
+		let dictionary = ChineseDictionary::new();
+		let results = dictionary.query("\"traditional chinese character\"").unwrap();
+		assert!(!results.is_empty());
+		assert!(results.iter().all(|entry| entry.english.iter().any(|gloss| gloss.to_lowercase().starts_with("traditional chinese character"))));
+		assert!(dictionary.query_by_english("\"traditional chinese\"").iter().all(|entry| entry.english.iter().any(|gloss| gloss.to_lowercase().starts_with("traditional chinese"))));
+		assert!(dictionary.query_by_english("\"character traditional\"").is_empty());
+	}
+
+	#[test]
+	fn search_english_grouped() {
+		let dictionary = ChineseDictionary::new();
+		let groups = dictionary.query_by_english_grouped("bank account");
+		let phrases: Vec<&str> = groups.iter().map(|group| group.phrase.as_str()).collect();
+		assert_eq!(vec!["bank account", "bank", "account"], phrases);
+		assert_eq!(1..2, groups[2].words);
+		assert!(groups[0].entries.iter().any(|entry| entry.simplified == "账户"));
+	}
+
+	#[test]
+	fn custom_ranking() {
+		let mut dictionary = ChineseDictionary::new();
+		let default: Vec<u32> = dictionary.query("to run").unwrap().iter().map(|entry| entry.word_id).collect();
+		dictionary.set_ranker(|entry, _| if entry.simplified == "跑" { 1.0 } else { 0.0 });
+		let ranked: Vec<u32> = dictionary.query("to run").unwrap().iter().map(|entry| entry.word_id).collect();
+		assert_eq!("跑", dictionary.entry_by_id(ranked[0]).unwrap().simplified);
+		assert_eq!(default.iter().filter(|id| **id != ranked[0]).collect::<Vec<_>>(), ranked[1..].iter().collect::<Vec<_>>());
+		dictionary.set_ranker(|entry, _| if entry.simplified == "跑" { f32::NAN } else { 0.0 });
+		let ranked: Vec<u32> = dictionary.query("to run").unwrap().iter().map(|entry| entry.word_id).collect();
+		assert_eq!("跑", dictionary.entry_by_id(*ranked.last().unwrap()).unwrap().simplified);
+		assert_eq!(default.iter().filter(|id| **id != *ranked.last().unwrap()).collect::<Vec<_>>(), ranked[..ranked.len() - 1].iter().collect::<Vec<_>>());
+		dictionary.set_ranker(|_, context| context.rank as f32);
+		assert_eq!(default.iter().rev().copied().collect::<Vec<u32>>(), dictionary.query("to run").unwrap().iter().map(|entry| entry.word_id).collect::<Vec<u32>>());
+		dictionary.clear_ranker();
+		assert_eq!(default, dictionary.query("to run").unwrap().iter().map(|entry| entry.word_id).collect::<Vec<u32>>());
+	}
+
+	#[test]
+	fn search_english_contains() {
+		let dictionary = ChineseDictionary::new();
+		let results = dictionary.query_english_contains("Watermelon");
+		assert_eq!("西瓜", results.first().unwrap().simplified);
+		assert!(results.iter().all(|entry| entry.english.iter().any(|gloss| gloss.to_lowercase().contains("watermelon"))));
+	}
+
+	#[test]
+	fn search_normalized() {
+		let dictionary = ChineseDictionary::new();
+		let result = dictionary.query("  ｗａｔｅｒｍｅｌｏｎ ");
+		assert_eq!("西瓜", result.unwrap().first().unwrap().traditional);
+		assert_eq!("Beijing", dictionary.normalize(" Beijing "));
+		// Queries normalizing leaves unchanged are borrowed rather than copied
+		assert!(matches!(Normalizer::default().normalize_text(" watermelon "), std::borrow::Cow::Borrowed("watermelon")));
+	}
+
+	#[test]
+	fn search_without_normalizer() {
+		let mut dictionary = ChineseDictionary::new();
+		dictionary.set_normalizer(Normalizer::none());
+		assert_eq!("Watermelon", dictionary.normalize("Watermelon"));
+		dictionary.set_normalizer(Normalizer { lowercase: true, ..Normalizer::default() });
+		assert_eq!("西瓜", dictionary.query("Watermelon").unwrap().first().unwrap().simplified);
+		dictionary.set_normalizer(Normalizer { strip_punctuation: true, fold_script: Some(Script::Simplified), ..Normalizer::default() });
+		assert_eq!("简体字", dictionary.normalize("「簡體字」"));
+	}
+
 	#[test]
 	fn search_by_traditional() {
 		let dictionary = ChineseDictionary::new();
@@ -173,6 +619,88 @@ mod tests {
 		assert_eq!(expected, actual);
 	}
 	
+	#[test]
+	fn umlaut_pinyin() {
+		let dictionary = ChineseDictionary::new();
+		for query in ["lv4", "lü4", "lǜ", "lu:4"] {
+			assert!(dictionary.query_by_pinyin(query).iter().any(|entry| entry.simplified == "绿"));
+		}
+		assert!(dictionary.query("nv3").unwrap().iter().any(|entry| entry.simplified == "女"));
+		assert_eq!("lü4 Nü3 nǚ", normalize_umlaut("lv4 Nu:3 nǔ:", UmlautStyle::Umlaut));
+		assert_eq!("lv4 nv3 nǚ", normalize_umlaut("lü4 nu:3 nǚ", UmlautStyle::V));
+		assert_eq!("lu:4 nǔ:", normalize_umlaut("lv4 nǚ", UmlautStyle::UColon));
+	}
+
+	#[test]
+	fn neutral_tone() {
+		let dictionary = ChineseDictionary::new();
+		for query in ["ma5", "ma0"] {
+			assert!(dictionary.query_by_pinyin(query).iter().any(|entry| entry.simplified == "吗"));
+		}
+		for query in ["xie4xie5", "xie4xie0", "xie4xie"] {
+			assert_eq!("谢谢", dictionary.query_by_pinyin(query)[0].simplified);
+		}
+		assert_eq!("xie4 xie0", render_neutral_tone("xie4 xie5", NeutralTone::Zero));
+		assert_eq!("xie4 xie", render_neutral_tone("xie4 xie0", NeutralTone::Unnumbered));
+		assert_eq!("xie4 xie5 · ma5", render_neutral_tone("xie4 xie · ma0", NeutralTone::Five));
+	}
+
+	#[test]
+	fn complete_chinese() {
+		let dictionary = ChineseDictionary::new();
+		assert_eq!(10, dictionary.complete_chinese("天", 10).len());
+		let completions = dictionary.complete_chinese("天", usize::MAX);
+		assert!(completions.iter().all(|entry| entry.simplified.starts_with('天') || entry.traditional.starts_with('天')));
+		assert!(completions.iter().any(|entry| entry.simplified == "天气"));
+		assert!(completions.iter().any(|entry| entry.simplified == "天空"));
+		assert!(!completions.iter().any(|entry| entry.simplified == "天"));
+		assert!(dictionary.complete_chinese("電腦", 10).iter().all(|entry| entry.traditional.starts_with("電腦")));
+		assert!(dictionary.complete_chinese("", 10).is_empty());
+	}
+
+	#[test]
+	fn diff_entries() {
+		let dictionary = ChineseDictionary::new();
+		let upstream = dictionary.query_by_chinese("书").into_iter().find(|entry| entry.pinyin_numbers == "shu1").unwrap().clone();
+		assert!(super::diff_entries(&upstream, &upstream).is_empty());
+
+		let mut edited = upstream.clone();
+		edited.english.retain(|gloss| gloss != "letter");
+		edited.english.push("volume".to_string());
+		edited.hsk = 5;
+		let diff = super::diff_entries(&upstream, &edited);
+		assert_eq!(vec![EntryField::English, EntryField::Hsk], diff.changed);
+		assert_eq!(vec!["volume".to_string()], diff.glosses_added);
+		assert_eq!(vec!["letter".to_string()], diff.glosses_removed);
+
+		let mut update = upstream.clone();
+		update.english.push("volume".to_string());
+		update.english.retain(|gloss| gloss != "letter");
+		update.hsk = 6;
+		update.measure_words.clear();
+		let other = super::diff_entries(&upstream, &update);
+		assert_eq!(3, other.changed.len());
+		assert_eq!(vec![EntryField::Hsk], diff.conflicts(&other));
+	}
+
+	#[test]
+	fn hsk_vocabulary() {
+		let dictionary = ChineseDictionary::new();
+		let level_1 = dictionary.hsk_vocabulary(1);
+		let words: Vec<&str> = level_1.iter().map(|entry| entry.simplified.as_str()).collect();
+		assert_eq!(["爱", "八", "爸爸", "杯子", "北京"], words[..5]);
+		assert!(words.contains(&"喂") && words.contains(&"这"));
+		assert_eq!(words.len(), words.iter().collect::<std::collections::HashSet<_>>().len());
+		assert!(dictionary.hsk_vocabulary(6).iter().any(|entry| entry.simplified == "喂"));
+		let counts: Vec<usize> = (1..=7).map(|level| hsk::hsk_words(level).count()).collect();
+		assert_eq!(vec![150, 150, 299, 600, 1300, 2500, 0], counts);
+		for level in 1..=6 {
+			let words_with_entries = hsk::hsk_words(level).filter(|(word, _)| dictionary.entries_for_headword(word).iter().any(|entry| entry.simplified == *word));
+			assert_eq!(words_with_entries.count(), dictionary.hsk_vocabulary(level).len());
+		}
+		assert!(!dictionary.hsk_vocabulary(2).iter().any(|entry| entry.simplified == "打篮球"));
+	}
+
 	#[test]
 	fn search_by_pinyin_1() {
 		let dictionary = ChineseDictionary::new();
@@ -203,6 +731,17 @@ mod tests {
 		assert_eq!(expected, actual);
 	}
 
+	#[test]
+	fn query_into_reuses_buffer() {
+		let dictionary = ChineseDictionary::new();
+		let mut results = Vec::new();
+		assert!(dictionary.query_into("watermelon", &mut results));
+		assert_eq!("西瓜", results.first().unwrap().traditional);
+		results.clear();
+		assert!(dictionary.query_into("電腦", &mut results));
+		assert_eq!("電腦", results.first().unwrap().traditional);
+	}
+
 	#[test]
 	fn segment_traditional() {
 		let dictionary = ChineseDictionary::new();
@@ -230,6 +769,920 @@ mod tests {
 		assert_eq!(expected, actual);
 	}
 
+	#[test]
+	fn highlight_known_words() {
+		let dictionary = ChineseDictionary::new();
+		let sentence = "今天的天气, 挺爽";
+		let mut known = std::collections::HashSet::new();
+		for entry in dictionary.query_by_chinese("天气") {
+			known.insert(entry.word_id);
+		}
+		let actual: Vec<(&str, SpanKind)> = dictionary.highlight_known_words(sentence, &known).into_iter().map(|span| (&sentence[span.range], span.kind)).collect();
+		let expected = vec![("今天", SpanKind::New), ("的", SpanKind::New), ("天气", SpanKind::Known), (", ", SpanKind::Unmatched), ("挺", SpanKind::New), ("爽", SpanKind::New)];
+		assert_eq!(expected, actual);
+	}
+
+	#[test]
+	fn build_from_cedict_sample() {
+		let directory = std::env::temp_dir().join("chinese_dictionary_build_from_cedict_sample");
+		let source = directory.join("cedict_ts.u8");
+		std::fs::create_dir_all(&directory).unwrap();
+		std::fs::write(&source, "# CC-CEDICT\n書 书 [shu1] /book/letter/to write/CL:本[ben3],冊|册[ce4]/\n綠 绿 [lu:4] /green/\n").unwrap();
+		build_from_cedict(&source, &directory).unwrap();
+
+		let data: std::collections::HashMap<u32, WordEntry> = bincode::deserialize_from(std::fs::File::open(directory.join("data.dictionary")).unwrap()).unwrap();
+		let english = crate::index::KeyIndex::from_bytes(&std::fs::read(directory.join("english.dictionary")).unwrap()).unwrap();
+		let book = data.get(&0).unwrap();
+		assert_eq!("shū", book.pinyin_marks);
+		assert_eq!(vec!["book".to_string(), "letter".to_string(), "to write".to_string()], book.english);
+		assert_eq!("册", book.measure_words[1].simplified);
+		assert_eq!("lù:", data.get(&1).unwrap().pinyin_marks);
+		assert_eq!(Some(&[0][..]), english.get("write"));
+		assert_eq!(2, read_metadata(directory.join("metadata.dictionary")).unwrap().entries.len());
+	}
+
+	#[test]
+	fn reproducible_build() {
+		assert_eq!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad", super::checksum::sha256(b"abc"));
+		let source = "書 书 [shu1] /book/letter/\n綠 绿 [lu:4] /green/\n水 水 [shui3] /water/\n";
+		let builds: Vec<Vec<Vec<u8>>> = ["first", "second"].iter().map(|name| {
+			let directory = std::env::temp_dir().join(format!("chinese_dictionary_reproducible_{}", name));
+			std::fs::create_dir_all(&directory).unwrap();
+			std::fs::write(directory.join("cedict_ts.u8"), source).unwrap();
+			build_from_cedict(directory.join("cedict_ts.u8"), &directory).unwrap();
+			["traditional", "simplified", "pinyin", "english", "headwords", "data", "metadata"].iter()
+				.map(|file| std::fs::read(directory.join(format!("{}.dictionary", file))).unwrap())
+				.collect()
+		}).collect();
+		assert_eq!(builds[0], builds[1]);
+
+		let checksums = data_checksums();
+		assert_eq!(8, checksums.len());
+		assert!(checksums.iter().all(|(_, checksum)| checksum.len() == 64));
+		assert_eq!(64, data_checksum().len());
+	}
+
+	#[test]
+	fn rebuild_embedded_data() {
+		let directory = std::env::temp_dir().join("chinese_dictionary_rebuild_embedded_data");
+		build_from_data(concat!(env!("CARGO_MANIFEST_DIR"), "/data/data.dictionary"), &directory).unwrap();
+		let rebuilt = ["traditional.dictionary", "simplified.dictionary", "pinyin.dictionary", "english.dictionary", "headwords.dictionary", "data.dictionary"];
+		for (name, checksum) in data_checksums().into_iter().filter(|(name, _)| rebuilt.contains(name)) {
+			assert_eq!(checksum, super::checksum::sha256(&std::fs::read(directory.join(name)).unwrap()), "{} differs from the rebuilt file", name);
+		}
+		std::fs::remove_dir_all(&directory).unwrap();
+	}
+
+	#[test]
+	fn format_entry() {
+		let dictionary = ChineseDictionary::new();
+		let entry = dictionary.query_by_chinese("书").into_iter().find(|entry| entry.pinyin_numbers == "shu1").unwrap();
+		let spec = FormatSpec::new("{traditional} [{pinyin_marks}] {english;join=, } {{CL: {measure_words;join= }}}").unwrap();
+		assert_eq!("書 [shū] book, letter, document, to write {CL: 本[ben3] 冊|册[ce4] 部[bu4]}", entry.format(&spec));
+		assert_eq!("書 书 [shū] book; letter; document; to write", entry.to_string());
+	}
+
+	#[test]
+	fn format_spec_errors() {
+		assert_eq!(Err(FormatError::UnknownField("pinyin".to_string())), FormatSpec::new("{pinyin}"));
+		assert_eq!(Err(FormatError::UnknownOption("sep=,".to_string())), FormatSpec::new("{english;sep=,}"));
+		assert_eq!(Err(FormatError::UnclosedBrace), FormatSpec::new("{english"));
+	}
+
+	#[test]
+	fn shared_dictionary_across_threads() {
+		let dictionary = SharedDictionary::new();
+		let workers: Vec<_> = vec!["西瓜", "電腦"].into_iter().map(|query| {
+			let handle = dictionary.clone();
+			std::thread::spawn(move || handle.query(query).unwrap().first().unwrap().traditional.clone())
+		}).collect();
+		let actual: Vec<String> = workers.into_iter().map(|worker| worker.join().unwrap()).collect();
+		assert_eq!(vec!["西瓜".to_string(), "電腦".to_string()], actual);
+	}
+
+	#[test]
+	fn input_limits() {
+		let mut dictionary = ChineseDictionary::new();
+		let huge = "你好".repeat(MAX_QUERY_BYTES);
+		assert_eq!(None, dictionary.query(&huge));
+		assert_eq!(TraceEvent::InputTooLarge { length: huge.len(), limit: MAX_QUERY_BYTES }, dictionary.explain_query(&huge).events[0]);
+
+		let text = "我们今天去北京。他说：“你好！”然后我们吃了烤鸭。".repeat(3);
+		let whole = dictionary.tokenize_checked(text.as_bytes()).unwrap();
+		let chunked = dictionary.tokenize_chunked(&text, 40);
+		assert_eq!(whole, chunked);
+		assert_eq!(text, dictionary.tokenize_chunked(&text, 1).iter().map(|token| token.text).collect::<String>());
+		dictionary.set_input_limits(InputLimits { max_query_bytes: 4, max_tokenize_bytes: 16 });
+		assert_eq!(None, dictionary.query("你好"));
+		assert!(dictionary.tokenize_checked(text.as_bytes()).is_err());
+		assert_eq!(text, dictionary.tokenize_chunked(&text, 1000).iter().map(|token| token.text).collect::<String>());
+	}
+
+	#[test]
+	fn annotate_stream() {
+		let mut dictionary = ChineseDictionary::new();
+		let input = "我喜欢学习\r\n\nhello 中文".as_bytes();
+		let lines: Vec<AnnotatedLine> = dictionary.annotate_stream(input).collect();
+		assert_eq!(3, lines.len());
+		assert_eq!("我喜欢学习", lines[0].text);
+		assert_eq!(vec![0..3, 3..9, 9..15], lines[0].words.iter().map(|word| word.range.clone()).collect::<Vec<_>>());
+		assert!(lines[1].words.is_empty());
+		assert_eq!(3, lines[2].number);
+		assert_eq!("中文", &lines[2].text[lines[2].words[0].range.clone()]);
+
+		dictionary.set_input_limits(InputLimits { max_query_bytes: MAX_QUERY_BYTES, max_tokenize_bytes: 8 });
+		let lines: Vec<AnnotatedLine> = dictionary.annotate_stream("我喜欢学习\n你好".as_bytes()).collect();
+		assert_eq!(vec!["我喜", "欢学", "习", "你好"], lines.iter().map(|line| line.text.as_str()).collect::<Vec<_>>());
+		assert_eq!(vec![1, 1, 1, 2], lines.iter().map(|line| line.number).collect::<Vec<_>>());
+		assert_eq!(vec![true, true, false, false], lines.iter().map(|line| line.truncated).collect::<Vec<_>>());
+	}
+
+	#[test]
+	fn tokenize_checked() {
+		let dictionary = ChineseDictionary::new();
+		let tokens = dictionary.tokenize_checked("我喜欢, abc".as_bytes()).unwrap();
+		let kinds: Vec<(&str, TokenKind)> = tokens.iter().map(|token| (token.text, token.kind)).collect();
+		assert_eq!(vec![("我", TokenKind::Word), ("喜欢", TokenKind::Word), (", ", TokenKind::Unknown), ("abc", TokenKind::Foreign)], kinds);
+		assert_eq!(9..11, tokens[2].range);
+
+		assert_eq!(Err(TokenizeError::InvalidUtf8 { position: 3 }), dictionary.tokenize_checked(b"\xE6\x88\x91\xFF"));
+		let large = vec![b'a'; MAX_TOKENIZE_BYTES + 1];
+		assert_eq!(Err(TokenizeError::InputTooLarge { length: MAX_TOKENIZE_BYTES + 1, limit: MAX_TOKENIZE_BYTES }), dictionary.tokenize_checked(&large));
+	}
+
+	#[test]
+	fn tokenize_code_switching() {
+		let dictionary = ChineseDictionary::new();
+		let tokens = dictionary.tokenize_checked("我喜欢apple, 也用e-mail和3D打印".as_bytes()).unwrap();
+		let foreign: Vec<&str> = tokens.iter().filter(|token| token.kind == TokenKind::Foreign).map(|token| token.text).collect();
+		assert_eq!(vec!["apple", "e-mail", "3D"], foreign);
+		let apple = tokens.iter().find(|token| token.text == "apple").unwrap();
+		assert!(dictionary.foreign_candidates(apple).iter().any(|entry| entry.simplified == "苹果"));
+		assert!(dictionary.foreign_candidates(&tokens[0]).is_empty());
+	}
+
+	#[test]
+	fn tokenize_emoji_and_symbols() {
+		let dictionary = ChineseDictionary::new();
+		let text = "好的👍🏽👨\u{200D}👩\u{200D}👧🇨🇳 (＾▽＾) 1+1≠3 (╯°□°)╯";
+		let tokens = dictionary.tokenize_checked(text.as_bytes()).unwrap();
+		let typed: Vec<(&str, TokenKind)> = tokens.iter().filter(|token| token.kind != TokenKind::Word && token.kind != TokenKind::Unknown).map(|token| (token.text, token.kind)).collect();
+		assert_eq!(vec![
+			("👍🏽", TokenKind::Emoji),
+			("👨\u{200D}👩\u{200D}👧", TokenKind::Emoji),
+			("🇨🇳", TokenKind::Emoji),
+			("(＾▽＾)", TokenKind::Kaomoji),
+			("+", TokenKind::Symbol),
+			("≠", TokenKind::Symbol),
+			("(╯°□°)╯", TokenKind::Kaomoji),
+		], typed);
+		assert_eq!(text, tokens.iter().map(|token| token.text).collect::<String>());
+	}
+
+	#[test]
+	fn punctuation_styles() {
+		assert_eq!("你好，世界。他说：“走吧！”", normalize_punctuation("你好, 世界. 他说: \"走吧!\"", Style::Chinese));
+		assert_eq!("圆周率是3.14，对吗？Hello, world.", normalize_punctuation("圆周率是3.14, 对吗? Hello, world.", Style::Chinese));
+		assert_eq!("你好,世界.他说:\"走吧!\"...", normalize_punctuation("你好，世界。他说：“走吧！”……", Style::Western));
+		assert!(has_mixed_punctuation("你好，世界."));
+		assert!(!has_mixed_punctuation("你好，世界。他说“OK, fine.”"));
+	}
+
+	#[test]
+	fn pinyin_orthography() {
+		assert_eq!("wǒ xǐhuan Xī'ān", format_pinyin_words(&["wo3", "xi3 huan5", "Xi1 an1"]));
+		assert_eq!("Máo Zédōng nǚ'ér", format_pinyin_words(&["Mao2 Ze2 dong1", "nu:3 er2"]));
+		assert_eq!("tiān'ānmén", format_pinyin_words(&["tiān ān mén"]));
+	}
+
+	#[test]
+	fn segment_unigram() {
+		let mut dictionary = ChineseDictionary::new();
+		assert_eq!(vec!["结婚", "的", "和尚", "未", "结婚", "的"], dictionary.segment("结婚的和尚未结婚的"));
+		dictionary.set_segmentation_mode(SegmentationMode::Unigram);
+		assert_eq!(vec!["结婚", "的", "和", "尚未", "结婚", "的"], dictionary.segment("结婚的和尚未结婚的"));
+		assert_eq!(vec!["研究", "生命", "的", "起源"], dictionary.segment("研究生命的起源"));
+	}
+
+	#[test]
+	fn segment_bigram() {
+		let mut dictionary = ChineseDictionary::new();
+		dictionary.set_segmentation_mode(SegmentationMode::Bigram);
+		assert_eq!(vec!["结婚", "的", "和", "尚未", "结婚", "的"], dictionary.segment("结婚的和尚未结婚的"));
+		assert_eq!(vec!["研究", "生命", "的", "起源"], dictionary.segment("研究生命的起源"));
+		assert!(dictionary.segment("").is_empty());
+
+		assert!(dictionary.segmentation_score(&["我", "的", "朋友"]) > dictionary.segmentation_score(&["我", "白", "朋友"]));
+		assert!(dictionary.segmentation_score(&["今天", "天气", "很", "好"]) > dictionary.segmentation_score(&["今夭", "天气", "很", "好"]));
+		assert_eq!(dictionary.segmentation_score(&["今天", "天气"]), dictionary.segmentation_score(&["今天", "天氣"]));
+		let before = dictionary.segmentation_score(&["天气", "很"]);
+		dictionary.set_bigrams(vec![((String::from("天气"), String::from("很")), 1000)].into_iter().collect());
+		assert!(dictionary.segmentation_score(&["天气", "很"]) > before);
+	}
+
+	#[test]
+	fn correct_ocr() {
+		let dictionary = ChineseDictionary::new();
+		let text = "今夭天气很好，我们的末来";
+		let corrections = dictionary.correct_ocr(text);
+		assert_eq!(vec![('夭', '天'), ('末', '未')], corrections.iter().map(|correction| (correction.original, correction.replacement)).collect::<Vec<(char, char)>>());
+		assert_eq!("夭", &text[corrections[0].range.clone()]);
+		assert!(corrections.iter().all(|correction| correction.gain > 0.0));
+		assert!(dictionary.correct_ocr("北京是中国的首都，我已经在这个城市住了十年。").is_empty());
+	}
+
+	#[test]
+	fn transliterate() {
+		let dictionary = ChineseDictionary::new();
+		assert_eq!("wǒ shì xuésheng.", dictionary.transliterate("我是学生。", Target::PinyinMarks));
+		assert_eq!("wo3 shi4 xue2sheng5.", dictionary.transliterate("我是学生。", Target::PinyinNumbers));
+		assert_eq!("ㄨㄛˇ ㄕˋ ㄒㄩㄝˊ˙ㄕㄥ.", dictionary.transliterate("我是学生。", Target::Zhuyin));
+		assert_eq!("wo³ shih⁴ hsüeh²-shêng.", dictionary.transliterate("我是学生。", Target::WadeGiles));
+		assert_eq!("wo˨˩˦ ʂɻ̩˥˩ ɕɥɛ˧˥.ʂəŋ.", dictionary.transliterate("我是学生。", Target::Ipa));
+		assert_eq!("Pei³-ching¹ huan¹-ying² ni³!", dictionary.transliterate("北京欢迎你！", Target::WadeGiles));
+		assert_eq!("ránhòu zǒu le.", dictionary.transliterate("然后走了。", Target::PinyinMarks));
+	}
+
+	#[test]
+	fn ipa() {
+		assert_eq!(Some(String::from("ɕɥɛ˧˥")), pinyin_to_ipa("xue", 2));
+		assert_eq!(Some(String::from("ʈʂʰɻ̩˥")), pinyin_to_ipa("chi", 1));
+		assert_eq!(Some(String::from("ny˨˩˦")), pinyin_to_ipa("nv", 3));
+		assert_eq!(Some(String::from("ʂəŋ")), pinyin_to_ipa("sheng", 5));
+		assert_eq!(None, pinyin_to_ipa("xong", 1));
+		assert_eq!(None, pinyin_to_ipa("xue", 6));
+		let dictionary = ChineseDictionary::new();
+		let student = dictionary.query_by_chinese("学生").into_iter().next().unwrap();
+		assert_eq!("ɕɥɛ˧˥.ʂəŋ", student.ipa());
+	}
+
+	#[test]
+	fn subtitles() {
+		let dictionary = ChineseDictionary::new();
+		let srt = "1\r\n00:00:01,000 --> 00:00:02,500\r\n我是学生\r\n\r\n2\r\n00:01:03,000 --> 00:01:04,000\r\n学生！\r\n";
+		let cues = parse_srt(srt).unwrap();
+		assert_eq!(2, cues.len());
+		assert_eq!(std::time::Duration::from_millis(63_000), cues[1].start);
+		let annotated = dictionary.annotate_subtitles(&cues);
+		assert_eq!("学生！\nxuésheng", annotated[1].text);
+		assert!(write_srt(&annotated).starts_with("1\n00:00:01,000 --> 00:00:02,500\n我是学生\n"));
+		let vocabulary = dictionary.subtitle_vocabulary(&cues);
+		let student = vocabulary.iter().find(|word| word.word == "学生").unwrap();
+		assert_eq!(vec![cues[0].start, cues[1].start], student.occurrences);
+		assert_eq!(Err(SubtitleError { line: 2 }), parse_srt("1\nnot a timing line\n"));
+	}
+
+	#[test]
+	fn annotate_html() {
+		let dictionary = ChineseDictionary::new();
+		let html = "<p class=\"a>b\">学生<b>西瓜</b></p><script>var word = '西瓜';</script><!-- 西瓜 -->";
+		let ruby = dictionary.annotate_html(html, &AnnotateOptions::default());
+		let expected = "<p class=\"a>b\"><ruby>学生<rt>xué sheng</rt></ruby><b><ruby>西瓜<rt>xī guā</rt></ruby></b></p><script>var word = '西瓜';</script><!-- 西瓜 -->";
+		assert_eq!(expected, ruby);
+		let options = AnnotateOptions { style: AnnotationStyle::Tooltip, max_glosses: Some(1) };
+		assert_eq!("hi <span title=\"xī guā: watermelon\">西瓜</span>", dictionary.annotate_html("hi 西瓜", &options));
+	}
+
+	#[test]
+	fn stardict_export() {
+		let dictionary = ChineseDictionary::new();
+		let out_dir = std::env::temp_dir().join("chinese_dictionary_stardict_test");
+		let entries = dictionary.entries().filter(|entry| entry.simplified == "学生" || entry.simplified == "西瓜");
+		export_stardict(entries, &out_dir, "test").unwrap();
+		let ifo = std::fs::read_to_string(out_dir.join("test.ifo")).unwrap();
+		assert!(ifo.starts_with("StarDict's dict ifo file\nversion=2.4.2\nbookname=test\nwordcount=2\nsynwordcount=1\n"));
+		let idx = std::fs::read(out_dir.join("test.idx")).unwrap();
+		assert!(idx.starts_with("学生\0".as_bytes()));
+		let size = u32::from_be_bytes([idx[11], idx[12], idx[13], idx[14]]) as usize;
+		let dict = std::fs::read_to_string(out_dir.join("test.dict")).unwrap();
+		assert!(dict[..size].starts_with("学生 (學生) [xué sheng]\n1. student"));
+		assert_eq!(b"\xe5\xad\xb8\xe7\x94\x9f\0\0\0\0\0", &std::fs::read(out_dir.join("test.syn")).unwrap()[..]);
+	}
+
+	#[test]
+	fn unknown_words() {
+		let entries = ChineseDictionary::new().query_by_chinese("西瓜").into_iter().cloned().collect();
+		let dictionary = ChineseDictionary::from_entries(entries);
+		let text = "我爱吃西瓜，也爱苹果。";
+		let unknown = dictionary.unknown_words(text);
+		assert_eq!(vec!["我爱吃", "也爱苹果"], unknown.iter().map(|span| span.text).collect::<Vec<&str>>());
+		assert_eq!(&text[unknown[1].range.clone()], "也爱苹果");
+		assert_eq!("我爱吃西瓜，也爱苹果。", unknown[1].context);
+	}
+
+	#[test]
+	fn is_word() {
+		let dictionary = ChineseDictionary::new();
+		assert!(dictionary.is_word("西瓜"));
+		assert!(dictionary.is_word("學生"));
+		assert!(!dictionary.is_word("西瓜学"));
+		assert!(dictionary.is_word_in_script("学生", Script::Simplified));
+		assert!(!dictionary.is_word_in_script("學生", Script::Simplified));
+		assert!(dictionary.is_word_in_script("學生", Script::Traditional));
+	}
+
+	#[test]
+	fn compare_entries() {
+		let dictionary = ChineseDictionary::new();
+		let er = dictionary.query_by_chinese("二")[0];
+		let liang = dictionary.query_by_chinese("两")[0];
+		let table = dictionary.compare_entries(&[er, liang]);
+		assert_eq!(vec!["two"], table.shared_glosses);
+		assert_eq!(vec!["èr", "liǎng"], table.columns.iter().map(|column| column.pinyin).collect::<Vec<&str>>());
+		assert!(table.columns[1].distinct_glosses.contains(&"both"));
+		assert!(!table.columns[0].distinct_glosses.contains(&"two"));
+		assert!(table.columns[0].frequency > 0);
+	}
+
+	#[test]
+	fn segmentation_filter() {
+		let mut dictionary = ChineseDictionary::new();
+		assert_eq!(vec!["研究生", "命", "的", "起源"], dictionary.segment("研究生命的起源"));
+		let excluded_ids = dictionary.query_by_chinese("研究生").iter().map(|entry| entry.word_id).collect();
+		dictionary.set_segmentation_filter(SegmentationFilter { excluded_ids, ..SegmentationFilter::default() });
+		assert_eq!(vec!["研究", "生命", "的", "起源"], dictionary.segment("研究生命的起源"));
+		assert_eq!("研究生", dictionary.query_by_chinese("研究生")[0].simplified);
+	}
+
+	#[test]
+	fn tone_mark_placement() {
+		assert_eq!("hǎo", add_tone_mark("hao", 3));
+		assert_eq!("guì", add_tone_mark("gui", 4));
+		assert_eq!("liú", add_tone_mark("liu", 2));
+		assert_eq!("dōu", add_tone_mark("dou", 1));
+		assert_eq!("Lüè", add_tone_mark("Lve", 4));
+		assert_eq!("nǚ", add_tone_mark("nu:", 3));
+		assert_eq!("ma", add_tone_mark("ma", 5));
+	}
+
+	#[test]
+	fn tone_colors() {
+		assert_eq!(vec![(0..3, 3), (4..8, 3)], tone_spans("nǐ hǎo"));
+		assert_eq!(vec![(0..4, 2), (5..10, 5)], tone_spans("xué sheng"));
+		assert_eq!(vec![(0..2, 1), (6..11, 3)], tone_spans("Ā · Lǐng"));
+	}
+
+	#[test]
+	fn random_sampling() {
+		let dictionary = ChineseDictionary::new();
+		let filter = EntryFilter { min_frequency: Some(1000), characters: Some(2..=2), ..EntryFilter::default() };
+		let sample = dictionary.sample_entries(5, 42, Some(&filter));
+		assert_eq!(5, sample.len());
+		assert_eq!(sample, dictionary.sample_entries(5, 42, Some(&filter)));
+		assert!(sample.iter().all(|entry| entry.simplified.chars().count() == 2));
+		assert_eq!(Some(sample[0]), dictionary.random_entry(42, Some(&filter)));
+		assert_ne!(sample, dictionary.sample_entries(5, 43, Some(&filter)));
+	}
+
+	#[test]
+	fn quiz_distractors() {
+		let dictionary = ChineseDictionary::new();
+		let watermelon = dictionary.query_by_chinese("西瓜")[0];
+		let distractors = dictionary.distractors(watermelon, 3);
+		assert_eq!(3, distractors.len());
+		assert!(distractors.iter().all(|entry| entry.simplified != "西瓜" && !entry.english.contains(&"watermelon".to_string())));
+		assert!(distractors.iter().all(|entry| entry.simplified.chars().count() == 2));
+		assert_eq!(distractors, dictionary.distractors(watermelon, 3));
+	}
+
+	#[test]
+	fn radical_index() {
+		assert_eq!(214, radicals().count());
+		assert_eq!(Some(Radical { number: 9, character: '人', strokes: 2 }), radical_of('你'));
+		assert_eq!(149, radical_of('语').unwrap().number);
+		assert_eq!(214, radical_of('龠').unwrap().number);
+		assert_eq!(None, radical_of('a'));
+		let dictionary = ChineseDictionary::new();
+		let index: Vec<RadicalGroup> = dictionary.radical_index().collect();
+		assert_eq!('一', index[0].radical.character);
+		assert!(index[0].characters.starts_with(&['一', '丁']));
+		assert!(index.iter().find(|group| group.radical.character == '水').unwrap().characters.contains(&'河'));
+	}
+
+	#[test]
+	fn input_codes() {
+		let mut dictionary = ChineseDictionary::new();
+		assert!(dictionary.query_by_cangjie("ONF").is_empty());
+		let unihan = "# Unihan_DictionaryLikeData.txt\nU+4F60\tkCangjie\tONF\nU+4F60\tkFourCornerCode\t2729.2\nU+597D\tkCangjie\tVND\nU+597D\tkPhonetic\t409\n";
+		dictionary.set_input_codes(InputCodes::from_unihan(unihan));
+		assert_eq!(&["ONF".to_string()], dictionary.input_codes().cangjie('你'));
+		assert!(dictionary.query_by_cangjie("vnd").iter().all(|entry| entry.simplified == "好"));
+		assert_eq!("你", dictionary.query_by_four_corner("2729")[0].simplified);
+		assert!(dictionary.query_by_four_corner("2729.1").is_empty());
+	}
+
+	#[test]
+	fn charset_report() {
+		let report = super::charset_report("Hi 丟镕你\u{20BB7}");
+		assert_eq!(vec![(3, '丟'), (6, '镕'), (12, '\u{20BB7}')], report.gb2312);
+		assert_eq!(vec![(12, '\u{20BB7}')], report.gbk);
+		assert_eq!(vec![(6, '镕'), (12, '\u{20BB7}')], report.big5);
+		assert!(!report.is_encodable(Charset::Big5));
+		assert!(Charset::Gb2312.contains('你') && Charset::Big5.contains('你'));
+		assert!(super::charset_report("你好").is_encodable(Charset::Gb2312));
+	}
+
+	#[test]
+	fn english_boolean_search() {
+		let mut dictionary = ChineseDictionary::new();
+		let words = |query: &str| dictionary.query_by_english(query).iter().map(|entry| entry.simplified.clone()).collect::<Vec<String>>();
+		let tropical = words("fruit AND tropical");
+		assert!(tropical.contains(&"人心果".to_string()));
+		assert!(dictionary.query_by_english("fruit AND tropical").iter().all(|entry| entry.english.iter().any(|gloss| gloss.contains("tropical"))));
+		let melons = words("watermelon OR melon");
+		assert_eq!(vec!["西瓜", "瓜"], melons.iter().filter(|word| ["西瓜", "瓜"].contains(&word.as_str())).collect::<Vec<_>>());
+		assert!(melons.len() > 2);
+		assert!(!words("melon NOT watermelon").contains(&"西瓜".to_string()));
+		assert!(words("NOT melon").is_empty());
+		assert_eq!(words("fruit AND (c)"), words("fruit AND fruit"));
+
+		// The operators are found before the query is lowercased
+		let expected = dictionary.query_by_english("fruit AND tropical").iter().map(|entry| entry.word_id).collect::<Vec<u32>>();
+		dictionary.set_normalizer(Normalizer { lowercase: true, ..Normalizer::default() });
+		assert_eq!("fruit AND tropical", dictionary.normalize("Fruit AND Tropical"));
+		assert_eq!(expected, dictionary.query("Fruit AND Tropical").unwrap().iter().map(|entry| entry.word_id).collect::<Vec<u32>>());
+	}
+
+	#[test]
+	fn explain_query() {
+		let dictionary = ChineseDictionary::new();
+		let trace = dictionary.explain_query("hsk:<=2 en:watermelon");
+		assert_eq!(TraceEvent::Operators(2), trace.events[0]);
+		assert_eq!(vec![(Index::English, "watermelon", 1)], trace.probes().collect::<Vec<_>>());
+		assert_eq!(Some(&TraceEvent::HskFilter { removed: 1 - trace.results }), trace.events.last());
+
+		let trace = dictionary.explain_query("电脑游戏");
+		assert!(trace.probes().all(|(index, _, _)| index == Index::Simplified));
+		assert_eq!(trace.results, dictionary.query("电脑游戏").unwrap().len());
+		assert!(dictionary.explain_query("\"to eat\"").events.contains(&TraceEvent::ExactPhrase("to eat".to_string())));
+	}
+
+	#[test]
+	fn max_word_length() {
+		let mut dictionary = ChineseDictionary::new();
+		assert_eq!(20, dictionary.max_word_length());
+		assert_eq!(vec!["中华人民共和国", "成立"], dictionary.segment("中华人民共和国成立"));
+		dictionary.set_max_word_length(4);
+		assert!(dictionary.segment("中华人民共和国成立").iter().all(|word| word.chars().count() <= 4));
+		dictionary.set_segmentation_mode(SegmentationMode::Unigram);
+		assert!(dictionary.segment("中华人民共和国成立").iter().all(|word| word.chars().count() <= 4));
+		dictionary.set_max_word_length(0);
+		assert_eq!(1, dictionary.max_word_length());
+	}
+
+	#[test]
+	fn query_by_chinese_both() {
+		let dictionary = ChineseDictionary::new();
+		let results = dictionary.query_by_chinese_both("我们學習中文");
+		let words: Vec<(&str, ScriptMatch)> = results.iter().map(|(entry, matched)| (entry.simplified.as_str(), *matched)).collect();
+		assert!(words.contains(&("我们", ScriptMatch::Simplified)));
+		assert!(words.contains(&("学习", ScriptMatch::Traditional)));
+		assert!(words.contains(&("中文", ScriptMatch::Both)));
+		assert_eq!(1, words.iter().filter(|(word, _)| *word == "中文").count());
+	}
+
+	#[test]
+	fn allocate_word_id() {
+		let id = super::allocate_word_id("電腦", "dian4 nao3");
+		assert!(id >= USER_WORD_ID_START);
+		assert_eq!(id, super::allocate_word_id("電腦", " dian4  nao3"));
+		assert_ne!(id, super::allocate_word_id("電腦", "dian4 nao4"));
+		let dictionary = ChineseDictionary::new();
+		assert!(dictionary.entry_by_id(id).is_none());
+	}
+
+	#[test]
+	fn validate_entry() {
+		let entry = WordEntryBuilder::new("電腦", "电脑", "dian4  nao3").gloss("computer").measure_word("臺", "台", "tai2").build().unwrap();
+		assert_eq!("diàn nǎo", entry.pinyin_marks);
+		assert_eq!(vec![4, 3], entry.tone_marks);
+		assert_eq!("tái", entry.measure_words[0].pinyin_marks);
+		assert!(super::validate_entry(&entry).is_empty());
+		assert_eq!(super::allocate_word_id("電腦", "dian4 nao3"), entry.word_id);
+
+		let issues = WordEntryBuilder::new("電腦", "电", "dian4 naox3").gloss(" ").build().unwrap_err();
+		assert_eq!(vec![
+			ValidationIssue::ScriptLengthMismatch,
+			ValidationIssue::SyllableCountMismatch { characters: 1, syllables: 2 },
+			ValidationIssue::InvalidSyllable("naox3".to_string()),
+			ValidationIssue::EmptyGloss(0),
+		], issues);
+
+		let dictionary = ChineseDictionary::new();
+		let mut entry = dictionary.query_by_chinese("侵略")[0].clone();
+		assert!(super::validate_entry(&entry).is_empty());
+		entry.tone_marks.pop();
+		assert_eq!(vec![ValidationIssue::ToneMarksMismatch], super::validate_entry(&entry));
+	}
+
+	#[test]
+	fn font_subset() {
+		assert_eq!(vec!['!', '你', '好'], super::unique_characters("你好 你好!\n").into_iter().collect::<Vec<char>>());
+		let dictionary = ChineseDictionary::new();
+		let characters = super::characters_for_entries(dictionary.query_by_chinese("书"));
+		assert!(['书', '書', 'ū', '本', 'b'].iter().all(|character| characters.contains(character)));
+		assert!(!characters.contains(&' '));
+	}
+
+	#[test]
+	fn collocations() {
+		let mut dictionary = ChineseDictionary::new();
+		let corpus = ["我们一定要解决问题。", "先解决问题，再休息。", "他们讨论问题，然后解决困难。"];
+		dictionary.set_collocation_data(Collocations::from_corpus(&dictionary, corpus.iter().copied(), 2));
+		assert_eq!(&["问题".to_string()], dictionary.collocation_data().partners("解决"));
+		let solve = dictionary.query_by_chinese("解决")[0];
+		let partners: Vec<&str> = dictionary.collocations(solve, 5).iter().map(|entry| entry.simplified.as_str()).collect();
+		assert_eq!(vec!["问题"], partners);
+	}
+
+	#[cfg(feature = "collocations")]
+	#[test]
+	fn bundled_collocations() {
+		let dictionary = ChineseDictionary::new();
+		let solve = dictionary.query_by_chinese("解决")[0];
+		assert_eq!("问题", dictionary.collocations(solve, 3)[0].simplified);
+	}
+
+	#[test]
+	fn keywords() {
+		let dictionary = ChineseDictionary::new();
+		let keywords = dictionary.keywords("熊猫是中国的国宝。熊猫主要吃竹子，每天要吃很多竹子。野生熊猫生活在四川的山区。", 2);
+		let words: Vec<&str> = keywords.iter().map(|(entry, _)| entry.simplified.as_str()).collect();
+		assert_eq!(vec!["熊猫", "竹子"], words);
+		assert!(keywords[0].1 > keywords[1].1);
+		assert!(dictionary.keywords("", 5).is_empty());
+	}
+
+	#[test]
+	fn simplify_vocabulary() {
+		let dictionary = ChineseDictionary::new();
+		let suggestions = dictionary.simplify_vocabulary("他非常愤怒，这个问题必须迅速解决。", 3);
+		let words: Vec<&str> = suggestions.iter().map(|suggestion| suggestion.word).collect();
+		assert_eq!(vec!["愤怒", "迅速"], words);
+		assert_eq!(6, suggestions[0].hsk);
+		assert_eq!(9..15, suggestions[0].range);
+		assert_eq!("生气", suggestions[0].replacements[0].simplified);
+		assert_eq!("快", suggestions[1].replacements[0].simplified);
+	}
+
+	#[test]
+	fn stroke_order() {
+		let graphics = "{\"character\":\"\\u4e00\",\"strokes\":[\"M 518 382 Q 572 385 623 389 Z\"],\"medians\":[[[121,393],[903,383]]]}\n\
+			{\"character\":\"二\",\"strokes\":[\"M 1 2 Z\",\"M 3 4 Z\"],\"medians\":[[[1,2]],[[3,4]]]}\n";
+		let strokes = StrokeData::from_graphics(graphics).unwrap();
+		assert_eq!(vec![StrokePath { path: "M 518 382 Q 572 385 623 389 Z".to_string(), median: vec![(121, 393), (903, 383)] }], strokes.stroke_order('一'));
+		assert_eq!(Some(2), strokes.stroke_count('二'));
+		assert!(strokes.stroke_order('三').is_empty());
+		assert_eq!(Err(StrokeDataError { line: 2 }), StrokeData::from_graphics("\n{\"character\":\"一\"}"));
+	}
+
+	#[test]
+	fn query_grouped() {
+		let dictionary = ChineseDictionary::new();
+		let entries = dictionary.query_by_chinese("行");
+		assert!(entries.len() > 1);
+		let groups = dictionary.query_grouped("行");
+		assert_eq!(1, groups.len());
+		assert_eq!("行", groups[0].simplified);
+		assert_eq!(entries, groups[0].entries);
+	}
+
+	#[test]
+	fn replace_word() {
+		let dictionary = ChineseDictionary::new();
+		assert_eq!("明天的天气很好", dictionary.replace_word("明天的天气很好", "天", "日"));
+		assert_eq!("我的电脑坏了", dictionary.replace_word("我的计算机坏了", "计算机", "电脑"));
+		assert_eq!("日，天气", dictionary.replace_word("天，天气", "天", "日"));
+	}
+
+	#[test]
+	fn check_terminology() {
+		let dictionary = ChineseDictionary::new();
+		let glossary: std::collections::HashMap<String, String> = [("计算机", "电脑"), ("天", "日")].iter()
+			.map(|(term, preferred)| (term.to_string(), preferred.to_string()))
+			.collect();
+		let text = "我的计算机坏了，天气很好";
+		let violations = dictionary.check_terminology(text, &glossary);
+		assert_eq!(1, violations.len());
+		assert_eq!("计算机", &text[violations[0].range.clone()]);
+		assert_eq!("电脑", violations[0].preferred);
+	}
+
+	#[test]
+	fn break_opportunities() {
+		let dictionary = ChineseDictionary::new();
+		let text = "我喜欢“中国菜”，hello world。";
+		let breaks: Vec<&str> = dictionary.break_opportunities(text).into_iter().map(|index| &text[index..]).collect();
+		assert_eq!(vec!["喜欢“中国菜”，hello world。", "“中国菜”，hello world。", "hello world。", "world。"], breaks);
+	}
+
+	#[test]
+	fn sentences() {
+		assert_eq!(vec!["你好。", "你叫什么名字？！", "我叫王明……"], split_sentences("你好。你叫什么名字？！我叫王明……"));
+		assert_eq!(vec!["他说：“我不去。你去吧！”然后走了。", "(See p. 3.)"], split_sentences("他说：“我不去。你去吧！”然后走了。\n(See p. 3.)"));
+		assert_eq!(vec!["It costs 3.5 yuan.", "OK"], split_sentences("It costs 3.5 yuan. OK"));
+	}
+
+	#[test]
+	fn variant_preference() {
+		let mut dictionary = ChineseDictionary::new();
+		assert_eq!("臺灣", Region::Taiwan.apply_variants("台灣"));
+		assert_eq!("台北", Region::HongKong.apply_variants("臺北"));
+		let entry = dictionary.query_by_chinese("台湾")[0].clone();
+		assert_eq!(entry.traditional, dictionary.display_headword(&entry, Script::Traditional));
+		dictionary.set_variant_preference(Some(Region::Taiwan));
+		assert_eq!(Some(Region::Taiwan), dictionary.variant_preference());
+		assert_eq!("臺灣", dictionary.display_headword(&entry, Script::Traditional));
+		assert_eq!("台湾", dictionary.display_headword(&entry, Script::Simplified));
+		dictionary.set_variant_preference(Some(Region::HongKong));
+		assert_eq!("台灣", dictionary.display_headword(&entry, Script::Traditional));
+	}
+
+	#[test]
+	fn pinyin_collator() {
+		let dictionary = ChineseDictionary::new();
+		let collator = PinyinCollator::new(&dictionary);
+		let mut names = vec!["张山", "王五", "长城", "Alice", "张三", "阿姨", "马", "妈", "李四"];
+		collator.sort(&mut names);
+		assert_eq!(vec!["阿姨", "Alice", "长城", "李四", "妈", "马", "王五", "张三", "张山"], names);
+		assert_eq!(std::cmp::Ordering::Less, collator.compare("银行", "银幕"));
+		assert!(collator.sort_key("張三") < collator.sort_key("張山"));
+	}
+
+	#[test]
+	fn group_by_pinyin_initial() {
+		let dictionary = ChineseDictionary::new();
+		let entries: Vec<&WordEntry> = ["爱", "北京", "八", "绿", "吃"].iter().map(|word| dictionary.query_by_chinese(word)[0]).collect();
+		let sections = super::group_by_pinyin_initial(&entries);
+		assert_eq!(vec!['A', 'B', 'C', 'L'], sections.keys().copied().collect::<Vec<char>>());
+		assert_eq!(vec!["北京", "八"], sections[&'B'].iter().map(|entry| entry.simplified.as_str()).collect::<Vec<&str>>());
+	}
+
+	#[test]
+	fn measure_words_for() {
+		let dictionary = ChineseDictionary::new();
+		let simplified: Vec<&str> = dictionary.measure_words_for("书").iter().map(|measure_word| measure_word.simplified.as_str()).collect();
+		assert_eq!(vec!["本", "册", "部"], simplified);
+		assert_eq!(dictionary.measure_words_for("书"), dictionary.measure_words_for("書"));
+		assert!(dictionary.measure_words_for("不存在的词").is_empty());
+	}
+
+	#[test]
+	fn char_mapping() {
+		let dictionary = ChineseDictionary::new();
+		let to_traditional: std::collections::HashMap<char, Vec<char>> = dictionary.char_mapping(Script::Traditional).collect();
+		assert_eq!(vec!['發', '髮'], to_traditional[&'发']);
+		assert!(to_traditional[&'干'].contains(&'干') && to_traditional[&'干'].contains(&'幹'));
+		assert!(!to_traditional.contains_key(&'我'));
+		let to_simplified: std::collections::HashMap<char, Vec<char>> = dictionary.char_mapping(Script::Simplified).collect();
+		assert_eq!(vec!['发'], to_simplified[&'髮']);
+	}
+
+	#[test]
+	fn convert_with_vocabulary() {
+		let mut dictionary = ChineseDictionary::new();
+		assert_eq!("用軟體看影片", dictionary.convert_with_vocabulary("用软件看视频", Region::Taiwan));
+		assert_eq!("的士", dictionary.convert_with_vocabulary("出租车", Region::HongKong));
+		dictionary.add_vocabulary(Region::Taiwan, "下载", "下載");
+		assert_eq!("下載", dictionary.convert_with_vocabulary("下载", Region::Taiwan));
+	}
+
+	#[test]
+	fn search_by_shuangpin() {
+		assert_eq!(Some("nihao".to_string()), ShuangpinScheme::Xiaohe.expand("nihc"));
+		assert_eq!(Some("zhongguo".to_string()), ShuangpinScheme::Microsoft.expand("vsgo"));
+		assert_eq!(Some("xingqi".to_string()), ShuangpinScheme::Ziranma.expand("xyqi"));
+		assert_eq!(Some("ai".to_string()), ShuangpinScheme::Xiaohe.expand("ai"));
+		assert_eq!(None, ShuangpinScheme::Xiaohe.expand("nih"));
+
+		let mut dictionary = ChineseDictionary::new();
+		dictionary.set_query_options(QueryOptions { pinyin_scheme: Some(ShuangpinScheme::Xiaohe), ..QueryOptions::default() });
+		assert!(dictionary.query("nihc").unwrap().iter().any(|entry| entry.simplified == "你好"));
+		assert!(dictionary.query_by_pinyin("ni3hao3").iter().any(|entry| entry.simplified == "你好"));
+	}
+
+	#[test]
+	fn search_by_initials() {
+		let dictionary = ChineseDictionary::new();
+		assert!(dictionary.query_by_initials("bj").iter().any(|entry| entry.simplified == "北京"));
+		assert!(dictionary.query_by_initials("N H").iter().any(|entry| entry.simplified == "你好"));
+		assert!(dictionary.query_by_initials("").is_empty());
+	}
+
+	#[test]
+	fn pinyin_syllables() {
+		assert!(is_valid_pinyin_syllable("xiang"));
+		assert!(is_valid_pinyin_syllable("Lu:4"));
+		assert!(is_valid_pinyin_syllable("nǚ"));
+		assert!(!is_valid_pinyin_syllable("shnag"));
+		assert_eq!(vec!["shang"], suggest_syllables("shnag"));
+		assert_eq!(vec!["xiang"], suggest_syllables("xiang3"));
+		assert!(suggest_syllables("qwertyuiop").is_empty());
+	}
+
+	#[test]
+	fn tone_sandhi() {
+		let dictionary = ChineseDictionary::new();
+		let word = |chinese: &str, pinyin: &str| dictionary.query_by_chinese(chinese).into_iter().find(|entry| entry.pinyin_numbers == pinyin).unwrap();
+		let tokens = vec![word("我", "wo3"), word("很", "hen3"), word("好", "hao3"), word("不", "bu4"), word("是", "shi4"), word("一", "yi1"), word("天", "tian1")];
+		let surface: Vec<String> = apply_tone_sandhi(&tokens).iter().map(|syllable| syllable.surface_pinyin_marks()).collect();
+		assert_eq!(vec!["wó", "hén", "hǎo", "bú", "shì", "yì", "tiān"], surface);
+
+		let tokens = vec![word("看", "kan4"), word("一", "yi1"), word("看", "kan4"), word("第一", "di4 yi1")];
+		let surface: Vec<u8> = apply_tone_sandhi(&tokens).iter().map(|syllable| syllable.surface_tone).collect();
+		assert_eq!(vec![4, 5, 4, 4, 1], surface);
+	}
+
+	#[test]
+	fn entry_by_hash() {
+		let dictionary = ChineseDictionary::new();
+		let entry = dictionary.query_by_chinese("你好")[0];
+		assert_eq!(0x3cf4_9978_4348_4215, entry.hash);
+		assert_eq!(Some(entry), dictionary.entry_by_hash(entry.hash));
+		assert_eq!(None, dictionary.entry_by_hash(0));
+	}
+
+	#[test]
+	fn diff_data() {
+		let dictionary = ChineseDictionary::new();
+		let metadata = dictionary.metadata();
+		let mut entries: Vec<WordEntry> = dictionary.entries().filter(|entry| entry.word_id != 0).cloned().collect();
+		let next_id = metadata.entries.keys().max().unwrap() + 1;
+		entries.iter_mut().find(|entry| entry.word_id == 1).unwrap().english.push("updated".to_string());
+		entries.push(WordEntry { word_id: next_id, ..entries[0].clone() });
+
+		let diff = ChineseDictionary::from_entries(entries).diff_data(&metadata);
+		assert_eq!(vec![next_id], diff.added);
+		assert_eq!(vec![0], diff.removed);
+		assert_eq!(vec![1], diff.changed);
+		assert!(dictionary.diff_data(&metadata).is_empty());
+	}
+
+	#[test]
+	fn from_entries() {
+		let entry = WordEntry {
+			traditional: "線上".to_string(),
+			simplified: "线上".to_string(),
+			pinyin_marks: "xiàn shàng".to_string(),
+			pinyin_numbers: "xian4 shang4".to_string(),
+			english: vec!["online".to_string()],
+			tone_marks: vec![4, 4],
+			hash: 0,
+			measure_words: vec![],
+			hsk: 0,
+			word_id: 7,
+			domain: None,
+			year: None,
+			source: SourceId::User,
+		};
+		let dictionary = ChineseDictionary::from_entries(vec![entry]);
+		assert_eq!(7, dictionary.query_by_english("online").first().unwrap().word_id);
+		assert_eq!(7, dictionary.query_by_pinyin("xian4shang4").first().unwrap().word_id);
+		assert_eq!(vec!["线上".to_string()], dictionary.segment("线上"));
+		assert!(dictionary.query_by_english("watermelon").is_empty());
+		assert_eq!(vec![SourceId::User], dictionary.sources().iter().map(|source| source.id).collect::<Vec<SourceId>>());
+	}
+
+	#[test]
+	fn sources() {
+		let dictionary = ChineseDictionary::new();
+		assert_eq!(SourceId::CcCedict, dictionary.query_by_chinese("西瓜")[0].source);
+		assert_eq!(Some("CC-CEDICT"), dictionary.sources().first().map(|source| source.name));
+		assert_eq!(Some("Creative Commons Attribution-ShareAlike 4.0"), SourceId::CcCedict.source().license);
+		assert_eq!(Some("MIT"), SourceId::GazetteerBrands.source().license);
+	}
+
+	#[test]
+	fn merge_dictionaries() {
+		let mut builtin = ChineseDictionary::new();
+		builtin.set_normalizer(Normalizer { lowercase: true, ..Normalizer::default() });
+		builtin.set_segmentation_mode(SegmentationMode::Unigram);
+		let mut custom = builtin.query_by_chinese("西瓜").first().cloned().cloned().unwrap();
+		custom.english = vec!["custom watermelon".to_string()];
+		let user = ChineseDictionary::from_entries(vec![custom.clone()]);
+
+		let shadowed = ChineseDictionary::merge(&[&user, &builtin], MergeStrategy::WordId);
+		assert_eq!(custom.english, shadowed.query_by_chinese("西瓜").first().unwrap().english);
+		assert!(!shadowed.query_by_english("custom watermelon").is_empty());
+
+		let supplemented = ChineseDictionary::merge(&[&builtin, &user], MergeStrategy::HeadwordPinyin);
+		assert_eq!(builtin.entries().count(), supplemented.entries().count());
+		assert_eq!(builtin.query_by_chinese("西瓜").first().unwrap().english, supplemented.query_by_chinese("西瓜").first().unwrap().english);
+		// The merged dictionary keeps the configuration and frequencies of the first dictionary
+		assert_eq!(builtin.normalizer(), supplemented.normalizer());
+		assert_eq!(SegmentationMode::Unigram, supplemented.segmentation_mode());
+		assert_eq!("西瓜", supplemented.query("Watermelon").unwrap().first().unwrap().simplified);
+		assert_eq!(vec!["今天", "天气", "不错"], supplemented.segment("今天天气不错"));
+	}
+
+	#[test]
+	fn reload_user_dictionary() {
+		let path = std::env::temp_dir().join("chinese_dictionary_reload_test.u8");
+		std::fs::write(&path, "內捲 内卷 [nei4 juan3] /involution/\n").unwrap();
+		let mut base = ChineseDictionary::from_entries(vec![]);
+		base.set_normalizer(Normalizer::none());
+		let dictionary = ReloadableDictionary::new(base);
+		let before = dictionary.snapshot();
+		assert_eq!(1, dictionary.reload_user_dictionary(&path).unwrap());
+		assert!(before.query_by_chinese("内卷").is_empty());
+		assert_eq!(&Normalizer::none(), dictionary.snapshot().normalizer());
+		let entry = dictionary.snapshot().query_by_chinese("内卷")[0].clone();
+		assert_eq!((vec!["involution".to_string()], SourceId::User), (entry.english, entry.source));
+
+		std::fs::write(&path, "內捲 内卷 [nei4 juan3] /involution/\nnot an entry\n").unwrap();
+		assert!(dictionary.reload_user_dictionary(&path).is_err());
+		assert!(!dictionary.snapshot().query_by_chinese("内卷").is_empty());
+		dictionary.clear_user_dictionary();
+		assert!(dictionary.snapshot().query_by_chinese("内卷").is_empty());
+		std::fs::remove_file(&path).unwrap();
+	}
+
+	#[cfg(feature = "terms-it")]
+	#[test]
+	fn terminology_it() {
+		let dictionary = ChineseDictionary::new();
+		assert_eq!(vec!["负载均衡".to_string()], dictionary.segment("负载均衡"));
+		assert!(dictionary.query_by_chinese("负载均衡").iter().all(|entry| entry.domain == Some(Domain::It)));
+		assert!(dictionary.query_by_chinese("区块链").iter().any(|entry| entry.domain == Some(Domain::It)));
+		assert_eq!(None, dictionary.query_by_chinese("西瓜")[0].domain);
+	}
+
+	#[cfg(feature = "neologisms")]
+	#[test]
+	fn neologism_years() {
+		let mut dictionary = ChineseDictionary::new();
+		assert_eq!(vec!["躺平".to_string()], dictionary.segment("躺平"));
+		let tang_ping = dictionary.query_by_chinese("躺平");
+		assert_eq!(Some(2021), tang_ping[0].year);
+		assert_eq!(Some(2010), dictionary.query_by_chinese("给力")[0].year);
+		assert!(!dictionary.query_by_chinese("西瓜")[0].is_neologism());
+		dictionary.set_query_options(QueryOptions { years: Some(2020..=2021), ..QueryOptions::default() });
+		assert_eq!(Some(2020), dictionary.query("内卷").unwrap()[0].year);
+		assert!(dictionary.query("给力").unwrap().is_empty());
+		// Entries already in the buffer are kept by the years filter
+		let mut results = dictionary.query_by_chinese("西瓜");
+		assert!(dictionary.query_into("给力", &mut results));
+		assert!(dictionary.query_into("内卷", &mut results));
+		assert_eq!(vec!["西瓜", "内卷"], results.iter().map(|entry| entry.simplified.as_str()).collect::<Vec<&str>>());
+	}
+
+	#[test]
+	fn segment_classical() {
+		let mut dictionary = ChineseDictionary::new();
+		dictionary.set_segmentation_mode(SegmentationMode::Classical);
+		let expected: Vec<String> = ["孔子", "曰", "学", "而", "时", "习", "之"].iter().map(|word| word.to_string()).collect();
+		assert_eq!(expected, dictionary.segment("孔子曰学而时习之"));
+	}
+
+	#[cfg(feature = "classical")]
+	#[test]
+	fn classical_glossary() {
+		let mut dictionary = ChineseDictionary::new();
+		let zhi = dictionary.query_classical("之");
+		assert_eq!(SourceId::ClassicalGlossary, zhi[0].source);
+		assert!(zhi.iter().any(|entry| entry.source == SourceId::CcCedict));
+		assert!(dictionary.query_by_chinese("之").iter().all(|entry| entry.source != SourceId::ClassicalGlossary));
+		dictionary.set_segmentation_mode(SegmentationMode::Classical);
+		let expected: Vec<String> = ["不亦", "乐", "乎"].iter().map(|word| word.to_string()).collect();
+		assert_eq!(expected, dictionary.segment("不亦乐乎"));
+	}
+
+	#[cfg(feature = "grammar-patterns")]
+	#[test]
+	fn grammar_patterns_for_entries() {
+		let dictionary = ChineseDictionary::new();
+		let ba = dictionary.query_by_chinese("把").into_iter().find(|entry| entry.pinyin_numbers == "ba3").unwrap();
+		assert_eq!("Subj + 把 + Obj + Verb + Complement", grammar_patterns(ba)[0].pattern);
+		let yue = dictionary.query_by_chinese("越").into_iter().next().unwrap();
+		assert!(grammar_patterns(yue).iter().any(|pattern| pattern.pattern == "越 + A + 越 + B"));
+		assert!(grammar_patterns(dictionary.query_by_chinese("猫")[0]).is_empty());
+	}
+
+	#[cfg(feature = "gazetteer-brands")]
+	#[test]
+	fn gazetteer_brands() {
+		let dictionary = ChineseDictionary::new();
+		assert_eq!(vec!["我".to_string(), "在".to_string(), "京东".to_string(), "买".to_string()], dictionary.segment("我在京东买"));
+		assert!(dictionary.query_by_pinyin("jing1dong1").iter().any(|entry| entry.traditional == "京東"));
+	}
+
+	#[cfg(feature = "gazetteer-places")]
+	#[test]
+	fn gazetteer_places() {
+		let dictionary = ChineseDictionary::new();
+		assert_eq!("Lù jiā zuǐ", dictionary.query_by_chinese("陆家嘴").first().unwrap().pinyin_marks);
+		// Without the gazetteer, these split into 三 and 沙市, and into single characters
+		let expected: Vec<String> = ["他", "在", "三沙市", "工作"].iter().map(|word| word.to_string()).collect();
+		assert_eq!(expected, dictionary.segment("他在三沙市工作"));
+		let expected: Vec<String> = ["埃斯瓦蒂尼", "的", "首都", "是", "姆巴巴内"].iter().map(|word| word.to_string()).collect();
+		assert_eq!(expected, dictionary.segment("埃斯瓦蒂尼的首都是姆巴巴内"));
+		assert!(dictionary.query_by_chinese("金奈").iter().all(|entry| entry.source == SourceId::GazetteerPlaces));
+		// Names CC-CEDICT already has are not added a second time
+		let shijiazhuang = dictionary.query_by_chinese_both("石家庄");
+		assert_eq!(1, shijiazhuang.len());
+		assert_eq!(SourceId::CcCedict, shijiazhuang[0].0.source);
+	}
+
 	#[test]
 	fn classify_english() {
 		let dictionary = ChineseDictionary::new();
@@ -284,6 +1737,102 @@ mod tests {
 		assert_eq!(expected, actual);
 	}
 
+	#[test]
+	fn rhymes_with() {
+		let dictionary = ChineseDictionary::new();
+		let rhymes = dictionary.rhymes_with("光", false);
+		assert!(["双", "窗", "黄", "王"].iter().all(|word| rhymes.iter().any(|entry| entry.simplified == *word)));
+		assert!(rhymes.iter().all(|entry| entry.simplified != "光" && entry.simplified != "方"));
+		let same_tone = dictionary.rhymes_with("光", true);
+		assert!(same_tone.iter().any(|entry| entry.simplified == "双"));
+		assert!(same_tone.iter().all(|entry| entry.simplified != "黄" && entry.simplified != "王"));
+		assert!(dictionary.rhymes_with("鱼", true).iter().any(|entry| entry.simplified == "局"));
+		assert!(dictionary.rhymes_with("他", false).iter().any(|entry| entry.simplified == "那儿"));
+	}
+
+	#[test]
+	fn homophones() {
+		let dictionary = ChineseDictionary::new();
+		let shi = dictionary.query_homophones("shi4");
+		assert!(["是", "事", "市"].iter().all(|word| shi.iter().any(|entry| entry.simplified == *word)));
+		assert!(shi.iter().all(|entry| entry.pinyin_numbers.to_lowercase() == "shi4"));
+		assert_eq!(shi.iter().map(|entry| entry.word_id).collect::<Vec<u32>>(), dictionary.query_homophones("shì").iter().map(|entry| entry.word_id).collect::<Vec<u32>>());
+		assert!(dictionary.query_homophones("shi").is_empty());
+
+		let gongshi = dictionary.query_by_chinese("公式").into_iter().next().unwrap();
+		let homophones = dictionary.homophones(gongshi);
+		assert!(homophones.iter().any(|entry| entry.simplified == "攻势"));
+		assert!(homophones.iter().all(|entry| entry.simplified != "公式"));
+	}
+
+	#[test]
+	fn pronunciation_variants() {
+		let dictionary = ChineseDictionary::new();
+		let xing = dictionary.query_by_chinese("行").into_iter().find(|entry| entry.pinyin_numbers == "xing2").unwrap();
+		let variants = dictionary.pronunciation_variants(xing);
+		assert!(variants.iter().any(|entry| entry.pinyin_numbers == "hang2"));
+		assert!(variants.iter().all(|entry| entry.simplified == "行" && entry.pinyin_numbers.to_lowercase() != "xing2"));
+		let hang = variants.iter().find(|entry| entry.pinyin_numbers == "hang2").unwrap();
+		assert!(dictionary.pronunciation_variants(hang).iter().any(|entry| entry.word_id == xing.word_id));
+	}
+
+	#[test]
+	fn measure_words_in_glosses() {
+		let dictionary = ChineseDictionary::new();
+		let entry = dictionary.query_by_chinese("手脚").into_iter().next().unwrap();
+		assert!(entry.english.contains(&String::from("step in a procedure")));
+		assert_eq!(vec!["道"], entry.measure_words.iter().map(|measure_word| measure_word.simplified.as_str()).collect::<Vec<&str>>());
+		assert!(dictionary.entries().all(|entry| entry.english.iter().all(|gloss| !gloss.contains("CL:"))));
+		assert!(dictionary.query_by_english("step in a procedure").iter().any(|entry| entry.simplified == "手脚"));
+
+		let directory = std::env::temp_dir().join("chinese_dictionary_measure_words_in_glosses");
+		std::fs::create_dir_all(&directory).unwrap();
+		std::fs::write(directory.join("cedict_ts.u8"), "念 念 [nian4] /to read/to give (sb) a tongue-lashing (CL:頓|顿[dun4])/CL:次[ci4]/\n").unwrap();
+		build_from_cedict(directory.join("cedict_ts.u8"), &directory).unwrap();
+		let data: std::collections::HashMap<u32, WordEntry> = bincode::deserialize_from(std::fs::File::open(directory.join("data.dictionary")).unwrap()).unwrap();
+		let entry = data.get(&0).unwrap();
+		assert_eq!(vec!["to read", "to give (sb) a tongue-lashing"], entry.english);
+		assert_eq!(vec!["顿", "次"], entry.measure_words.iter().map(|measure_word| measure_word.simplified.as_str()).collect::<Vec<&str>>());
+
+		std::fs::write(directory.join("cedict_ts.u8"), "人 人 [ren2] /person/ CL:個|个[ge4]/\n事 事 [shi4] /matter/(CL:件[jian4])/\n").unwrap();
+		build_from_cedict(directory.join("cedict_ts.u8"), &directory).unwrap();
+		let data: std::collections::HashMap<u32, WordEntry> = bincode::deserialize_from(std::fs::File::open(directory.join("data.dictionary")).unwrap()).unwrap();
+		assert_eq!(vec!["person"], data[&0].english);
+		assert_eq!(vec!["个"], data[&0].measure_words.iter().map(|measure_word| measure_word.simplified.as_str()).collect::<Vec<&str>>());
+		assert_eq!(vec!["matter"], data[&1].english);
+		assert_eq!(vec!["件"], data[&1].measure_words.iter().map(|measure_word| measure_word.simplified.as_str()).collect::<Vec<&str>>());
+	}
+
+	#[test]
+	fn clean_gloss() {
+		assert_eq!("Bilibili, Chinese video-sharing website featuring scrolled user comments 弹幕 overlaid on the videos", super::clean_gloss("(coll.) Bilibili, Chinese video-sharing website featuring scrolled user comments 彈幕|弹幕[dan4 mu4] overlaid on the videos"));
+		assert_eq!("to cram (from Taiwanese 齧書, Tai-lo pr. [khè su], lit. to gnaw a book)", super::clean_gloss("(Tw) to cram (from Taiwanese 齧書, Tai-lo pr. [khè su], lit. to gnaw a book)"));
+		assert_eq!("sandwich", super::clean_gloss("sandwich (loanword)"));
+		assert_eq!("", super::clean_gloss("CL:個|个[ge4]"));
+		let cleanup = GlossCleanup { pinyin: false, references: Some(Script::Traditional), ..GlossCleanup::default() };
+		assert_eq!("see 開金[kai1 jin1]", cleanup.clean("see 開金|开金[kai1 jin1]"));
+
+		let dictionary = ChineseDictionary::new();
+		let entry = dictionary.query_by_chinese("B站").into_iter().next().unwrap();
+		assert!(entry.english[0].starts_with("(coll.)"));
+		assert!(entry.clean_english(&GlossCleanup::default())[0].starts_with("Bilibili"));
+	}
+
+	#[test]
+	fn classification_options() {
+		let mut dictionary = ChineseDictionary::new();
+		assert_eq!(ClassificationResult::PY, dictionary.classify("ma5"));
+		assert_eq!(ClassificationResult::PY, dictionary.classify("nǚ"));
+		assert_eq!(ClassificationResult::EN, dictionary.classify("test"));
+		let mut options = ClassificationOptions { ambiguous: AmbiguousQuery::Pinyin, ..ClassificationOptions::default() };
+		options.english_words.insert(String::from("me"));
+		dictionary.set_classification_options(options);
+		assert_eq!(ClassificationResult::EN, dictionary.classify("Me"));
+		assert_eq!(ClassificationResult::PY, dictionary.classify("ma"));
+		assert_eq!(ClassificationResult::EN, dictionary.classify("boat"));
+		assert!(dictionary.query("ma").unwrap().iter().any(|entry| entry.simplified == "吗"));
+	}
+
 	#[test]
 	fn convert_to_traditional() {
 		let dictionary = ChineseDictionary::new();
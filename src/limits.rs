@@ -0,0 +1,25 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Limits on the size of the input accepted by queries and tokenization
+
+use crate::tokenizer::MAX_TOKENIZE_BYTES;
+
+/// The largest query, in bytes, accepted by `query` by default.
+pub const MAX_QUERY_BYTES: usize = 4096;
+
+/// # Input Limits
+/// The largest inputs the dictionary accepts, set with `Dictionary::set_input_limits`, so an accidental paste of a whole book
+/// into a search box is refused instead of being searched. Longer text can be tokenized in pieces with `tokenize_chunked`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputLimits {
+	/// The longest query, in bytes, accepted by `query` and the methods built on it. Longer queries find nothing. `MAX_QUERY_BYTES` by default
+	pub max_query_bytes: usize,
+	/// The longest input, in bytes, accepted by `tokenize_checked`, and the largest chunk used by `tokenize_chunked`. `MAX_TOKENIZE_BYTES` by default
+	pub max_tokenize_bytes: usize,
+}
+
+impl Default for InputLimits {
+	fn default() -> InputLimits {
+		InputLimits { max_query_bytes: MAX_QUERY_BYTES, max_tokenize_bytes: MAX_TOKENIZE_BYTES }
+	}
+}
@@ -0,0 +1,23 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Lookup of the measure words used with a noun
+
+use crate::chinese_dictionary::{Dictionary, MeasureWord};
+
+impl Dictionary {
+	/// # Measure Words For
+	/// The measure words used with a word written in simplified or traditional characters, e.g. 本, 册, and 部 for 书 or 書.
+	/// The measure words of every entry with the headword are merged, in the order they first appear, without duplicates.
+	pub fn measure_words_for(&self, word: &str) -> Vec<&MeasureWord> {
+		let mut measure_words: Vec<&MeasureWord> = Vec::new();
+		for entry in self.entries_for_headword(word) {
+			for measure_word in &entry.measure_words {
+				if !measure_words.contains(&measure_word) {
+					measure_words.push(measure_word);
+				}
+			}
+		}
+
+		measure_words
+	}
+}
@@ -0,0 +1,80 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Metadata for comparing releases of the dictionary data
+
+use crate::chinese_dictionary::WordEntry;
+use crate::hash::FieldHasher;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// # Data Metadata
+/// A summary of the contents of a dictionary: the crate version that produced it and a content hash for every entry, keyed by `word_id`.
+/// It can be serialized and stored by applications that cache entries locally, then compared against a newer dictionary with `diff_data`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DataMetadata {
+	pub version: String,
+	pub entries: BTreeMap<u32, u64>,
+}
+
+/// # Data Diff
+/// The `word_id`s that were added, removed, or changed between two versions of the dictionary data, each in ascending order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DataDiff {
+	pub added: Vec<u32>,
+	pub removed: Vec<u32>,
+	pub changed: Vec<u32>,
+}
+
+impl DataDiff {
+	/// Checks if the two versions contain exactly the same entries.
+	pub fn is_empty(&self) -> bool {
+		self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+	}
+}
+
+impl DataMetadata {
+	pub(crate) fn from_entries<'a, I: IntoIterator<Item = &'a WordEntry>>(entries: I) -> DataMetadata {
+		DataMetadata {
+			version: env!("CARGO_PKG_VERSION").to_string(),
+			entries: entries.into_iter().map(|entry| (entry.word_id, content_hash(entry))).collect(),
+		}
+	}
+
+	/// # Diff
+	/// Compare this metadata against the metadata of an older version of the data.
+	pub fn diff(&self, older: &DataMetadata) -> DataDiff {
+		let mut diff = DataDiff::default();
+
+		for (word_id, hash) in &self.entries {
+			match older.entries.get(word_id) {
+				Some(older_hash) if older_hash != hash => diff.changed.push(*word_id),
+				Some(_) => {},
+				None => diff.added.push(*word_id),
+			}
+		}
+		diff.removed = older.entries.keys().filter(|word_id| !self.entries.contains_key(word_id)).cloned().collect();
+
+		diff
+	}
+}
+
+/// A 64-bit FNV-1a hash of every field of an entry except `hash` and `word_id`.
+pub(crate) fn content_hash(entry: &WordEntry) -> u64 {
+	let mut hasher = FieldHasher::new();
+	hasher.write(entry.traditional.as_bytes());
+	hasher.write(entry.simplified.as_bytes());
+	hasher.write(entry.pinyin_marks.as_bytes());
+	hasher.write(entry.pinyin_numbers.as_bytes());
+	for gloss in &entry.english {
+		hasher.write(gloss.as_bytes());
+	}
+	hasher.write(&entry.tone_marks);
+	for measure_word in &entry.measure_words {
+		hasher.write(measure_word.traditional.as_bytes());
+		hasher.write(measure_word.simplified.as_bytes());
+		hasher.write(measure_word.pinyin_marks.as_bytes());
+		hasher.write(measure_word.pinyin_numbers.as_bytes());
+	}
+	hasher.write(&[entry.hsk]);
+	hasher.finish()
+}
@@ -0,0 +1,27 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Optional list of neologisms and Internet slang tagged with the year they came into use
+
+use crate::builder::parse_cedict;
+use crate::chinese_dictionary::WordEntry;
+use crate::source::SourceId;
+
+/// The bundled list of neologisms when the `neologisms` feature is enabled.
+/// Each line is a year, a tab, and an entry in the CC-CEDICT format.
+#[cfg(feature = "neologisms")]
+static NEOLOGISMS: &str = include_str!("../data/neologisms/neologisms.u8");
+#[cfg(not(feature = "neologisms"))]
+static NEOLOGISMS: &str = "";
+
+/// Parses the bundled neologisms, each with the year it came into wide use.
+pub(crate) fn entries() -> Vec<(u16, WordEntry)> {
+	NEOLOGISMS.lines()
+		.filter(|line| !line.is_empty() && !line.starts_with('#'))
+		.map(|line| {
+			let (year, entry) = line.split_once('\t').expect("Bundled neologisms start with a year");
+			let mut entry = parse_cedict(entry).expect("Bundled neologisms are valid CC-CEDICT").remove(0);
+			entry.source = SourceId::Neologisms;
+			(year.parse().expect("Bundled neologisms start with a year"), entry)
+		})
+		.collect()
+}
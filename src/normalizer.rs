@@ -0,0 +1,85 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Configurable preprocessing applied to queries
+
+use crate::chinese_dictionary::Script;
+use std::borrow::Cow;
+use unicode_normalization::{is_nfkc_quick, IsNormalized, UnicodeNormalization};
+
+/// # Normalizer
+/// The preprocessing steps `query` applies to its input before looking anything up.
+/// Each step can be turned on or off independently. The steps run in the order the fields are listed.
+///
+/// By default the query is trimmed and NFKC normalized, and its case is left alone, so proper nouns such as "Beijing" stay intact.
+/// Turning on `lowercase` matches how the English and pinyin indexes are keyed, so queries containing capitals match them too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Normalizer {
+	/// Remove leading and trailing whitespace
+	pub trim: bool,
+	/// Apply Unicode NFKC normalization, which folds full width Latin letters and digits to their ASCII forms
+	pub nfkc: bool,
+	/// Lowercase the query
+	pub lowercase: bool,
+	/// Remove Western and Chinese punctuation
+	pub strip_punctuation: bool,
+	/// Convert Chinese characters to a single script before querying
+	pub fold_script: Option<Script>,
+}
+
+impl Default for Normalizer {
+	fn default() -> Normalizer {
+		Normalizer {
+			trim: true,
+			nfkc: true,
+			lowercase: false,
+			strip_punctuation: false,
+			fold_script: None,
+		}
+	}
+}
+
+impl Normalizer {
+	/// # None
+	/// A normalizer with every step turned off, passing queries through unchanged.
+	pub fn none() -> Normalizer {
+		Normalizer {
+			trim: false,
+			nfkc: false,
+			lowercase: false,
+			strip_punctuation: false,
+			fold_script: None,
+		}
+	}
+
+	/// Applies the text steps of this normalizer. Script folding requires the character conversion data
+	/// owned by the dictionary, and is applied by `Dictionary::normalize`.
+	/// The query is borrowed unless a step changes it, so already normalized queries are not copied.
+	pub(crate) fn normalize_text<'a>(&self, raw: &'a str) -> Cow<'a, str> {
+		let mut text = Cow::Borrowed(if self.trim { raw.trim() } else { raw });
+		if self.nfkc && is_nfkc_quick(text.chars()) != IsNormalized::Yes {
+			text = Cow::Owned(text.nfkc().collect());
+		}
+		if self.lowercase && text.chars().any(char::is_uppercase) {
+			text = Cow::Owned(text.to_lowercase());
+		}
+		if self.strip_punctuation && text.chars().any(is_punctuation) {
+			text = Cow::Owned(text.chars().filter(|c| !is_punctuation(*c)).collect());
+		}
+
+		text
+	}
+}
+
+/// Checks if a character is Western or Chinese punctuation.
+pub(crate) fn is_punctuation(c: char) -> bool {
+	c.is_ascii_punctuation()
+		|| ('\u{2010}'..='\u{2027}').contains(&c) // General punctuation such as dashes, quotation marks, and ellipses
+		|| ('\u{3001}'..='\u{3003}').contains(&c) // 、。〃
+		|| ('\u{3008}'..='\u{3011}').contains(&c) // Angle and corner brackets
+		|| ('\u{3014}'..='\u{301F}').contains(&c)
+		|| ('\u{FF01}'..='\u{FF0F}').contains(&c) // Full width punctuation
+		|| ('\u{FF1A}'..='\u{FF20}').contains(&c)
+		|| ('\u{FF3B}'..='\u{FF40}').contains(&c)
+		|| ('\u{FF5B}'..='\u{FF65}').contains(&c)
+		|| c == '·'
+}
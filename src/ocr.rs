@@ -0,0 +1,85 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Correction of characters misread by OCR using look-alike characters
+
+use crate::chinese_dictionary::Dictionary;
+use crate::confusables::similar_characters;
+use crate::punctuation::is_han;
+use std::ops::Range;
+
+/// How many characters on each side of a character are scored along with it.
+const CONTEXT_LENGTH: usize = 3;
+
+/// How much higher the `segmentation_score` of the text around a character must be with a look-alike in its place for the look-alike to be proposed.
+const MIN_GAIN: f32 = 3.0;
+
+/// # Correction
+/// A look-alike character proposed in place of one that was probably misread, as returned by `correct_ocr`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Correction {
+	/// The byte range of the misread character in the text
+	pub range: Range<usize>,
+	pub original: char,
+	pub replacement: char,
+	/// How much higher the `segmentation_score` of the text around the character is with the replacement
+	pub gain: f32,
+}
+
+impl Dictionary {
+	/// # Correct OCR
+	/// Propose fixes for characters that were probably misread in scanned text, such as 今夭天气很好 for 今天天气很好.
+	/// A character is only considered when it is not part of a longer dictionary word, and it is replaced by whichever of its look-alikes from `similar_characters`
+	/// most raises the `segmentation_score` of the few characters around it, when that raises it enough to be confident.
+	/// Corrections are in the order of the text, and are proposed independently of each other.
+	pub fn correct_ocr(&self, text: &str) -> Vec<Correction> {
+		let characters: Vec<(usize, char)> = text.char_indices().collect();
+		let in_words: Vec<bool> = {
+			let mut in_words = vec![false; text.len()];
+			for range in self.scan(text).into_iter().filter(|range| text[range.clone()].chars().count() > 1) {
+				in_words[range].iter_mut().for_each(|in_word| *in_word = true);
+			}
+			in_words
+		};
+
+		let mut corrections: Vec<Correction> = Vec::new();
+		for (index, (position, original)) in characters.iter().enumerate() {
+			if in_words[*position] || !is_han(*original) {
+				continue;
+			}
+			let similar = similar_characters(*original);
+			if similar.is_empty() {
+				continue;
+			}
+
+			// The context stops at anything that is not a Chinese character, such as punctuation
+			let start = (index.saturating_sub(CONTEXT_LENGTH)..index).rev().take_while(|before| is_han(characters[*before].1)).last().unwrap_or(index);
+			let end = (index + 1..characters.len().min(index + 1 + CONTEXT_LENGTH)).take_while(|after| is_han(characters[*after].1)).last().map_or(index + 1, |after| after + 1);
+			let context = |replacement: char| -> String {
+				characters[start..end].iter().map(|(at, character)| if at == position { replacement } else { *character }).collect()
+			};
+			let original_score = self.context_score(&context(*original));
+			let best = similar.into_iter()
+				.map(|replacement| (replacement, self.context_score(&context(replacement)) - original_score))
+				.max_by(|a, b| a.1.total_cmp(&b.1));
+			if let Some((replacement, gain)) = best.filter(|(_, gain)| *gain >= MIN_GAIN) {
+				corrections.push(Correction { range: *position..*position + original.len_utf8(), original: *original, replacement, gain });
+			}
+		}
+
+		corrections
+	}
+
+	/// The `segmentation_score` of a piece of text, segmented as `segment` does, with every character outside of a dictionary word as a word of its own.
+	fn context_score(&self, text: &str) -> f32 {
+		let mut tokens: Vec<&str> = Vec::new();
+		let mut position = 0;
+		for range in self.scan(text) {
+			tokens.extend(text[position..range.start].char_indices().map(|(at, character)| &text[position + at..position + at + character.len_utf8()]));
+			position = range.end;
+			tokens.push(&text[range]);
+		}
+		tokens.extend(text[position..].char_indices().map(|(at, character)| &text[position + at..position + at + character.len_utf8()]));
+
+		self.segmentation_score(&tokens)
+	}
+}
@@ -0,0 +1,404 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Utilities for working with pinyin
+
+use std::ops::Range;
+
+static TONED_VOWELS: [(char, [char; 4]); 12] = [
+	('a', ['ā', 'á', 'ǎ', 'à']),
+	('e', ['ē', 'é', 'ě', 'è']),
+	('i', ['ī', 'í', 'ǐ', 'ì']),
+	('o', ['ō', 'ó', 'ǒ', 'ò']),
+	('u', ['ū', 'ú', 'ǔ', 'ù']),
+	('ü', ['ǖ', 'ǘ', 'ǚ', 'ǜ']),
+	('A', ['Ā', 'Á', 'Ǎ', 'À']),
+	('E', ['Ē', 'É', 'Ě', 'È']),
+	('I', ['Ī', 'Í', 'Ǐ', 'Ì']),
+	('O', ['Ō', 'Ó', 'Ǒ', 'Ò']),
+	('U', ['Ū', 'Ú', 'Ǔ', 'Ù']),
+	('Ü', ['Ǖ', 'Ǘ', 'Ǚ', 'Ǜ']),
+];
+
+/// Every syllable of Standard Mandarin without tones, in sorted order, including the interjections `m`, `n`, `ng`, `hm`, `hng`, and `ê`.
+pub(crate) static SYLLABLES: [&str; 421] = [
+	"a", "ai", "an", "ang", "ao", "ba", "bai", "ban", "bang", "bao", "bei", "ben", "beng", "bi", "bia", "bian",
+	"biang", "biao", "bie", "bin", "bing", "biu", "bo", "bu", "ca", "cai", "can", "cang", "cao", "ce", "cen",
+	"ceng", "cha", "chai", "chan", "chang", "chao", "che", "chen", "cheng", "chi", "chong", "chou", "chu",
+	"chua", "chuai", "chuan", "chuang", "chui", "chun", "chuo", "ci", "cong", "cou", "cu", "cuan", "cui", "cun",
+	"cuo", "da", "dai", "dan", "dang", "dao", "de", "dei", "den", "deng", "di", "dia", "dian", "diao", "die",
+	"ding", "diu", "dong", "dou", "du", "duan", "dui", "dun", "duo", "e", "ei", "en", "eng", "er", "fa", "fan",
+	"fang", "fei", "fen", "feng", "fiao", "fo", "fou", "fu", "ga", "gai", "gan", "gang", "gao", "ge", "gei",
+	"gen", "geng", "gong", "gou", "gu", "gua", "guai", "guan", "guang", "gui", "gun", "guo", "ha", "hai", "han",
+	"hang", "hao", "he", "hei", "hen", "heng", "hm", "hng", "hong", "hou", "hu", "hua", "huai", "huan", "huang",
+	"hui", "hun", "huo", "ji", "jia", "jian", "jiang", "jiao", "jie", "jin", "jing", "jiong", "jiu", "ju",
+	"juan", "jue", "jun", "ka", "kai", "kan", "kang", "kao", "ke", "kei", "ken", "keng", "kong", "kou", "ku",
+	"kua", "kuai", "kuan", "kuang", "kui", "kun", "kuo", "la", "lai", "lan", "lang", "lao", "le", "lei", "leng",
+	"li", "lia", "lian", "liang", "liao", "lie", "lin", "ling", "liu", "lo", "long", "lou", "lu", "luan", "lun",
+	"luo", "lü", "lüe", "m", "ma", "mai", "man", "mang", "mao", "me", "mei", "men", "meng", "mi", "mian", "miao",
+	"mie", "min", "ming", "miu", "mo", "mou", "mu", "n", "na", "nai", "nan", "nang", "nao", "ne", "nei", "nen",
+	"neng", "ng", "ni", "nian", "niang", "niao", "nie", "nin", "ning", "niu", "nong", "nou", "nu", "nuan", "nun",
+	"nuo", "nü", "nüe", "o", "ou", "pa", "pai", "pan", "pang", "pao", "pei", "pen", "peng", "pi", "pian", "piao",
+	"pie", "pin", "ping", "po", "pou", "pu", "qi", "qia", "qian", "qiang", "qiao", "qie", "qin", "qing", "qiong",
+	"qiu", "qu", "quan", "que", "qun", "ran", "rang", "rao", "re", "ren", "reng", "ri", "rong", "rou", "ru",
+	"ruan", "rui", "run", "ruo", "sa", "sai", "san", "sang", "sao", "se", "sei", "sen", "seng", "sha", "shai",
+	"shan", "shang", "shao", "she", "shei", "shen", "sheng", "shi", "shou", "shu", "shua", "shuai", "shuan",
+	"shuang", "shui", "shun", "shuo", "si", "song", "sou", "su", "suan", "sui", "sun", "suo", "ta", "tai", "tan",
+	"tang", "tao", "te", "tei", "teng", "ti", "tian", "tiao", "tie", "ting", "tong", "tou", "tu", "tuan", "tui",
+	"tun", "tuo", "wa", "wai", "wan", "wang", "wei", "wen", "weng", "wo", "wu", "xi", "xia", "xian", "xiang",
+	"xiao", "xie", "xin", "xing", "xiong", "xiu", "xu", "xuan", "xue", "xun", "ya", "yan", "yang", "yao", "ye",
+	"yi", "yin", "ying", "yo", "yong", "you", "yu", "yuan", "yue", "yun", "za", "zai", "zan", "zang", "zao",
+	"ze", "zei", "zen", "zeng", "zha", "zhai", "zhan", "zhang", "zhao", "zhe", "zhen", "zheng", "zhi", "zhong",
+	"zhou", "zhu", "zhua", "zhuai", "zhuan", "zhuang", "zhui", "zhun", "zhuo", "zi", "zong", "zou", "zu", "zuan",
+	"zui", "zun", "zuo", "ê"
+];
+
+/// Splits a syllable written with a tone number into its letters and tone, e.g. `"hao3"` into `("hao", Some(3))`.
+pub(crate) fn split_tone_number(syllable: &str) -> (&str, Option<u8>) {
+	match syllable.chars().last().and_then(|last| last.to_digit(10)) {
+		Some(tone) if (1..=5).contains(&tone) => (&syllable[..syllable.len() - 1], Some(tone as u8)),
+		_ => (syllable, None),
+	}
+}
+
+/// # Add Tone Mark
+/// Write a syllable without tones, such as `"hao"`, with the tone mark for `tone`, such as `"hǎo"` for tone 3, e.g. to build pinyin from tone input.
+/// The mark goes on `a` or `e` when the syllable has one, on the `o` of `ou`, and otherwise on the last of `i`, `o`, `u`, and `ü`, so `"gui"` becomes `"guì"` for tone 4.
+/// `v` and `u:` are written as `ü`, so `"lv"` and `"lu:"` both become `"lǜ"` for tone 4, and case is kept.
+/// Tones outside of 1 through 4, such as the neutral tone `5`, leave the syllable without a mark.
+pub fn add_tone_mark(syllable: &str, tone: u8) -> String {
+	let syllable = syllable.replace("u:", "ü").replace("U:", "Ü").replace('v', "ü").replace('V', "Ü");
+	place_tone_mark(&syllable, tone)
+}
+
+/// Places the tone mark for `tone` on the correct vowel of a syllable written without tones, keeping any `u:` as it is, as CC-CEDICT does.
+/// Tones outside of 1 through 4 leave the syllable unchanged.
+pub(crate) fn place_tone_mark(syllable: &str, tone: u8) -> String {
+	if !(1..=4).contains(&tone) {
+		return syllable.to_string();
+	}
+
+	let lowercase = syllable.to_lowercase();
+	let position = lowercase.find('a')
+		.or_else(|| lowercase.find('e'))
+		.or_else(|| lowercase.find("ou"))
+		.or_else(|| lowercase.rfind(|c: char| "iouü".contains(c)));
+
+	match position {
+		Some(position) => syllable.char_indices().map(|(index, c)| {
+			if index == position { toned_vowel(c, tone) } else { c }
+		}).collect(),
+		None => syllable.to_string(),
+	}
+}
+
+fn toned_vowel(vowel: char, tone: u8) -> char {
+	TONED_VOWELS.iter()
+		.find(|(plain, _)| *plain == vowel)
+		.map_or(vowel, |(_, toned)| toned[tone as usize - 1])
+}
+
+/// Converts space separated pinyin with tone numbers, e.g. `"ni3 hao3"`, to pinyin with tone marks, e.g. `"nǐ hǎo"`.
+pub(crate) fn numbers_to_marks(pinyin_numbers: &str) -> String {
+	pinyin_numbers.split(' ').map(|syllable| {
+		match split_tone_number(syllable) {
+			(letters, Some(tone)) => place_tone_mark(letters, tone),
+			(letters, None) => letters.to_string(),
+		}
+	}).collect::<Vec<String>>().join(" ")
+}
+
+/// Strips the tone from a syllable and folds it to the spelling used by `SYLLABLES`:
+/// lowercase, with `v` and `u:` written as `ü`.
+fn plain_syllable(syllable: &str) -> String {
+	let (letters, _) = split_tone_number(syllable.trim());
+	// Tone marks are removed first, so that the legacy spelling `lù:e` also folds to `lüe`
+	letters.to_lowercase()
+		.chars()
+		.map(|c| TONED_VOWELS.iter().find(|(_, toned)| toned.contains(&c)).map_or(c, |(plain, _)| *plain))
+		.collect::<String>()
+		.replace("u:", "ü")
+		.replace('v', "ü")
+}
+
+/// Whether a syllable with a tone mark, such as `"lǜ"` or the legacy `"lù:"`, is the same as a syllable with a tone number, such as `"lu:4"`.
+pub(crate) fn same_syllable(marked: &str, numbered: &str) -> bool {
+	plain_syllable(marked) == plain_syllable(numbered) && syllable_tone(marked) == split_tone_number(numbered).1.unwrap_or(5)
+}
+
+/// # Umlaut Style
+/// How `normalize_umlaut` writes the vowel ü.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UmlautStyle {
+	/// `ü`, as in `"nü3"` and `"nǚ"`
+	Umlaut,
+	/// `v`, as typed on a keyboard, as in `"nv3"`. Since `v` cannot carry a tone mark, an ü with a tone mark is kept as it is.
+	V,
+	/// `u:`, as in CC-CEDICT, as in `"nu:3"` and `"nǔ:"`
+	UColon,
+}
+
+/// # Normalize Umlaut
+/// Write every ü in a pinyin string, however it was typed, in one style, e.g. `"lv4 nu:3 nǚ"` becomes `"lü4 nü3 nǚ"` with `UmlautStyle::Umlaut`.
+/// `v`, `ü`, and `u:` are all read as ü, in any case and with or without a tone mark.
+pub fn normalize_umlaut(pinyin: &str, style: UmlautStyle) -> String {
+	let mut output = String::with_capacity(pinyin.len());
+	let mut characters = pinyin.chars().peekable();
+	while let Some(c) = characters.next() {
+		// Each ü is read as its case and tone, with tone 0 for none
+		let umlaut = match c {
+			'v' | 'ü' => Some((false, 0)),
+			'V' | 'Ü' => Some((true, 0)),
+			'u' | 'U' | 'ū' | 'ú' | 'ǔ' | 'ù' | 'Ū' | 'Ú' | 'Ǔ' | 'Ù' if characters.peek() == Some(&':') => {
+				characters.next();
+				let (plain, tone) = untoned_vowel(c);
+				Some((plain == 'U', tone))
+			},
+			_ => match untoned_vowel(c) {
+				(plain @ ('ü' | 'Ü'), tone) => Some((plain == 'Ü', tone)),
+				_ => None,
+			},
+		};
+		let Some((uppercase, tone)) = umlaut else {
+			output.push(c);
+			continue;
+		};
+		let (u, umlaut) = if uppercase { ('U', 'Ü') } else { ('u', 'ü') };
+		match (style, tone) {
+			(UmlautStyle::UColon, tone) => {
+				output.push(if tone == 0 { u } else { toned_vowel(u, tone) });
+				output.push(':');
+			},
+			(UmlautStyle::V, 0) => output.push(if uppercase { 'V' } else { 'v' }),
+			(_, 0) => output.push(umlaut),
+			(_, tone) => output.push(toned_vowel(umlaut, tone)),
+		}
+	}
+
+	output
+}
+
+/// Splits a vowel with a tone mark into the plain vowel and its tone, e.g. `'ǜ'` into `('ü', 4)`, with tone 0 for any other character.
+fn untoned_vowel(c: char) -> (char, u8) {
+	TONED_VOWELS.iter()
+		.find_map(|(plain, toned)| toned.iter().position(|t| *t == c).map(|tone| (*plain, tone as u8 + 1)))
+		.unwrap_or((c, 0))
+}
+
+/// # Neutral Tone
+/// How `render_neutral_tone` writes the neutral tone in pinyin with tone numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NeutralTone {
+	/// Tone 5, as in CC-CEDICT, e.g. `"ma5"`
+	Five,
+	/// Tone 0, e.g. `"ma0"`
+	Zero,
+	/// No tone number, e.g. `"ma"`
+	Unnumbered,
+}
+
+/// # Render Neutral Tone
+/// Write the neutral tone syllables of pinyin with tone numbers, such as `WordEntry::pinyin_numbers`, in one convention,
+/// e.g. `"xie4 xie5"` becomes `"xie4 xie0"` with `NeutralTone::Zero` and `"xie4 xie"` with `NeutralTone::Unnumbered`.
+/// The input may use any of the three conventions, though a syllable without a number is only read as neutral when it is separated by a space.
+pub fn render_neutral_tone(pinyin_numbers: &str, style: NeutralTone) -> String {
+	pinyin_numbers.split(' ').map(|syllable| {
+		let letters = match split_tone_number(syllable.strip_suffix('0').unwrap_or(syllable)) {
+			(letters, Some(5) | None) if letters.chars().any(char::is_alphabetic) => letters,
+			_ => return syllable.to_string(),
+		};
+		match style {
+			NeutralTone::Five => format!("{}5", letters),
+			NeutralTone::Zero => format!("{}0", letters),
+			NeutralTone::Unnumbered => letters.to_string(),
+		}
+	}).collect::<Vec<String>>().join(" ")
+}
+
+/// Writes the neutral tone of a pinyin query word with tone numbers as tone 5, the way the index does.
+/// Tone 0 becomes tone 5, and when the word has tone numbers but its last syllable does not, as in `"xie4xie"`, the last syllable is given tone 5.
+/// Words without any tone numbers are left alone, since they match every tone.
+pub(crate) fn normalize_neutral_tone(word: &str) -> String {
+	let mut output = String::with_capacity(word.len() + 1);
+	let mut previous: Option<char> = None;
+	for c in word.chars() {
+		match (previous, c) {
+			(Some(letter), '0') if letter.is_alphabetic() || letter == ':' => output.push('5'),
+			_ => output.push(c),
+		}
+		previous = Some(c);
+	}
+	let numbered = output.chars().any(|c| ('1'..='5').contains(&c));
+	if numbered && previous.is_some_and(|last| last.is_alphabetic() || last == ':') {
+		output.push('5');
+	}
+
+	output
+}
+
+/// The initial, final, and tone of a pinyin syllable with a tone number, such as `("g", "uang", Some(1))` for `guang1`, in lowercase with `ü` for `u:` and `v`.
+/// Syllables without an initial are spelled out, so `wang2` has the final `uang`, `you3` has `iu`, and `yu2` has `ü` like `ju2`.
+pub(crate) fn split_syllable(syllable: &str) -> (String, String, Option<u8>) {
+	let (letters, tone) = split_tone_number(syllable);
+	let letters = letters.to_lowercase().replace("u:", "ü").replace('v', "ü");
+	let initial_length = match letters.get(..2) {
+		Some("zh") | Some("ch") | Some("sh") => 2,
+		_ if letters.starts_with(|c: char| "bpmfdtnlgkhjqxrzcs".contains(c)) => 1,
+		_ => 0,
+	};
+	let (initial, rest) = letters.split_at(initial_length);
+	let fin = match (initial, rest) {
+		("", "yi" | "yin" | "ying") => rest[1..].to_string(),
+		("", "yu" | "yue" | "yuan" | "yun") => format!("ü{}", &rest[2..]),
+		("", "wu") => String::from("u"),
+		("", "you") => String::from("iu"),
+		("", "wei") => String::from("ui"),
+		("", "wen") => String::from("un"),
+		("", _) if rest.starts_with('y') => format!("i{}", &rest[1..]),
+		("", _) if rest.starts_with('w') => format!("u{}", &rest[1..]),
+		("j" | "q" | "x", _) if rest.starts_with('u') => format!("ü{}", &rest[1..]),
+		_ => rest.to_string(),
+	};
+
+	(initial.to_string(), fin, tone)
+}
+
+/// # Format Pinyin Words
+/// Write the pinyin of a sequence of words following the official orthography (汉语拼音正词法), e.g. the pinyin of each word of the output of `segment`.
+/// Each word is given as its space separated syllables, with tone numbers or tone marks, such as `WordEntry::pinyin_numbers`.
+///
+/// * The syllables of a word are joined together, and words are separated by spaces: `["wo3", "xi3 huan5"]` becomes `"wǒ xǐhuan"`.
+/// * Syllables starting with `a`, `o`, or `e` are separated from the syllable before them with an apostrophe: `"Xi1 an1"` becomes `"Xī'ān"`.
+/// * Capitalized syllables mark proper nouns, as in CC-CEDICT, and are kept capitalized. A capitalized syllable within a word starts
+///   a new part of the name, so `"Mao2 Ze2 dong1"` becomes `"Máo Zédōng"`.
+pub fn format_pinyin_words(words: &[&str]) -> String {
+	let mut parts: Vec<String> = Vec::new();
+	for word in words {
+		let mut part = String::new();
+		for syllable in word.split_whitespace() {
+			let syllable = syllable.replace("u:", "ü").replace("U:", "Ü");
+			let marked = match split_tone_number(&syllable) {
+				(letters, Some(tone)) => add_tone_mark(letters, tone),
+				(letters, None) => letters.to_string(),
+			};
+			if !part.is_empty() && marked.starts_with(char::is_uppercase) {
+				parts.push(std::mem::take(&mut part));
+			}
+			if !part.is_empty() && plain_syllable(&marked).starts_with(['a', 'o', 'e']) {
+				part.push('\'');
+			}
+			part.push_str(&marked);
+		}
+		if !part.is_empty() {
+			parts.push(part);
+		}
+	}
+
+	parts.join(" ")
+}
+
+/// # Tone Spans
+/// The byte range and tone of each syllable of space separated pinyin with tone marks, such as `WordEntry::pinyin_marks`,
+/// for coloring each syllable by its tone. `"nǐ hǎo"` gives `[(0..3, 3), (4..8, 3)]`.
+/// Syllables without a tone mark have the neutral tone `5`, and anything without a letter, such as the `·` in names, is skipped.
+pub fn tone_spans(pinyin_marks: &str) -> Vec<(Range<usize>, u8)> {
+	let mut spans: Vec<(Range<usize>, u8)> = Vec::new();
+	let mut start: Option<usize> = None;
+	for (index, character) in pinyin_marks.char_indices().chain(std::iter::once((pinyin_marks.len(), ' '))) {
+		match (start, character.is_whitespace()) {
+			(None, false) => start = Some(index),
+			(Some(syllable_start), true) => {
+				let syllable = &pinyin_marks[syllable_start..index];
+				if syllable.chars().any(char::is_alphabetic) {
+					spans.push((syllable_start..index, syllable_tone(syllable)));
+				}
+				start = None;
+			},
+			_ => {},
+		}
+	}
+
+	spans
+}
+
+/// The tone of a syllable written with a tone mark, or `5` when it has none.
+fn syllable_tone(syllable: &str) -> u8 {
+	syllable.chars()
+		.find_map(|c| TONED_VOWELS.iter().find_map(|(_, toned)| toned.iter().position(|toned| *toned == c)))
+		.map_or(5, |position| position as u8 + 1)
+}
+
+/// # Is Valid Pinyin Syllable
+/// Check if a single syllable is a syllable of Standard Mandarin, e.g. `"xiang"`.
+/// The syllable may be written with a tone number or tone mark, in any case, and with `v` or `u:` in place of `ü`.
+pub fn is_valid_pinyin_syllable(syllable: &str) -> bool {
+	SYLLABLES.binary_search(&plain_syllable(syllable).as_str()).is_ok()
+}
+
+/// Whether a word can be read as a run of pinyin syllables, each with an optional tone number or tone mark, such as `"ni3hao3"`, `"xièxie"`, or `"xi'an"`.
+pub(crate) fn is_pinyin_word(word: &str) -> bool {
+	let word = normalize_neutral_tone(&normalize_umlaut(word, UmlautStyle::Umlaut));
+	word.split('\'').all(|part| {
+		let characters: Vec<char> = part.chars().collect();
+		let mut reachable = vec![false; characters.len() + 1];
+		reachable[0] = true;
+		for start in 0..characters.len() {
+			if !reachable[start] {
+				continue;
+			}
+			// The longest syllables, such as `zhuang` or `shuang`, have six letters and a tone number
+			for end in start + 1..=(start + 7).min(characters.len()) {
+				if is_valid_pinyin_syllable(&characters[start..end].iter().collect::<String>()) {
+					reachable[end] = true;
+				}
+			}
+		}
+		!characters.is_empty() && reachable[characters.len()]
+	})
+}
+
+/// Whether a word is written with a tone number after a syllable, such as `"ma5"`, or with a tone mark, such as `"mā"`.
+pub(crate) fn has_tones(word: &str) -> bool {
+	let mut previous: Option<char> = None;
+	word.chars().any(|c| {
+		let toned = TONED_VOWELS.iter().any(|(_, toned)| toned.contains(&c))
+			|| (('0'..='5').contains(&c) && previous.is_some_and(|letter| letter.is_alphabetic() || letter == ':'));
+		previous = Some(c);
+		toned
+	})
+}
+
+/// # Suggest Syllables
+/// Suggest corrections for a mistyped pinyin syllable, e.g. `"shang"` for `"shnag"`.
+/// Suggestions are the syllables one insertion, deletion, substitution, or transposition away, or two edits away if there are none closer.
+/// They are returned without tones, in alphabetical order. A valid syllable is returned as its only suggestion.
+pub fn suggest_syllables(syllable: &str) -> Vec<&'static str> {
+	let plain: Vec<char> = plain_syllable(syllable).chars().collect();
+	let distances: Vec<(usize, &'static str)> = SYLLABLES.iter()
+		.map(|candidate| (edit_distance(&plain, &candidate.chars().collect::<Vec<char>>()), *candidate))
+		.collect();
+
+	match distances.iter().map(|(distance, _)| *distance).min() {
+		Some(closest) if closest <= 2 => distances.into_iter().filter(|(distance, _)| *distance == closest).map(|(_, candidate)| candidate).collect(),
+		_ => Vec::new(),
+	}
+}
+
+/// The optimal string alignment distance between two strings: the number of insertions, deletions,
+/// substitutions, and transpositions of adjacent characters needed to turn one into the other.
+pub(crate) fn edit_distance(a: &[char], b: &[char]) -> usize {
+	let mut rows: Vec<Vec<usize>> = (0..=a.len()).map(|i| {
+		(0..=b.len()).map(|j| if i == 0 { j } else if j == 0 { i } else { 0 }).collect()
+	}).collect();
+
+	for i in 1..=a.len() {
+		for j in 1..=b.len() {
+			let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+			rows[i][j] = (rows[i - 1][j] + 1).min(rows[i][j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+			if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+				rows[i][j] = rows[i][j].min(rows[i - 2][j - 2] + 1);
+			}
+		}
+	}
+
+	rows[a.len()][b.len()]
+}
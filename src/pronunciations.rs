@@ -0,0 +1,25 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Linking of entries written the same way with different pronunciations
+
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+
+impl Dictionary {
+	/// # Pronunciation Variants
+	/// The other readings of an entry: the entries with the same simplified or traditional headword and a different pronunciation,
+	/// e.g. the háng entries of 行 for a xíng entry, so an app can show every reading of a word on one card.
+	/// Pinyin that only differs in case, such as the surname Xíng, is the same reading. Entries are in the order of their `word_id`.
+	pub fn pronunciation_variants(&self, entry: &WordEntry) -> Vec<&WordEntry> {
+		let mut variants: Vec<&WordEntry> = self.entries_for_headword(&entry.simplified);
+		variants.extend(self.entries_for_headword(&entry.traditional));
+		variants.sort_by_key(|variant| variant.word_id);
+		variants.dedup_by_key(|variant| variant.word_id);
+
+		let pinyin = entry.pinyin_numbers.to_lowercase();
+		variants.retain(|variant| {
+			(variant.simplified == entry.simplified || variant.traditional == entry.traditional) && variant.pinyin_numbers.to_lowercase() != pinyin
+		});
+
+		variants
+	}
+}
@@ -0,0 +1,146 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Conversion between Chinese and Western punctuation
+
+/// A convention for writing punctuation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Style {
+	/// Full width Chinese punctuation, such as ，。？“”
+	Chinese,
+	/// ASCII punctuation, such as , . ? ""
+	Western,
+}
+
+/// Western punctuation and the Chinese punctuation it corresponds to. Quotation marks are handled separately, since their direction depends on context.
+static PAIRS: [(char, char); 9] = [(',', '，'), ('.', '。'), ('?', '？'), ('!', '！'), (':', '：'), (';', '；'), ('(', '（'), (')', '）'), ('~', '～')];
+
+/// Whether a character is a Chinese character.
+pub(crate) fn is_han(character: char) -> bool {
+	matches!(character, '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' | '\u{F900}'..='\u{FAFF}' | '\u{20000}'..='\u{2FA1F}')
+}
+
+/// Whether punctuation after a character should be Chinese: after Chinese characters and Chinese punctuation.
+fn is_chinese_context(character: char) -> bool {
+	is_han(character) || is_chinese_punctuation(character)
+}
+
+fn is_chinese_punctuation(character: char) -> bool {
+	PAIRS.iter().any(|(_, chinese)| *chinese == character) || "“”‘’、…《》「」".contains(character)
+}
+
+/// # Normalize Punctuation
+/// Convert the punctuation of a string of text to a single `Style`.
+///
+/// With `Style::Chinese`, Western punctuation next to Chinese characters is converted to its full width form, and the spaces after it are removed,
+/// so `你好, 世界.` becomes `你好，世界。`. Punctuation between Latin letters or digits, such as in `Hello, world` or `3.14`, is left alone.
+/// Straight quotes become curly quotes, alternating between opening and closing, and `...` becomes `……`.
+///
+/// With `Style::Western`, full width punctuation is converted to ASCII, curly quotes and corner brackets become straight quotes, and `……` becomes `...`.
+/// The enumeration comma `、` becomes `,`, and book title marks like `《》` are kept.
+pub fn normalize_punctuation(text: &str, style: Style) -> String {
+	match style {
+		Style::Chinese => to_chinese(text),
+		Style::Western => to_western(text),
+	}
+}
+
+fn to_chinese(text: &str) -> String {
+	let characters: Vec<char> = text.chars().collect();
+	let mut result = String::with_capacity(text.len());
+	let mut double_open = false;
+	let mut single_open = false;
+	let mut index = 0;
+
+	while index < characters.len() {
+		let character = characters[index];
+		// The previous character is taken from the converted text, so punctuation after converted punctuation, as in !", is converted too
+		let previous = result.chars().rev().find(|c| !c.is_whitespace());
+		let next = characters[index + 1..].iter().find(|c| !c.is_whitespace()).copied();
+		let beside_han = previous.is_some_and(is_chinese_context) || next.is_some_and(is_han);
+
+		if characters[index..].starts_with(&['.', '.', '.']) && previous.is_some_and(is_chinese_context) {
+			result.push_str("……");
+			index += 3;
+			index += skip_space(&characters[index..]);
+			continue;
+		}
+
+		let converted = match character {
+			'"' if beside_han => {
+				double_open = !double_open;
+				Some(if double_open { '“' } else { '”' })
+			},
+			// An apostrophe between letters, as in don't, is not a quotation mark
+			'\'' if beside_han && !(index > 0 && characters[index - 1].is_alphabetic() && characters.get(index + 1).is_some_and(|c| c.is_alphabetic())) => {
+				single_open = !single_open;
+				Some(if single_open { '‘' } else { '’' })
+			},
+			'(' if next.is_some_and(is_han) => Some('（'),
+			// A full stop is only converted after Chinese, so decimals and abbreviations before Chinese text are kept
+			'.' if previous.is_some_and(is_chinese_context) => Some('。'),
+			_ if character != '(' && character != '.' && beside_han => PAIRS.iter().find(|(western, _)| *western == character).map(|(_, chinese)| *chinese),
+			_ => None,
+		};
+
+		match converted {
+			Some(chinese) => {
+				result.push(chinese);
+				index += 1;
+				// Chinese punctuation is full width, so the space after it is dropped, except before an opening quote's text
+				if !matches!(chinese, '“' | '‘' | '（') {
+					index += skip_space(&characters[index..]);
+				}
+			},
+			None => {
+				result.push(character);
+				index += 1;
+			},
+		}
+	}
+
+	result
+}
+
+/// The number of spaces to drop after converted punctuation, since full width punctuation already includes its own spacing.
+fn skip_space(rest: &[char]) -> usize {
+	rest.iter().take_while(|c| **c == ' ' || **c == '\t').count()
+}
+
+fn to_western(text: &str) -> String {
+	let mut result = String::with_capacity(text.len());
+	let mut characters = text.chars().peekable();
+	while let Some(character) = characters.next() {
+		match character {
+			'…' => {
+				if characters.peek() == Some(&'…') {
+					characters.next();
+				}
+				result.push_str("...");
+			},
+			'“' | '”' | '「' | '」' | '＂' => result.push('"'),
+			'‘' | '’' | '『' | '』' | '＇' => result.push('\''),
+			'、' => result.push(','),
+			'．' | '｡' => result.push('.'),
+			_ => match PAIRS.iter().find(|(_, chinese)| *chinese == character) {
+				Some((western, _)) => result.push(*western),
+				None => result.push(character),
+			},
+		}
+	}
+
+	result
+}
+
+/// # Has Mixed Punctuation
+/// Whether a string of text uses both Chinese punctuation and Western punctuation next to Chinese characters, such as `你好，世界.`
+/// Western punctuation inside Latin text, like an English quote within a Chinese article, does not count.
+pub fn has_mixed_punctuation(text: &str) -> bool {
+	let characters: Vec<char> = text.chars().collect();
+	let chinese = characters.iter().any(|character| is_chinese_punctuation(*character));
+	let western = characters.iter().enumerate().any(|(index, character)| {
+		(PAIRS.iter().any(|(western, _)| western == character) || *character == '"')
+			&& index > 0 && is_han(characters[index - 1])
+	});
+
+	chinese && western
+}
@@ -0,0 +1,126 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Parsing of operators such as `py:` and `hsk:<=3` in queries
+
+/// A comparison against an entry's HSK level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Comparison {
+	Less,
+	LessOrEqual,
+	Equal,
+	GreaterOrEqual,
+	Greater,
+}
+
+impl Comparison {
+	pub(crate) fn matches(self, value: u8, level: u8) -> bool {
+		match self {
+			Comparison::Less => value < level,
+			Comparison::LessOrEqual => value <= level,
+			Comparison::Equal => value == level,
+			Comparison::GreaterOrEqual => value >= level,
+			Comparison::Greater => value > level,
+		}
+	}
+}
+
+/// A single part of a query written with operators.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Clause {
+	/// `py:shi4`
+	Pinyin(String),
+	/// `en:run`
+	English(String),
+	/// `zh:行`
+	Chinese(String),
+	/// `hsk:<=3`
+	Hsk(Comparison, u8),
+	/// Text without an operator, which is classified like an ordinary query
+	Text(String),
+}
+
+/// Parses a query written with operators into its clauses. The value of `py:`, `en:`, and `zh:` runs until the next operator,
+/// so `en:to eat hsk:1` searches for "to eat". Returns `None` when the query contains no operators or an invalid `hsk:` value.
+pub(crate) fn parse(query: &str) -> Option<Vec<Clause>> {
+	let mut clauses: Vec<Clause> = Vec::new();
+	let mut current: Option<(&str, Vec<&str>)> = None;
+	let mut has_operator = false;
+
+	for word in query.split(' ').filter(|word| !word.is_empty()) {
+		let operator = ["py:", "en:", "zh:", "hsk:"].iter().find(|operator| word.starts_with(*operator));
+		match operator {
+			Some(operator) => {
+				has_operator = true;
+				if let Some((previous, words)) = current.take() {
+					clauses.push(clause(previous, &words.join(" "))?);
+				}
+				let value = &word[operator.len()..];
+				current = Some((operator, if value.is_empty() { Vec::new() } else { vec![value] }));
+			},
+			None => match current.as_mut() {
+				Some((_, words)) => words.push(word),
+				None => current = Some(("", vec![word])),
+			},
+		}
+	}
+	if let Some((previous, words)) = current {
+		clauses.push(clause(previous, &words.join(" "))?);
+	}
+
+	if has_operator { Some(clauses) } else { None }
+}
+
+fn clause(operator: &str, value: &str) -> Option<Clause> {
+	match operator {
+		"py:" => Some(Clause::Pinyin(value.to_string())),
+		"en:" => Some(Clause::English(value.to_string())),
+		"zh:" => Some(Clause::Chinese(value.to_string())),
+		"hsk:" => {
+			let (comparison, level) = [("<=", Comparison::LessOrEqual), (">=", Comparison::GreaterOrEqual), ("<", Comparison::Less), (">", Comparison::Greater), ("=", Comparison::Equal)].iter()
+				.find_map(|(symbol, comparison)| value.strip_prefix(symbol).map(|level| (*comparison, level)))
+				.unwrap_or((Comparison::Equal, value));
+			level.trim().parse().ok().map(|level| Clause::Hsk(comparison, level))
+		},
+		_ => Some(Clause::Text(value.to_string())),
+	}
+}
+
+/// The boolean operators of English queries.
+pub(crate) const BOOLEAN_OPERATORS: [&str; 3] = ["AND", "OR", "NOT"];
+
+/// Checks if a query contains any of the boolean operators as a word of its own.
+pub(crate) fn has_boolean_operator(query: &str) -> bool {
+	query.split(' ').any(|word| BOOLEAN_OPERATORS.contains(&word))
+}
+
+/// Parses an English query written with the boolean operators `AND`, `OR`, and `NOT`, which must be uppercase, into the terms
+/// of each alternative, with `true` for the terms negated by `NOT`. `AND` binds tighter than `OR`, so `a AND b OR c` is `(a AND b) OR c`.
+/// Returns `None` when the query contains no operators.
+pub(crate) fn parse_boolean(query: &str) -> Option<Vec<Vec<(bool, String)>>> {
+	if !has_boolean_operator(query) {
+		return None;
+	}
+
+	let mut alternatives: Vec<Vec<(bool, String)>> = Vec::new();
+	let mut terms: Vec<(bool, String)> = Vec::new();
+	let mut term: Vec<&str> = Vec::new();
+	let mut negated = false;
+	for word in query.split(' ').filter(|word| !word.is_empty()).chain(std::iter::once("OR")) {
+		if !BOOLEAN_OPERATORS.contains(&word) {
+			term.push(word);
+			continue;
+		}
+		if !term.is_empty() {
+			terms.push((negated, term.join(" ")));
+			term.clear();
+			negated = false;
+		}
+		match word {
+			"NOT" => negated = true,
+			"OR" if !terms.is_empty() => alternatives.push(std::mem::take(&mut terms)),
+			_ => {},
+		}
+	}
+
+	Some(alternatives)
+}
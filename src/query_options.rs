@@ -0,0 +1,18 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Options controlling how queries are interpreted
+
+use crate::shuangpin::ShuangpinScheme;
+use std::ops::RangeInclusive;
+
+/// # Query Options
+/// Options controlling how `query` and `query_by_pinyin` interpret their input, set with `Dictionary::set_query_options`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryOptions {
+	/// Read pinyin input as double pinyin (shuangpin) typed in this scheme, expanding it to full pinyin before lookup.
+	/// Input that does not expand to a known word is looked up as it was typed.
+	pub pinyin_scheme: Option<ShuangpinScheme>,
+	/// Only return entries whose `year` is within this range, such as `Some(2020..=2021)`, leaving out every entry without a year.
+	/// Years are only known for the entries tagged by the `neologisms` feature.
+	pub years: Option<RangeInclusive<u16>>,
+}
@@ -0,0 +1,85 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Distractors for multiple choice quizzes
+
+use crate::builder::english_key;
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+/// Words too common in glosses to say that two entries are related.
+static GLOSS_STOPWORDS: [&str; 16] = ["a", "an", "the", "to", "of", "and", "or", "in", "on", "for", "with", "sb", "sth", "one", "be", "as"];
+
+impl Dictionary {
+	/// # Distractors
+	/// Choose `n` plausible wrong answers for a multiple choice question about `entry`, such as "which word means watermelon?".
+	/// Candidates score higher for having the same number of characters, sharing a character, sounding alike (the same pinyin apart from tones,
+	/// or the same number of syllables), being at the same HSK level, and having glosses that share a word with those of `entry`.
+	///
+	/// Entries that could be a correct answer are never chosen: those with the same headword, and those with a gloss containing a whole gloss of `entry`,
+	/// such as "fellow student" for 学生. Proper nouns and variant entries are only chosen for proper nouns and variants.
+	/// Ties are broken by frequency and then `word_id`, so the result is deterministic.
+	pub fn distractors(&self, entry: &WordEntry, n: usize) -> Vec<&WordEntry> {
+		let glosses: Vec<String> = entry.english.iter().map(|gloss| format!("%20{}%20", english_key(gloss))).collect();
+		let keywords: HashSet<&str> = entry.english.iter()
+			.flat_map(|gloss| gloss_words(gloss))
+			.collect();
+		let characters: HashSet<char> = entry.simplified.chars().chain(entry.traditional.chars()).collect();
+		let toneless = toneless_pinyin(entry);
+		let length = entry.simplified.chars().count();
+		let syllables = entry.pinyin_numbers.split(' ').count();
+		let proper_noun = is_proper_noun(entry);
+		let variant = is_variant(entry);
+
+		let mut scored: Vec<(u32, &WordEntry)> = self.entries()
+			.filter(|candidate| candidate.simplified != entry.simplified && candidate.traditional != entry.traditional)
+			.filter(|candidate| is_proper_noun(candidate) == proper_noun && is_variant(candidate) == variant)
+			.filter(|candidate| !candidate.english.iter().any(|gloss| {
+				let gloss = format!("%20{}%20", english_key(gloss));
+				glosses.iter().any(|entry_gloss| gloss.contains(entry_gloss.as_str()))
+			}))
+			.map(|candidate| {
+				let mut score = 0;
+				if candidate.simplified.chars().count() == length {
+					score += 2;
+				}
+				if candidate.simplified.chars().chain(candidate.traditional.chars()).any(|c| characters.contains(&c)) {
+					score += 3;
+				}
+				if toneless_pinyin(candidate) == toneless {
+					score += 3;
+				} else if candidate.pinyin_numbers.split(' ').count() == syllables {
+					score += 1;
+				}
+				if entry.hsk > 0 && candidate.hsk == entry.hsk {
+					score += 2;
+				}
+				if candidate.english.iter().flat_map(|gloss| gloss_words(gloss)).any(|word| keywords.contains(word)) {
+					score += 2;
+				}
+				(score, candidate)
+			})
+			.collect();
+		scored.sort_by_key(|(score, candidate)| (Reverse(*score), Reverse(self.frequency(&candidate.simplified)), candidate.word_id));
+
+		scored.into_iter().take(n).map(|(_, candidate)| candidate).collect()
+	}
+}
+
+/// The lowercase words of a gloss, without stopwords or parenthetical notes.
+fn gloss_words(gloss: &str) -> impl Iterator<Item = &str> {
+	gloss.split(|c: char| !c.is_alphabetic())
+		.filter(|word| word.len() > 1 && !GLOSS_STOPWORDS.iter().any(|stopword| word.eq_ignore_ascii_case(stopword)))
+}
+
+fn toneless_pinyin(entry: &WordEntry) -> String {
+	entry.pinyin_numbers.to_lowercase().chars().filter(|c| !c.is_ascii_digit() && *c != ' ').collect()
+}
+
+fn is_proper_noun(entry: &WordEntry) -> bool {
+	entry.pinyin_numbers.starts_with(char::is_uppercase)
+}
+
+fn is_variant(entry: &WordEntry) -> bool {
+	entry.english.iter().any(|gloss| gloss.starts_with("variant of") || gloss.starts_with("old variant of"))
+}
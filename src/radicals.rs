@@ -0,0 +1,79 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: The 214 Kangxi radicals and a radical index of the dictionary's characters
+
+use crate::chinese_dictionary::Dictionary;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The 214 Kangxi radicals in order, written as the ideographs they normalize to.
+static RADICALS: &str = "一丨丶丿乙亅二亠人儿入八冂冖冫几凵刀力勹匕匚匸十卜卩厂厶又口囗土士夂夊夕\
+	大女子宀寸小尢尸屮山巛工己巾干幺广廴廾弋弓彐彡彳心戈戶手支攴文斗斤方无日\
+	曰月木欠止歹殳毋比毛氏气水火爪父爻爿片牙牛犬玄玉瓜瓦甘生用田疋疒癶白皮皿\
+	目矛矢石示禸禾穴立竹米糸缶网羊羽老而耒耳聿肉臣自至臼舌舛舟艮色艸虍虫血行\
+	衣襾見角言谷豆豕豸貝赤走足身車辛辰辵邑酉釆里金長門阜隶隹雨靑非面革韋韭音\
+	頁風飛食首香馬骨高髟鬥鬯鬲鬼魚鳥鹵鹿麥麻黃黍黑黹黽鼎鼓鼠鼻齊齒龍龜龠";
+
+/// The number of the last radical with each stroke count, from one stroke to seventeen.
+static LAST_RADICAL_BY_STROKES: [u8; 17] = [6, 29, 60, 94, 117, 146, 166, 175, 186, 194, 200, 204, 208, 210, 211, 213, 214];
+
+/// The last character of the CJK Unified Ideographs block that is sorted by radical. Characters added to the block later are not.
+const LAST_SORTED_IDEOGRAPH: char = '\u{9FA5}';
+
+/// One of the 214 Kangxi radicals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Radical {
+	/// The number of the radical, from `1` for 一 to `214` for 龠
+	pub number: u8,
+	pub character: char,
+	/// The number of strokes in the radical
+	pub strokes: u8,
+}
+
+/// The characters of a radical index page, as returned by `Dictionary::radical_index`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RadicalGroup {
+	pub radical: Radical,
+	/// The characters with this radical, ordered by their number of strokes outside the radical
+	pub characters: Vec<char>,
+}
+
+/// # Radicals
+/// Iterate over the 214 Kangxi radicals in order.
+pub fn radicals() -> impl Iterator<Item = Radical> {
+	RADICALS.chars().zip(1..).map(|(character, number)| Radical {
+		number,
+		character,
+		strokes: LAST_RADICAL_BY_STROKES.iter().position(|last| number <= *last).map_or(17, |position| position as u8 + 1),
+	})
+}
+
+/// # Radical Of
+/// The Kangxi radical a character is indexed under, such as 人 for 你 or 言 for 語.
+/// The radical is read from the position of the character in the CJK Unified Ideographs block, which Unicode arranges by radical and then by stroke count,
+/// so only characters from U+4E00 to U+9FA5 are supported. Simplified characters are indexed under the traditional form of their radical, such as 言 for 语.
+pub fn radical_of(character: char) -> Option<Radical> {
+	if !('\u{4E00}'..=LAST_SORTED_IDEOGRAPH).contains(&character) {
+		return None;
+	}
+	radicals().take_while(|radical| radical.character <= character).last()
+}
+
+impl Dictionary {
+	/// # Radical Index
+	/// Group every single character headword in the dictionary by its radical, like the radical index of a paper dictionary.
+	/// Groups are in radical order, and characters within a group are in order of their number of strokes outside the radical.
+	/// Radicals without any characters are skipped. Characters `radical_of` does not support are left out.
+	pub fn radical_index(&self) -> impl Iterator<Item = RadicalGroup> {
+		let mut groups: BTreeMap<u8, (Radical, BTreeSet<char>)> = BTreeMap::new();
+		for key in self.simplified_keys().chain(self.traditional_keys()) {
+			let mut characters = key.chars();
+			if let (Some(character), None) = (characters.next(), characters.next()) {
+				if let Some(radical) = radical_of(character) {
+					groups.entry(radical.number).or_insert_with(|| (radical, BTreeSet::new())).1.insert(character);
+				}
+			}
+		}
+
+		groups.into_values().map(|(radical, characters)| RadicalGroup { radical, characters: characters.into_iter().collect() })
+	}
+}
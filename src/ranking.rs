@@ -0,0 +1,44 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Caller provided ranking of query results
+
+use crate::chinese_dictionary::WordEntry;
+use std::sync::Arc;
+
+/// # Query Context
+/// Details about the query being ranked, passed to the closure given to `Dictionary::set_ranker`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryContext<'a> {
+	/// The query as it was passed to `query`
+	pub raw: &'a str,
+	/// The query after normalization
+	pub query: &'a str,
+	/// The position of the entry in the results before ranking, starting from 0
+	pub rank: usize,
+}
+
+/// A closure scoring how relevant an entry is to a query, where higher scores come first.
+pub(crate) type Ranker = Arc<dyn Fn(&WordEntry, &QueryContext) -> f32 + Send + Sync>;
+
+/// Sorts entries by their score from highest to lowest. Entries with equal scores keep their order,
+/// and scores that are not numbers are treated as negative infinity, so those entries come last.
+pub(crate) fn rank(ranker: &Ranker, entries: &mut [&WordEntry], raw: &str, query: &str) {
+	let mut scored: Vec<(f32, &WordEntry)> = entries.iter().enumerate()
+		.map(|(rank, entry)| (sort_key(ranker(entry, &QueryContext { raw, query, rank })), *entry))
+		.collect();
+	scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+	for (slot, (_, entry)) in entries.iter_mut().zip(scored) {
+		*slot = entry;
+	}
+}
+
+/// A score that `f32::total_cmp` orders numerically: NaN becomes negative infinity, and -0.0 becomes 0.0 so the two stay equal.
+fn sort_key(score: f32) -> f32 {
+	if score.is_nan() {
+		f32::NEG_INFINITY
+	} else if score == 0.0 {
+		0.0
+	} else {
+		score
+	}
+}
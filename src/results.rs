@@ -0,0 +1,75 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Compact encodings of query results for client/server deployments
+
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+use std::error::Error;
+use std::fmt;
+
+/// An encoding for `serialize_results`.
+/// JSON and MessagePack are only available with the `json` and `msgpack` features, which encode with `serde_json` and `rmp-serde`.
+/// Since any crate depending on this one can enable those features, a `match` on a format needs a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Format {
+	/// A JSON array of numbers, e.g. `[4561,880]`
+	#[cfg(feature = "json")]
+	Json,
+	/// A MessagePack array of unsigned integers
+	#[cfg(feature = "msgpack")]
+	MessagePack,
+	/// A bincode `Vec<u32>`
+	Bincode,
+}
+
+/// An error returned by `deserialize_results` when the bytes are not a valid encoding in the given `Format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeError {
+	pub format: Format,
+}
+
+impl fmt::Display for DecodeError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "malformed {:?} results", self.format)
+	}
+}
+
+impl Error for DecodeError {}
+
+/// # Serialize Results
+/// Encode query results as just their `word_id`s, in rank order, for sending to a client that has its own copy of the dictionary data.
+/// This is a fraction of the size of the full entries. The client passes the bytes to `Dictionary::resolve_results` to get the entries back,
+/// which requires both sides to use the same data release, since `word_id`s are not stable between releases.
+pub fn serialize_results(entries: &[&WordEntry], format: Format) -> Vec<u8> {
+	let ids: Vec<u32> = entries.iter().map(|entry| entry.word_id).collect();
+	match format {
+		#[cfg(feature = "json")]
+		Format::Json => serde_json::to_vec(&ids).unwrap(),
+		#[cfg(feature = "msgpack")]
+		Format::MessagePack => rmp_serde::to_vec(&ids).unwrap(),
+		Format::Bincode => bincode::serialize(&ids).unwrap(),
+	}
+}
+
+/// # Deserialize Results
+/// Decode the `word_id`s written by `serialize_results`, in rank order.
+pub fn deserialize_results(bytes: &[u8], format: Format) -> Result<Vec<u32>, DecodeError> {
+	let error = DecodeError { format };
+	match format {
+		#[cfg(feature = "json")]
+		Format::Json => serde_json::from_slice(bytes).map_err(|_| error),
+		#[cfg(feature = "msgpack")]
+		Format::MessagePack => rmp_serde::from_slice(bytes).map_err(|_| error),
+		Format::Bincode => bincode::deserialize(bytes).map_err(|_| error),
+	}
+}
+
+impl Dictionary {
+	/// # Resolve Results
+	/// Decode results written by `serialize_results` and look up their entries, in rank order.
+	/// Ids that are not in this dictionary are skipped.
+	pub fn resolve_results(&self, bytes: &[u8], format: Format) -> Result<Vec<&WordEntry>, DecodeError> {
+		let ids = deserialize_results(bytes, format)?;
+		Ok(ids.iter().filter_map(|id| self.entry_by_id(*id)).collect())
+	}
+}
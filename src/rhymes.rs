@@ -0,0 +1,53 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Lookup of words that rhyme by the final of their last syllable
+
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+use crate::pinyin::split_syllable;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+/// The final of a syllable with a tone number and its tone, such as `("uang", 1)` for `guang1`, spelled as `split_syllable` spells it.
+/// Syllables without a vowel, such as the `r5` of 儿化 or the interjection `hm`, have no final.
+pub(crate) fn syllable_final(syllable: &str) -> Option<(String, u8)> {
+	let (_, fin, tone) = split_syllable(syllable);
+	if !fin.contains(|c: char| "aeiouü".contains(c)) {
+		return None;
+	}
+
+	Some((fin, tone.unwrap_or(5)))
+}
+
+/// The rhyme index key of an entry: the final and tone of its last syllable with a final, such as `"uang1"` for 光,
+/// so the `r5` of 那儿 is skipped and it rhymes on `a`.
+pub(crate) fn rhyme_key(entry: &WordEntry) -> Option<String> {
+	entry.pinyin_numbers.split(' ').rev().find_map(syllable_final).map(|(fin, tone)| format!("{}{}", fin, tone))
+}
+
+impl Dictionary {
+	/// # Rhymes With
+	/// The words whose last syllable has the same final as the last syllable of `word`, written in simplified or traditional characters,
+	/// e.g. 双 and 黄 for 光, for songwriting and mnemonics. With `same_tone`, the tone of the syllables must match as well.
+	/// Words with more than one reading rhyme with any of them. The word itself is left out, and words are ordered from the most to the least frequent.
+	pub fn rhymes_with(&self, word: &str, same_tone: bool) -> Vec<&WordEntry> {
+		let mut keys: HashSet<String> = HashSet::new();
+		for key in self.entries_for_headword(word).iter().filter_map(|entry| rhyme_key(entry)) {
+			if same_tone {
+				keys.insert(key);
+			} else {
+				keys.extend((1..=5).map(|tone| format!("{}{}", key.trim_end_matches(|c: char| c.is_ascii_digit()), tone)));
+			}
+		}
+
+		let mut entries: Vec<&WordEntry> = keys.iter()
+			.filter_map(|key| self.rhymes().get(key))
+			.flatten()
+			.filter_map(|id| self.entry_by_id(*id))
+			.filter(|entry| entry.simplified != word && entry.traditional != word)
+			.collect();
+		entries.sort_by_key(|entry| (Reverse(self.frequency(&entry.simplified)), entry.word_id));
+		entries.dedup_by_key(|entry| entry.word_id);
+
+		entries
+	}
+}
@@ -0,0 +1,75 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Deterministic random sampling of entries
+
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+use std::ops::RangeInclusive;
+
+/// # Entry Filter
+/// Limits which entries `random_entry` and `sample_entries` may choose. The default filter allows every entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EntryFilter {
+	/// Only choose entries with an HSK level in this range, e.g. `Some(1..=3)`
+	pub hsk: Option<RangeInclusive<u8>>,
+	/// Only choose words with at least this frequency, as used by `SegmentationMode::Unigram`
+	pub min_frequency: Option<u32>,
+	/// Only choose entries whose simplified headword has a number of characters in this range
+	pub characters: Option<RangeInclusive<usize>>,
+}
+
+impl EntryFilter {
+	fn allows(&self, entry: &WordEntry, frequency: u32) -> bool {
+		self.hsk.as_ref().is_none_or(|hsk| hsk.contains(&entry.hsk))
+			&& self.min_frequency.is_none_or(|min_frequency| frequency >= min_frequency)
+			&& self.characters.as_ref().is_none_or(|characters| characters.contains(&entry.simplified.chars().count()))
+	}
+}
+
+/// The SplitMix64 generator, which gives the same sequence for a seed on every platform and Rust release.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+	fn next(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+		let mut z = self.0;
+		z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+		z ^ (z >> 31)
+	}
+
+	/// A number in `0..bound`.
+	fn below(&mut self, bound: usize) -> usize {
+		(self.next() % bound as u64) as usize
+	}
+}
+
+impl Dictionary {
+	/// # Random Entry
+	/// Choose an entry at random, such as for a word of the day, from the entries allowed by `filter`, or from every entry when it is `None`.
+	/// The choice is deterministic: the same `seed` picks the same entry for the same dictionary data, so a word of the day can be seeded with the date.
+	/// Returns `None` when no entry is allowed.
+	pub fn random_entry(&self, seed: u64, filter: Option<&EntryFilter>) -> Option<&WordEntry> {
+		self.sample_entries(1, seed, filter).into_iter().next()
+	}
+
+	/// # Sample Entries
+	/// Choose `n` different entries at random from the entries allowed by `filter`, such as for a quiz, or every allowed entry when there are fewer than `n`.
+	/// Like `random_entry`, the same `seed` always gives the same sample, in the same order, for the same dictionary data.
+	pub fn sample_entries(&self, n: usize, seed: u64, filter: Option<&EntryFilter>) -> Vec<&WordEntry> {
+		let mut candidates: Vec<&WordEntry> = self.entries()
+			.filter(|entry| filter.is_none_or(|filter| filter.allows(entry, self.frequency(&entry.simplified))))
+			.collect();
+		// Entries are stored in a hash map, so they are put in a fixed order before sampling
+		candidates.sort_by_key(|entry| entry.word_id);
+
+		let mut generator = SplitMix64(seed);
+		let n = n.min(candidates.len());
+		for index in 0..n {
+			let chosen = index + generator.below(candidates.len() - index);
+			candidates.swap(index, chosen);
+		}
+		candidates.truncate(n);
+
+		candidates
+	}
+}
@@ -0,0 +1,81 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Tone sandhi for sequences of dictionary entries
+
+use crate::chinese_dictionary::WordEntry;
+use crate::pinyin::{place_tone_mark, split_tone_number};
+
+/// # Sandhi Syllable
+/// A single syllable with both its dictionary (underlying) tone and the tone it is pronounced with in context (surface).
+/// Tones are numbered 1 through 5, with 5 as the neutral tone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SandhiSyllable {
+	/// The character the syllable belongs to, when the entry has one character per syllable
+	pub character: Option<char>,
+	/// The syllable without its tone, e.g. `"ni"`
+	pub letters: String,
+	pub underlying_tone: u8,
+	pub surface_tone: u8,
+}
+
+impl SandhiSyllable {
+	/// # Surface Pinyin Marks
+	/// The syllable written with the tone mark of its surface tone, e.g. `"ní"`.
+	pub fn surface_pinyin_marks(&self) -> String {
+		place_tone_mark(&self.letters, self.surface_tone)
+	}
+}
+
+/// # Apply Tone Sandhi
+/// Compute the surface tone of every syllable in a sequence of words, such as the entries for the output of `segment`.
+///
+/// The following rules are applied, each based on the underlying tones of the neighbouring syllables:
+/// * In a run of third tones, every syllable but the last is pronounced with the second tone (你好 ní hǎo, 我很好 wó hén hǎo).
+/// * 不 is pronounced with the second tone before a fourth tone (不是 bú shì).
+/// * 一 is pronounced with the second tone before a fourth tone (一個 yí gè) and with the fourth tone before any other tone (一天 yì tiān).
+///   It keeps the first tone at the end of a phrase and as an ordinal after 第.
+/// * 不 and 一 between two identical characters are neutral (好不好 hǎo bu hǎo, 看一看 kàn yi kàn).
+pub fn apply_tone_sandhi(tokens: &[&WordEntry]) -> Vec<SandhiSyllable> {
+	let mut syllables: Vec<SandhiSyllable> = Vec::new();
+
+	for token in tokens {
+		let characters: Vec<char> = token.simplified.chars().collect();
+		let pinyin: Vec<&str> = token.pinyin_numbers.split(' ').filter(|syllable| !syllable.is_empty()).collect();
+		for (index, syllable) in pinyin.iter().enumerate() {
+			let (letters, tone) = split_tone_number(syllable);
+			let tone = tone.unwrap_or(5);
+			syllables.push(SandhiSyllable {
+				character: if characters.len() == pinyin.len() { Some(characters[index]) } else { None },
+				letters: letters.to_string(),
+				underlying_tone: tone,
+				surface_tone: tone,
+			});
+		}
+	}
+
+	for index in 0..syllables.len() {
+		let previous = index.checked_sub(1).map(|previous| &syllables[previous]);
+		let next = syllables.get(index + 1);
+		let next_tone = next.map(|next| next.underlying_tone);
+		let reduplicated = match (previous, next) {
+			(Some(previous), Some(next)) => previous.character.is_some() && previous.character == next.character,
+			_ => false,
+		};
+
+		let surface_tone = match (syllables[index].character, syllables[index].underlying_tone) {
+			(Some('不'), 4) | (Some('一'), 1) if reduplicated => 5,
+			(Some('不'), 4) if next_tone == Some(4) => 2,
+			(Some('一'), 1) if previous.and_then(|previous| previous.character) == Some('第') => 1,
+			(Some('一'), 1) => match next_tone {
+				Some(4) => 2,
+				Some(1..=3) => 4,
+				_ => 1,
+			},
+			(_, 3) if next_tone == Some(3) => 2,
+			(_, tone) => tone,
+		};
+		syllables[index].surface_tone = surface_tone;
+	}
+
+	syllables
+}
@@ -0,0 +1,40 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Controls for pruning entries from the segmentation lexicon
+
+use crate::chinese_dictionary::WordEntry;
+use crate::source::SourceId;
+use crate::terminology::Domain;
+use std::collections::HashSet;
+
+/// # Segmentation Filter
+/// Entries to leave out of the lexicon used by `segment` and the other segmentation based methods, set with `Dictionary::set_segmentation_filter`.
+/// Rare multi-character entries sometimes win a greedy match they should not, and excluding them fixes the segmentation of common sentences.
+/// Excluded entries can still be queried: a query for exactly their headword finds them.
+///
+/// A word is left out when every one of its entries is excluded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SegmentationFilter {
+	/// Entries to exclude by `word_id`
+	pub excluded_ids: HashSet<u32>,
+	/// Exclude every entry from these sources, such as `SourceId::GazetteerBrands`
+	pub excluded_sources: HashSet<SourceId>,
+	/// Exclude every entry with one of these domains
+	pub excluded_domains: HashSet<Domain>,
+	/// Exclude words longer than one character whose frequency, as used by `SegmentationMode::Unigram`, is below this
+	pub min_frequency: Option<u32>,
+}
+
+impl SegmentationFilter {
+	pub(crate) fn is_empty(&self) -> bool {
+		*self == SegmentationFilter::default()
+	}
+
+	/// Whether an entry with the given frequency is kept in the segmentation lexicon.
+	pub(crate) fn allows(&self, entry: &WordEntry, frequency: u32) -> bool {
+		!self.excluded_ids.contains(&entry.word_id)
+			&& !self.excluded_sources.contains(&entry.source)
+			&& !entry.domain.is_some_and(|domain| self.excluded_domains.contains(&domain))
+			&& (entry.simplified.chars().count() < 2 || self.min_frequency.is_none_or(|min_frequency| frequency >= min_frequency))
+	}
+}
@@ -0,0 +1,68 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Splitting Chinese text into sentences
+
+/// Checks if a character ends a sentence. `.` is handled separately, as it also appears in numbers and abbreviations.
+fn is_terminator(c: char) -> bool {
+	"。！？；…!?;．｡".contains(c)
+}
+
+/// Checks if a character opens a quotation or bracket.
+pub(crate) fn is_opening(c: char) -> bool {
+	"「『“‘（《〈【〔〖([{".contains(c)
+}
+
+/// Checks if a character closes a quotation or bracket.
+pub(crate) fn is_closing(c: char) -> bool {
+	"」』”’）》〉】〕〗)]}".contains(c)
+}
+
+/// Checks if a character is punctuation that belongs to the text before it and should not start a line, such as `，` or `。`.
+pub(crate) fn is_trailing(c: char) -> bool {
+	is_terminator(c) || "，、：….,:".contains(c)
+}
+
+/// # Split Sentences
+/// Split text into sentences, ending each at `。`, `！`, `？`, `；`, `…`, their Western equivalents, or a line break.
+/// A run of terminators such as `？！` or `……` stays with its sentence, as do any closing quotes or brackets after it.
+/// Terminators inside quotes or brackets do not end the sentence, so quoted speech is kept with the sentence it belongs to.
+/// Each sentence is trimmed of surrounding whitespace, and empty sentences are skipped.
+pub fn split_sentences(text: &str) -> Vec<&str> {
+	let mut sentences: Vec<&str> = Vec::new();
+	let mut chars = text.char_indices().peekable();
+	let mut start = 0;
+	let mut depth: usize = 0;
+
+	while let Some((index, c)) = chars.next() {
+		let end = if c == '\n' {
+			Some(index)
+		} else if is_opening(c) {
+			depth += 1;
+			None
+		} else if is_closing(c) {
+			depth = depth.saturating_sub(1);
+			None
+		} else if depth == 0 && (is_terminator(c) || (c == '.' && !matches!(chars.peek(), Some((_, next)) if !next.is_whitespace()))) {
+			let mut end = index + c.len_utf8();
+			while let Some(&(next_index, next)) = chars.peek() {
+				if !(is_terminator(next) || next == '.' || is_closing(next)) {
+					break;
+				}
+				end = next_index + next.len_utf8();
+				chars.next();
+			}
+			Some(end)
+		} else {
+			None
+		};
+
+		if let Some(end) = end {
+			sentences.push(&text[start..end]);
+			start = end;
+			depth = 0;
+		}
+	}
+	sentences.push(&text[start..]);
+
+	sentences.into_iter().map(str::trim).filter(|sentence| !sentence.is_empty()).collect()
+}
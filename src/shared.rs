@@ -0,0 +1,110 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: A cheaply cloneable handle to a dictionary
+
+use crate::builder::parse_cedict;
+use crate::chinese_dictionary::{Dictionary, MergeStrategy};
+use crate::hash::allocate_word_id;
+use crate::source::SourceId;
+use std::fs;
+use std::io;
+use std::ops::Deref;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+/// # Shared Dictionary
+/// A handle to a `Dictionary` that can be cloned cheaply and passed to other threads.
+///
+/// Every query method takes `&self` and the entries are never modified after the dictionary is created,
+/// so any number of threads can query the same dictionary at once.
+/// The indexes are the exception: each is loaded the first time a query uses it, and other threads that need the same index
+/// block until it has loaded, which can take a moment. Create the dictionary with `Dictionary::init_only` to load them up front instead.
+/// Once loaded, queries run without locking. Cloning the handle only increments a reference count; the dictionary data is loaded once.
+#[derive(Clone)]
+pub struct SharedDictionary {
+	dictionary: Arc<Dictionary>,
+}
+
+impl SharedDictionary {
+	/// Load the built-in dictionary and wrap it in a shared handle. Instantiation may take a while.
+	pub fn new() -> SharedDictionary {
+		SharedDictionary::from(Dictionary::new())
+	}
+}
+
+impl Default for SharedDictionary {
+	fn default() -> SharedDictionary {
+		SharedDictionary::new()
+	}
+}
+
+impl From<Dictionary> for SharedDictionary {
+	fn from(dictionary: Dictionary) -> SharedDictionary {
+		SharedDictionary { dictionary: Arc::new(dictionary) }
+	}
+}
+
+impl Deref for SharedDictionary {
+	type Target = Dictionary;
+
+	fn deref(&self) -> &Dictionary {
+		&self.dictionary
+	}
+}
+
+/// # Reloadable Dictionary
+/// A dictionary with a user dictionary file layered over it that can be reloaded while other threads are querying it,
+/// such as to pick up edits to custom vocabulary while developing an annotation tool.
+///
+/// Queries go through a `snapshot`, which keeps the data it was taken from alive until it is dropped,
+/// so a reload never changes the results of a query already in flight. The next snapshot sees the new data.
+pub struct ReloadableDictionary {
+	base: Arc<Dictionary>,
+	current: RwLock<Arc<Dictionary>>,
+}
+
+impl ReloadableDictionary {
+	/// Wrap a dictionary, such as the built-in one, with no user dictionary layered over it yet.
+	pub fn new(base: Dictionary) -> ReloadableDictionary {
+		let base = Arc::new(base);
+		ReloadableDictionary { current: RwLock::new(Arc::clone(&base)), base }
+	}
+
+	/// # Snapshot
+	/// The dictionary as of the last reload, as a shared handle that stays valid across later reloads.
+	pub fn snapshot(&self) -> SharedDictionary {
+		SharedDictionary { dictionary: Arc::clone(&self.current.read().unwrap_or_else(|poisoned| poisoned.into_inner())) }
+	}
+
+	/// # Reload User Dictionary
+	/// Read a user dictionary in the CC-CEDICT format and atomically replace the previous one with it, returning the number of entries read.
+	/// User entries shadow base entries with the same headwords and pinyin, and are given `SourceId::User` and ids from `allocate_word_id`.
+	/// Only the entries change: the reloaded dictionary keeps the configuration of the base dictionary, such as its normalizer and ranker.
+	/// Building the combined dictionary takes a few seconds, during which snapshots still return the previous data.
+	/// If the file cannot be read or has a malformed line, the error is returned and the previous data is kept.
+	pub fn reload_user_dictionary<P: AsRef<Path>>(&self, path: P) -> io::Result<usize> {
+		let mut entries = parse_cedict(&fs::read_to_string(path)?)?;
+		for entry in &mut entries {
+			entry.source = SourceId::User;
+			entry.word_id = allocate_word_id(&entry.traditional, &entry.pinyin_numbers);
+		}
+		let merged = self.base.overlay(&entries, MergeStrategy::HeadwordPinyin);
+
+		*self.current.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Arc::new(merged);
+		Ok(entries.len())
+	}
+
+	/// # Clear User Dictionary
+	/// Remove the user dictionary, so the next snapshot only has the base dictionary.
+	pub fn clear_user_dictionary(&self) {
+		*self.current.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Arc::clone(&self.base);
+	}
+}
+
+// Fails to compile if the dictionary ever stops being safe to share between threads.
+const _: fn() = || {
+	fn assert_send_sync<T: Send + Sync>() {}
+	assert_send_sync::<Dictionary>();
+	assert_send_sync::<SharedDictionary>();
+	assert_send_sync::<ReloadableDictionary>();
+};
@@ -0,0 +1,92 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Expansion of double pinyin (shuangpin) input to full pinyin
+
+use crate::pinyin::SYLLABLES;
+
+/// A double pinyin (shuangpin) input scheme, where every syllable is typed with exactly two keys: one for the initial and one for the final.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShuangpinScheme {
+	/// 小鹤双拼
+	Xiaohe,
+	/// 微软双拼. The `;` key types the final `ing`.
+	Microsoft,
+	/// 自然码
+	Ziranma,
+}
+
+impl ShuangpinScheme {
+	/// The key for a final, as it is spelled after an initial.
+	fn final_key(self, fin: &str) -> Option<char> {
+		let key = match self {
+			ShuangpinScheme::Xiaohe => match fin {
+				"iu" => 'q', "ei" => 'w', "e" => 'e', "uan" => 'r', "ue" | "üe" => 't', "un" => 'y', "u" => 'u', "i" => 'i',
+				"uo" | "o" => 'o', "ie" => 'p', "a" => 'a', "ong" | "iong" => 's', "ai" => 'd', "en" => 'f', "eng" => 'g',
+				"ang" => 'h', "an" => 'j', "uai" | "ing" => 'k', "uang" | "iang" => 'l', "ou" => 'z', "ia" | "ua" => 'x',
+				"ao" => 'c', "ui" | "ü" => 'v', "in" => 'b', "iao" => 'n', "ian" => 'm',
+				_ => return None,
+			},
+			ShuangpinScheme::Microsoft => match fin {
+				"iu" => 'q', "ia" | "ua" => 'w', "e" => 'e', "uan" | "er" => 'r', "ue" | "üe" => 't', "uai" | "ü" => 'y',
+				"u" => 'u', "i" => 'i', "uo" | "o" => 'o', "un" => 'p', "a" => 'a', "ong" | "iong" => 's', "uang" | "iang" => 'd',
+				"en" => 'f', "eng" => 'g', "ang" => 'h', "an" => 'j', "ao" => 'k', "ai" => 'l', "ing" => ';', "ei" => 'z',
+				"ie" => 'x', "iao" => 'c', "ui" => 'v', "ou" => 'b', "in" => 'n', "ian" => 'm',
+				_ => return None,
+			},
+			ShuangpinScheme::Ziranma => match fin {
+				"iu" => 'q', "ia" | "ua" => 'w', "e" => 'e', "uan" => 'r', "ue" | "üe" => 't', "uai" | "ing" => 'y', "u" => 'u',
+				"i" => 'i', "uo" | "o" => 'o', "un" => 'p', "a" => 'a', "ong" | "iong" => 's', "uang" | "iang" => 'd', "en" => 'f',
+				"eng" => 'g', "ang" => 'h', "an" => 'j', "ao" => 'k', "ai" => 'l', "ei" => 'z', "ie" => 'x', "iao" => 'c',
+				"ui" | "ü" => 'v', "ou" => 'b', "in" => 'n', "ian" => 'm',
+				_ => return None,
+			},
+		};
+
+		Some(key)
+	}
+
+	/// The two keys typed for a syllable, or `None` for syllables the scheme cannot type, such as the interjection `hm`.
+	fn encode(self, syllable: &str) -> Option<(char, char)> {
+		let initial_length = match syllable.get(..2) {
+			Some("zh") | Some("ch") | Some("sh") => 2,
+			_ if syllable.starts_with(|c: char| "bpmfdtnlgkhjqxrzcsyw".contains(c)) => 1,
+			_ => 0,
+		};
+		let (initial, fin) = syllable.split_at(initial_length);
+		let first = fin.chars().next()?;
+		if !first.is_ascii() {
+			return None;
+		}
+
+		match initial {
+			"zh" => Some(('v', self.final_key(fin)?)),
+			"ch" => Some(('i', self.final_key(fin)?)),
+			"sh" => Some(('u', self.final_key(fin)?)),
+			"" if self == ShuangpinScheme::Microsoft => Some(('o', self.final_key(fin)?)),
+			// Finals without an initial are typed as their first letter followed by either their second letter, or the final key when they are longer
+			"" => match fin.len() {
+				1 => Some((first, first)),
+				2 => fin.chars().nth(1).map(|second| (first, second)),
+				_ => Some((first, self.final_key(fin)?)),
+			},
+			_ => Some((initial.chars().next()?, self.final_key(fin)?)),
+		}
+	}
+
+	/// The syllable typed by a pair of keys. When two syllables share keys, the later one in `SYLLABLES` wins, so `lo` reads as the far more common `luo`.
+	fn decode(self, keys: (char, char)) -> Option<&'static str> {
+		SYLLABLES.iter().rev().find(|syllable| self.encode(syllable) == Some(keys)).copied()
+	}
+
+	/// # Expand
+	/// Expand double pinyin input to full pinyin without spaces or tones, e.g. `"nihc"` to `"nihao"` in the Xiaohe scheme.
+	/// Returns `None` when the input is not a sequence of valid key pairs.
+	pub fn expand(self, input: &str) -> Option<String> {
+		let keys: Vec<char> = input.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).collect();
+		if keys.is_empty() || keys.len() % 2 == 1 {
+			return None;
+		}
+
+		keys.chunks(2).map(|pair| self.decode((pair[0], pair[1]))).collect()
+	}
+}
@@ -0,0 +1,68 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Suggest simpler synonyms to adapt texts to a learner's level
+
+use crate::builder::english_key;
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+/// A word above the target level, and the words within it that could replace it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion<'a> {
+	/// The word as it appears in the text
+	pub word: &'a str,
+	/// The byte range of the word in the text
+	pub range: Range<usize>,
+	/// The lowest HSK level of the word, or `0` if it is not in the HSK lists
+	pub hsk: u8,
+	/// Synonyms at or below the target level, most frequent first. Empty when there is no simpler synonym, so the word should be glossed instead
+	pub replacements: Vec<&'a WordEntry>,
+}
+
+impl Dictionary {
+	/// # Simplify Vocabulary
+	/// Find the words in `text` that are above HSK level `max_hsk`, or not in the HSK lists at all, and propose synonyms at or below that level.
+	/// Two words are synonyms when an entry of each has the same gloss, ignoring case and parenthetical notes, e.g. 凝视 and 盯 both mean "to stare at".
+	/// Words outside the HSK lists that are made up entirely of characters within the level, such as 这个, are treated as within it. Proper nouns are never suggested for simplification.
+	pub fn simplify_vocabulary<'a>(&'a self, text: &'a str, max_hsk: u8) -> Vec<Suggestion<'a>> {
+		let mut in_level: HashMap<String, Vec<&WordEntry>> = HashMap::new();
+		for entry in self.entries().filter(|entry| entry.hsk != 0 && entry.hsk <= max_hsk) {
+			for gloss in &entry.english {
+				in_level.entry(english_key(gloss)).or_default().push(entry);
+			}
+		}
+
+		self.scan(text).into_iter().filter_map(|range| {
+			let word = &text[range.clone()];
+			let entries = self.entries_for_headword(word);
+			if entries.is_empty() || entries.iter().all(|entry| entry.pinyin_numbers.starts_with(char::is_uppercase)) {
+				return None;
+			}
+			let hsk = self.hsk_level(word);
+			if hsk != 0 && hsk <= max_hsk {
+				return None;
+			}
+			if hsk == 0 && word.chars().count() > 1 && word.chars().all(|c| (1..=max_hsk).contains(&self.hsk_level(c.encode_utf8(&mut [0; 4])))) {
+				return None;
+			}
+
+			let mut seen: HashSet<u32> = HashSet::new();
+			let mut replacements: Vec<&WordEntry> = entries.iter()
+				.flat_map(|entry| entry.english.iter())
+				.filter_map(|gloss| in_level.get(&english_key(gloss)))
+				.flatten()
+				.copied()
+				.filter(|replacement| replacement.simplified != word && replacement.traditional != word && seen.insert(replacement.word_id))
+				.collect();
+			replacements.sort_by_key(|replacement| (std::cmp::Reverse(self.frequency(&replacement.simplified)), replacement.word_id));
+
+			Some(Suggestion { word, range, hsk, replacements })
+		}).collect()
+	}
+
+	/// The lowest HSK level of the entries for a word, or `0` if none of them has one.
+	fn hsk_level(&self, word: &str) -> u8 {
+		self.entries_for_headword(word).iter().map(|entry| entry.hsk).filter(|hsk| *hsk != 0).min().unwrap_or(0)
+	}
+}
@@ -0,0 +1,77 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Attribution for the sources of dictionary entries
+
+use crate::terminology::Domain;
+
+/// Identifies where a `WordEntry` came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SourceId {
+	/// The CC-CEDICT data built into the crate
+	CcCedict,
+	/// The `gazetteer-places` feature
+	GazetteerPlaces,
+	/// The `gazetteer-brands` feature
+	GazetteerBrands,
+	/// One of the `terms-medical`, `terms-legal`, or `terms-it` features
+	Terminology(Domain),
+	/// The `classical` feature, whose entries are only used by `Dictionary::query_classical` and `SegmentationMode::Classical`
+	ClassicalGlossary,
+	/// The `neologisms` feature
+	Neologisms,
+	/// Entries supplied by the application, such as through `Dictionary::from_entries`
+	#[default]
+	User,
+}
+
+/// Attribution details for a source of entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Source {
+	pub id: SourceId,
+	pub name: &'static str,
+	/// The license the entries are distributed under, if known
+	pub license: Option<&'static str>,
+	pub url: Option<&'static str>,
+}
+
+/// Every source, in the order `Dictionary::sources` lists them.
+static SOURCE_IDS: [SourceId; 9] = [
+	SourceId::CcCedict,
+	SourceId::GazetteerPlaces,
+	SourceId::GazetteerBrands,
+	SourceId::Terminology(Domain::Medical),
+	SourceId::Terminology(Domain::Legal),
+	SourceId::Terminology(Domain::It),
+	SourceId::ClassicalGlossary,
+	SourceId::Neologisms,
+	SourceId::User,
+];
+
+/// CC-CEDICT's license, which also covers the packs that repeat its entries.
+const CC_BY_SA: Option<&str> = Some("Creative Commons Attribution-ShareAlike 4.0");
+/// The crate's own license, for the packs written for it.
+const MIT: Option<&str> = Some("MIT");
+
+impl SourceId {
+	/// # Source
+	/// The attribution details for this source.
+	pub fn source(self) -> Source {
+		let (name, license, url) = match self {
+			SourceId::CcCedict => ("CC-CEDICT", CC_BY_SA, Some("https://www.mdbg.net/chinese/dictionary?page=cc-cedict")),
+			SourceId::GazetteerPlaces => ("chinese_dictionary place names", CC_BY_SA, None),
+			SourceId::GazetteerBrands => ("chinese_dictionary brand names", MIT, None),
+			SourceId::Terminology(Domain::Medical) => ("chinese_dictionary medical terminology", CC_BY_SA, None),
+			SourceId::Terminology(Domain::Legal) => ("chinese_dictionary legal terminology", CC_BY_SA, None),
+			SourceId::Terminology(Domain::It) => ("chinese_dictionary IT terminology", CC_BY_SA, None),
+			SourceId::ClassicalGlossary => ("chinese_dictionary classical glossary", MIT, None),
+			SourceId::Neologisms => ("chinese_dictionary neologisms", CC_BY_SA, None),
+			SourceId::User => ("User dictionary", None, None),
+		};
+
+		Source { id: self, name, license, url }
+	}
+
+	pub(crate) fn all() -> &'static [SourceId] {
+		&SOURCE_IDS
+	}
+}
@@ -0,0 +1,88 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Export of entries as a StarDict dictionary
+
+use crate::chinese_dictionary::WordEntry;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Error, ErrorKind};
+use std::path::Path;
+
+/// # Export StarDict
+/// Write entries as a StarDict dictionary named `name` into `out_dir`, which can be loaded into readers such as GoldenDict and KOReader.
+/// Pass `Dictionary::entries` to export everything, or filter it first to export a subset, such as a single HSK level.
+///
+/// Entries are grouped into one article per simplified headword, and traditional headwords that differ are added as synonyms.
+/// Four files are written: `name.ifo`, `name.idx`, `name.dict`, and `name.syn`.
+/// MDX, the format used by MDict, is not supported, but StarDict dictionaries can be converted to it with tools such as pyglossary.
+pub fn export_stardict<'a, I: IntoIterator<Item = &'a WordEntry>, P: AsRef<Path>>(entries: I, out_dir: P, name: &str) -> io::Result<()> {
+	if name.is_empty() || name.contains(['\n', '/', '\\']) {
+		return Err(Error::new(ErrorKind::InvalidInput, "The dictionary name must be a single line without path separators"));
+	}
+
+	let mut articles: BTreeMap<&str, Vec<&WordEntry>> = BTreeMap::new();
+	for entry in entries {
+		articles.entry(&entry.simplified).or_default().push(entry);
+	}
+	let mut headwords: Vec<(&str, Vec<&WordEntry>)> = articles.into_iter().collect();
+	headwords.sort_by(|(a, _), (b, _)| stardict_order(a, b));
+
+	let word_count = headwords.len();
+	let mut dict: Vec<u8> = Vec::new();
+	let mut idx: Vec<u8> = Vec::new();
+	let mut synonyms: Vec<(&str, u32)> = Vec::new();
+	for (position, (headword, mut entries)) in headwords.into_iter().enumerate() {
+		entries.sort_by_key(|entry| entry.word_id);
+		let article = entries.iter().map(|entry| article(entry)).collect::<Vec<String>>().join("\n");
+		idx.extend_from_slice(headword.as_bytes());
+		idx.push(0);
+		idx.extend_from_slice(&(dict.len() as u32).to_be_bytes());
+		idx.extend_from_slice(&(article.len() as u32).to_be_bytes());
+		dict.extend_from_slice(article.as_bytes());
+		for entry in entries {
+			if entry.traditional != headword && !synonyms.contains(&(&entry.traditional, position as u32)) {
+				synonyms.push((&entry.traditional, position as u32));
+			}
+		}
+	}
+	synonyms.sort_by(|(a, _), (b, _)| stardict_order(a, b));
+	let mut syn: Vec<u8> = Vec::new();
+	for (synonym, position) in &synonyms {
+		syn.extend_from_slice(synonym.as_bytes());
+		syn.push(0);
+		syn.extend_from_slice(&position.to_be_bytes());
+	}
+
+	let ifo = format!(
+		"StarDict's dict ifo file\nversion=2.4.2\nbookname={}\nwordcount={}\nsynwordcount={}\nidxfilesize={}\nsametypesequence=m\n",
+		name, word_count, synonyms.len(), idx.len(),
+	);
+
+	let out_dir = out_dir.as_ref();
+	fs::create_dir_all(out_dir)?;
+	fs::write(out_dir.join(format!("{}.ifo", name)), ifo)?;
+	fs::write(out_dir.join(format!("{}.idx", name)), idx)?;
+	fs::write(out_dir.join(format!("{}.dict", name)), dict)?;
+	fs::write(out_dir.join(format!("{}.syn", name)), syn)
+}
+
+/// The plain text article for an entry: its headwords and pinyin, then its numbered glosses and measure words.
+fn article(entry: &WordEntry) -> String {
+	let mut article = match entry.traditional == entry.simplified {
+		true => format!("{} [{}]\n", entry.simplified, entry.pinyin_marks),
+		false => format!("{} ({}) [{}]\n", entry.simplified, entry.traditional, entry.pinyin_marks),
+	};
+	for (number, gloss) in entry.english.iter().enumerate() {
+		article.push_str(&format!("{}. {}\n", number + 1, gloss));
+	}
+	if !entry.measure_words.is_empty() {
+		let measure_words: Vec<&str> = entry.measure_words.iter().map(|measure_word| measure_word.simplified.as_str()).collect();
+		article.push_str(&format!("Measure words: {}\n", measure_words.join(", ")));
+	}
+	article
+}
+
+/// The order StarDict requires for the words of its index files: ASCII case insensitive, then byte by byte.
+fn stardict_order(a: &str, b: &str) -> std::cmp::Ordering {
+	a.bytes().map(|byte| byte.to_ascii_lowercase()).cmp(b.bytes().map(|byte| byte.to_ascii_lowercase())).then_with(|| a.cmp(b))
+}
@@ -0,0 +1,109 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Line by line annotation of text streams with bounded memory
+
+use crate::chinese_dictionary::Dictionary;
+use std::io::{self, BufRead, Read};
+use std::ops::Range;
+
+/// # Annotated Word
+/// A dictionary word found in an `AnnotatedLine`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotatedWord {
+	/// The byte range of the word in `AnnotatedLine::text`
+	pub range: Range<usize>,
+	/// The ids of every entry with this headword
+	pub word_ids: Vec<u32>,
+}
+
+/// # Annotated Line
+/// A line of input along with the dictionary words in it, as returned by `annotate_stream`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotatedLine {
+	/// The line number, starting at 1
+	pub number: usize,
+	/// The text of the line without its line break. Invalid UTF-8 is replaced with `U+FFFD`
+	pub text: String,
+	pub words: Vec<AnnotatedWord>,
+	/// The line was longer than `InputLimits::max_tokenize_bytes` and was cut off, with the rest following in the next `AnnotatedLine` with the same number
+	pub truncated: bool,
+}
+
+/// # Annotated Lines
+/// An iterator over the annotated lines of a reader, returned by `Dictionary::annotate_stream`.
+pub struct AnnotatedLines<'a, R> {
+	dictionary: &'a Dictionary,
+	reader: R,
+	number: usize,
+	/// The start of a character cut off at the end of a truncated line
+	carry: Vec<u8>,
+	/// Whether the next line continues a truncated one
+	continued: bool,
+	error: Option<io::Error>,
+}
+
+impl<R> AnnotatedLines<'_, R> {
+	/// The error that ended the iteration early, if reading from the reader failed.
+	pub fn error(&self) -> Option<&io::Error> {
+		self.error.as_ref()
+	}
+}
+
+impl<R: BufRead> Iterator for AnnotatedLines<'_, R> {
+	type Item = AnnotatedLine;
+
+	fn next(&mut self) -> Option<AnnotatedLine> {
+		if self.error.is_some() {
+			return None;
+		}
+		let limit = self.dictionary.input_limits().max_tokenize_bytes.max(4);
+		let mut buffer = std::mem::take(&mut self.carry);
+		let remaining = (limit - buffer.len()) as u64;
+		match (&mut self.reader).take(remaining).read_until(b'\n', &mut buffer) {
+			Ok(0) if buffer.is_empty() => return None,
+			Ok(_) => {},
+			Err(error) => {
+				self.error = Some(error);
+				return None;
+			},
+		}
+
+		let truncated = buffer.len() >= limit && !buffer.ends_with(b"\n");
+		if truncated {
+			// A character cut in half is carried over to the next part of the line instead of being replaced
+			if let Err(error) = std::str::from_utf8(&buffer) {
+				if error.error_len().is_none() {
+					self.carry = buffer.split_off(error.valid_up_to());
+				}
+			}
+		} else if buffer.ends_with(b"\n") {
+			buffer.pop();
+			if buffer.ends_with(b"\r") {
+				buffer.pop();
+			}
+		}
+		if !self.continued {
+			self.number += 1;
+		}
+		self.continued = truncated;
+
+		let text = String::from_utf8_lossy(&buffer).into_owned();
+		let words = self.dictionary.scan(&text).into_iter().map(|range| {
+			let word_ids = self.dictionary.entries_for_headword(&text[range.clone()]).iter().map(|entry| entry.word_id).collect();
+			AnnotatedWord { range, word_ids }
+		}).collect();
+		Some(AnnotatedLine { number: self.number, text, words, truncated })
+	}
+}
+
+impl Dictionary {
+	/// # Annotate Stream
+	/// Find the dictionary words in every line read from `reader`, one line at a time, for piping corpora of any size through the dictionary in batch jobs.
+	/// Only one line is held in memory at a time, and lines longer than `InputLimits::max_tokenize_bytes` are split into parts of at most that size,
+	/// so memory stays bounded even for input without line breaks. Everything runs on the calling thread as the iterator is advanced.
+	///
+	/// Iteration stops at the end of the input or at the first read error, which is then available from `AnnotatedLines::error`.
+	pub fn annotate_stream<R: BufRead>(&self, reader: R) -> AnnotatedLines<'_, R> {
+		AnnotatedLines { dictionary: self, reader, number: 0, carry: Vec::new(), continued: false, error: None }
+	}
+}
@@ -0,0 +1,204 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Stroke order data in the Make Me A Hanzi format
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// A single stroke of a character, in the coordinate system of Make Me A Hanzi: a 1024 by 1024 box with the y axis pointing up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrokePath {
+	/// The outline of the stroke as SVG path data, e.g. `"M 323 706 Q 325 699 ..."`
+	pub path: String,
+	/// The points along the middle of the stroke, in the direction it is written, for animating it
+	pub median: Vec<(i32, i32)>,
+}
+
+/// An error returned by `StrokeData::from_graphics` for a line that is not a valid Make Me A Hanzi record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrokeDataError {
+	/// The malformed line, counting from `1`
+	pub line: usize,
+}
+
+impl fmt::Display for StrokeDataError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "malformed stroke data on line {}", self.line)
+	}
+}
+
+impl Error for StrokeDataError {}
+
+/// # Stroke Data
+/// Stroke order for characters, loaded from the `graphics.txt` file of Make Me A Hanzi (https://github.com/skishore/makemeahanzi).
+/// The data is not bundled with the crate, since it is several megabytes and licensed separately under the Arphic Public License.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StrokeData {
+	characters: HashMap<char, Vec<StrokePath>>,
+}
+
+impl StrokeData {
+	/// # From Graphics
+	/// Parse the contents of a Make Me A Hanzi `graphics.txt` file, which has one JSON object per line with a `character`, its `strokes`, and their `medians`.
+	pub fn from_graphics(source: &str) -> Result<StrokeData, StrokeDataError> {
+		let mut characters: HashMap<char, Vec<StrokePath>> = HashMap::new();
+		for (number, line) in source.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+			let error = StrokeDataError { line: number + 1 };
+			let record = JsonReader { text: line, position: 0 }.value().ok_or(error)?;
+			let mut chars = record.field("character").and_then(Json::as_str).ok_or(error)?.chars();
+			let character = match (chars.next(), chars.next()) {
+				(Some(character), None) => character,
+				_ => return Err(error),
+			};
+			let paths = record.field("strokes").and_then(Json::as_array).ok_or(error)?;
+			let medians = record.field("medians").and_then(Json::as_array).ok_or(error)?;
+			if paths.len() != medians.len() {
+				return Err(error);
+			}
+			let strokes = paths.iter().zip(medians).map(|(path, median)| {
+				let median = median.as_array()?.iter().map(|point| match point.as_array()? {
+					[Json::Number(x), Json::Number(y)] => Some((*x as i32, *y as i32)),
+					_ => None,
+				}).collect::<Option<Vec<(i32, i32)>>>()?;
+				Some(StrokePath { path: path.as_str()?.to_string(), median })
+			}).collect::<Option<Vec<StrokePath>>>().ok_or(error)?;
+			characters.insert(character, strokes);
+		}
+
+		Ok(StrokeData { characters })
+	}
+
+	/// # Stroke Order
+	/// The strokes of a character in the order they are written, or an empty slice if the character is not in the data.
+	pub fn stroke_order(&self, character: char) -> &[StrokePath] {
+		self.characters.get(&character).map_or(&[], Vec::as_slice)
+	}
+
+	/// # Stroke Count
+	/// The number of strokes in a character, if it is in the data.
+	pub fn stroke_count(&self, character: char) -> Option<usize> {
+		self.characters.get(&character).map(Vec::len)
+	}
+}
+
+/// The JSON values used by Make Me A Hanzi.
+enum Json {
+	String(String),
+	Number(f64),
+	Array(Vec<Json>),
+	Object(Vec<(String, Json)>),
+	Null,
+}
+
+impl Json {
+	fn field(&self, name: &str) -> Option<&Json> {
+		match self {
+			Json::Object(fields) => fields.iter().find(|(key, _)| key == name).map(|(_, value)| value),
+			_ => None,
+		}
+	}
+
+	fn as_str(&self) -> Option<&str> {
+		match self {
+			Json::String(string) => Some(string),
+			_ => None,
+		}
+	}
+
+	fn as_array(&self) -> Option<&[Json]> {
+		match self {
+			Json::Array(values) => Some(values),
+			_ => None,
+		}
+	}
+}
+
+/// Reads a single JSON value. Booleans are not used by Make Me A Hanzi and are not supported.
+struct JsonReader<'a> {
+	text: &'a str,
+	position: usize,
+}
+
+impl JsonReader<'_> {
+	fn peek(&mut self) -> Option<char> {
+		self.position += self.text[self.position..].len() - self.text[self.position..].trim_start().len();
+		self.text[self.position..].chars().next()
+	}
+
+	fn expect(&mut self, expected: char) -> Option<()> {
+		if self.peek()? != expected {
+			return None;
+		}
+		self.position += expected.len_utf8();
+		Some(())
+	}
+
+	fn value(&mut self) -> Option<Json> {
+		match self.peek()? {
+			'"' => self.string().map(Json::String),
+			'[' => {
+				self.expect('[')?;
+				let mut values: Vec<Json> = Vec::new();
+				while self.peek()? != ']' {
+					values.push(self.value()?);
+					if self.peek()? == ',' {
+						self.expect(',')?;
+					}
+				}
+				self.expect(']')?;
+				Some(Json::Array(values))
+			},
+			'{' => {
+				self.expect('{')?;
+				let mut fields: Vec<(String, Json)> = Vec::new();
+				while self.peek()? != '}' {
+					let key = self.string()?;
+					self.expect(':')?;
+					fields.push((key, self.value()?));
+					if self.peek()? == ',' {
+						self.expect(',')?;
+					}
+				}
+				self.expect('}')?;
+				Some(Json::Object(fields))
+			},
+			'n' => {
+				self.text[self.position..].starts_with("null").then_some(())?;
+				self.position += 4;
+				Some(Json::Null)
+			},
+			_ => {
+				let rest = &self.text[self.position..];
+				let length = rest.find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c))).unwrap_or(rest.len());
+				self.position += length;
+				rest[..length].parse().ok().map(Json::Number)
+			},
+		}
+	}
+
+	fn string(&mut self) -> Option<String> {
+		self.expect('"')?;
+		let mut string = String::new();
+		let mut chars = self.text[self.position..].char_indices();
+		while let Some((offset, character)) = chars.next() {
+			match character {
+				'"' => {
+					self.position += offset + 1;
+					return Some(string);
+				},
+				'\\' => match chars.next()?.1 {
+					'n' => string.push('\n'),
+					't' => string.push('\t'),
+					'u' => {
+						let hex: String = (0..4).map(|_| chars.next().map(|(_, c)| c)).collect::<Option<String>>()?;
+						string.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+					},
+					escaped => string.push(escaped),
+				},
+				_ => string.push(character),
+			}
+		}
+		None
+	}
+}
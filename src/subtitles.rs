@@ -0,0 +1,149 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Annotation and vocabulary lists for SRT subtitles
+
+use crate::chinese_dictionary::Dictionary;
+use crate::pinyin::format_pinyin_words;
+use crate::punctuation::is_han;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+/// A single subtitle, shown from `start` to `end`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cue {
+	pub start: Duration,
+	pub end: Duration,
+	/// The text of the subtitle, with lines separated by `\n`
+	pub text: String,
+}
+
+/// An error returned by `parse_srt` when a cue does not have a valid timing line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubtitleError {
+	/// The line of the malformed cue, counting from `1`
+	pub line: usize,
+}
+
+impl fmt::Display for SubtitleError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "malformed subtitle cue on line {}", self.line)
+	}
+}
+
+impl Error for SubtitleError {}
+
+/// A word of a vocabulary list made by `Dictionary::subtitle_vocabulary`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubtitleWord {
+	/// The word as it was written in the subtitles
+	pub word: String,
+	/// The ids of every entry with this headword
+	pub word_ids: Vec<u32>,
+	/// The start time of each cue the word appears in, once per occurrence
+	pub occurrences: Vec<Duration>,
+}
+
+/// # Parse SRT
+/// Parse the cues of a SubRip (`.srt`) subtitle file. The number before each cue is optional and is not kept.
+pub fn parse_srt(source: &str) -> Result<Vec<Cue>, SubtitleError> {
+	let mut cues: Vec<Cue> = Vec::new();
+	let mut lines = source.trim_start_matches('\u{FEFF}').lines().map(|line| line.trim_end_matches('\r')).enumerate().peekable();
+
+	while let Some((number, line)) = lines.next() {
+		if line.trim().is_empty() {
+			continue;
+		}
+		let (number, timing) = match line.contains("-->") {
+			true => (number, line),
+			false => lines.next().ok_or(SubtitleError { line: number + 1 })?,
+		};
+		let (start, end) = parse_timing(timing).ok_or(SubtitleError { line: number + 1 })?;
+		let mut text: Vec<&str> = Vec::new();
+		while let Some((_, line)) = lines.next_if(|(_, line)| !line.trim().is_empty()) {
+			text.push(line);
+		}
+		cues.push(Cue { start, end, text: text.join("\n") });
+	}
+
+	Ok(cues)
+}
+
+/// # Write SRT
+/// Write cues as a SubRip (`.srt`) subtitle file, numbering them from `1`.
+pub fn write_srt(cues: &[Cue]) -> String {
+	cues.iter().enumerate()
+		.map(|(index, cue)| format!("{}\n{} --> {}\n{}\n", index + 1, format_timestamp(cue.start), format_timestamp(cue.end), cue.text))
+		.collect::<Vec<String>>()
+		.join("\n")
+}
+
+/// Parses a timing line such as `00:01:02,500 --> 00:01:04,000`, ignoring any position coordinates after it.
+fn parse_timing(line: &str) -> Option<(Duration, Duration)> {
+	let (start, end) = line.split_once("-->")?;
+	let end = end.split_whitespace().next()?;
+	Some((parse_timestamp(start.trim())?, parse_timestamp(end)?))
+}
+
+/// Parses a timestamp such as `00:01:02,500`. A `.` is accepted in place of the `,`.
+fn parse_timestamp(timestamp: &str) -> Option<Duration> {
+	let (clock, milliseconds) = timestamp.split_once([',', '.'])?;
+	let mut parts = clock.split(':').map(|part| part.parse::<u64>().ok());
+	let (hours, minutes, seconds) = (parts.next()??, parts.next()??, parts.next()??);
+	if parts.next().is_some() || minutes > 59 || seconds > 59 {
+		return None;
+	}
+	Some(Duration::from_millis(((hours * 60 + minutes) * 60 + seconds) * 1000 + milliseconds.parse::<u64>().ok()?))
+}
+
+fn format_timestamp(timestamp: Duration) -> String {
+	let milliseconds = timestamp.as_millis();
+	format!("{:02}:{:02}:{:02},{:03}", milliseconds / 3_600_000, milliseconds / 60_000 % 60, milliseconds / 1000 % 60, milliseconds % 1000)
+}
+
+impl Dictionary {
+	/// # Annotate Subtitles
+	/// Add a line of pinyin under the text of each cue, written with `format_pinyin_words`, for watching with pinyin subtitles.
+	/// Each word uses the pinyin of its first entry. Cues without any Chinese words are left as they are.
+	pub fn annotate_subtitles(&self, cues: &[Cue]) -> Vec<Cue> {
+		cues.iter().map(|cue| {
+			let pinyin: Vec<String> = cue.text.lines().map(|line| {
+				let words: Vec<&str> = self.subtitle_words(line).into_iter()
+					.filter_map(|word| self.entries_for_headword(word).first().map(|entry| entry.pinyin_numbers.as_str()))
+					.collect();
+				format_pinyin_words(&words)
+			}).filter(|pinyin| !pinyin.is_empty()).collect();
+
+			let mut annotated = cue.clone();
+			if !pinyin.is_empty() {
+				annotated.text = format!("{}\n{}", cue.text, pinyin.join(" "));
+			}
+			annotated
+		}).collect()
+	}
+
+	/// # Subtitle Vocabulary
+	/// List every Chinese word in a set of cues, such as an episode of a TV show, with the time of each cue it appears in, in order of first appearance.
+	pub fn subtitle_vocabulary(&self, cues: &[Cue]) -> Vec<SubtitleWord> {
+		let mut words: Vec<SubtitleWord> = Vec::new();
+		let mut positions: HashMap<String, usize> = HashMap::new();
+		for cue in cues {
+			for word in self.subtitle_words(&cue.text) {
+				let position = *positions.entry(word.to_string()).or_insert_with(|| {
+					let word_ids = self.entries_for_headword(word).iter().map(|entry| entry.word_id).collect();
+					words.push(SubtitleWord { word: word.to_string(), word_ids, occurrences: Vec::new() });
+					words.len() - 1
+				});
+				words[position].occurrences.push(cue.start);
+			}
+		}
+
+		words
+	}
+
+	/// The dictionary words of a subtitle that contain Chinese characters.
+	fn subtitle_words<'a>(&self, text: &'a str) -> Vec<&'a str> {
+		self.scan(text).into_iter().map(|range| &text[range]).filter(|word| word.chars().any(is_han)).collect()
+	}
+}
@@ -0,0 +1,117 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: "Did you mean" suggestions for queries without results
+
+use crate::chinese_dictionary::Dictionary;
+use crate::confusables::similar_characters;
+use chinese_detection::ClassificationResult;
+
+/// Endings removed from English words to find their stem, along with what replaces them, e.g. `cities` to `city`.
+static STEMS: [(&str, &str); 7] = [("ies", "y"), ("es", ""), ("s", ""), ("ing", ""), ("ed", ""), ("ed", "e"), ("ly", "")];
+
+impl Dictionary {
+	/// # Suggest
+	/// Suggest up to `limit` queries close to `raw` that have results, for showing "did you mean" when a query finds nothing.
+	/// Suggestions are made by:
+	/// * replacing characters with look-alikes (自已 to 自己), or adding, removing, or replacing a single character,
+	/// * changing the tone of a pinyin syllable (ni3hao4 to ni3hao3), or joining pinyin split with spaces,
+	/// * reducing English words to their stem (watermelons to watermelon), or splitting and joining English words (icecream to ice cream).
+	///
+	/// Suggestions are ordered from the most to the least likely, and never include the query itself.
+	pub fn suggest(&self, raw: &str, limit: usize) -> Vec<String> {
+		let query = self.normalize(raw).trim().to_string();
+		let mut suggestions: Vec<String> = Vec::new();
+		if query.is_empty() {
+			return suggestions;
+		}
+
+		match self.classify(&query) {
+			ClassificationResult::ZH => self.suggest_chinese(&query, &mut suggestions),
+			_ => {
+				self.suggest_pinyin(&query, &mut suggestions);
+				self.suggest_english(&query, &mut suggestions);
+			},
+		}
+
+		let mut unique: Vec<String> = Vec::new();
+		for suggestion in suggestions {
+			if suggestion != query && !unique.contains(&suggestion) {
+				unique.push(suggestion);
+			}
+		}
+		unique.truncate(limit);
+		unique
+	}
+
+	fn suggest_chinese(&self, query: &str, suggestions: &mut Vec<String>) {
+		let characters: Vec<char> = query.chars().collect();
+		for (index, character) in characters.iter().enumerate() {
+			for similar in similar_characters(*character) {
+				let mut candidate = characters.clone();
+				candidate[index] = similar;
+				let candidate: String = candidate.into_iter().collect();
+				if self.is_word(&candidate) {
+					suggestions.push(candidate);
+				}
+			}
+		}
+
+		let traditional = self.is_traditional(query);
+		for entry in self.query_chinese_fuzzy(query, 1) {
+			suggestions.push(if traditional { entry.traditional.clone() } else { entry.simplified.clone() });
+		}
+	}
+
+	fn suggest_pinyin(&self, query: &str, suggestions: &mut Vec<String>) {
+		let words: Vec<&str> = query.split(' ').filter(|word| !word.is_empty()).collect();
+		let joined = words.concat();
+		if words.len() > 1 && self.is_pinyin_key(&joined) {
+			suggestions.push(joined);
+		}
+
+		for (position, word) in words.iter().enumerate() {
+			if self.is_pinyin_key(word) {
+				continue;
+			}
+			for (index, character) in word.char_indices().filter(|(_, character)| ('1'..='5').contains(character)) {
+				for tone in ('1'..='5').filter(|tone| tone != &character) {
+					let candidate = format!("{}{}{}", &word[..index], tone, &word[index + 1..]);
+					if self.is_pinyin_key(&candidate) {
+						let mut replaced = words.clone();
+						replaced[position] = &candidate;
+						suggestions.push(replaced.join(" "));
+					}
+				}
+			}
+		}
+	}
+
+	fn suggest_english(&self, query: &str, suggestions: &mut Vec<String>) {
+		let words: Vec<&str> = query.split(' ').filter(|word| !word.is_empty()).collect();
+		for (position, word) in words.iter().enumerate() {
+			for (ending, replacement) in STEMS {
+				if let Some(stem) = word.strip_suffix(ending).filter(|stem| stem.len() > 1) {
+					let candidate = format!("{}{}", stem, replacement);
+					let mut replaced = words.clone();
+					replaced[position] = &candidate;
+					let replaced = replaced.join(" ");
+					if self.is_english_phrase(&replaced) {
+						suggestions.push(replaced);
+					}
+				}
+			}
+		}
+
+		if words.len() > 1 && self.is_english_phrase(&words.concat()) {
+			suggestions.push(words.concat());
+		}
+		if let [word] = words.as_slice() {
+			for (index, _) in word.char_indices().skip(1) {
+				let candidate = format!("{} {}", &word[..index], &word[index..]);
+				if self.is_english_phrase(&candidate) {
+					suggestions.push(candidate);
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,37 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Optional packs of domain terminology
+
+use crate::builder::parse_cedict;
+use crate::chinese_dictionary::WordEntry;
+use crate::source::SourceId;
+
+/// A professional domain covered by a terminology pack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Domain {
+	Medical,
+	Legal,
+	It,
+}
+
+/// The bundled terminology packs enabled through cargo features, in the CC-CEDICT format.
+static PACKS: &[(Domain, &str)] = &[
+	#[cfg(feature = "terms-medical")]
+	(Domain::Medical, include_str!("../data/terminology/medical.u8")),
+	#[cfg(feature = "terms-legal")]
+	(Domain::Legal, include_str!("../data/terminology/legal.u8")),
+	#[cfg(feature = "terms-it")]
+	(Domain::It, include_str!("../data/terminology/it.u8")),
+];
+
+/// Parses the entries of every enabled terminology pack, each tagged with its domain.
+pub(crate) fn packs() -> Vec<(Domain, Vec<WordEntry>)> {
+	PACKS.iter().map(|(domain, source)| {
+		let mut entries = parse_cedict(source).expect("Bundled terminology packs are valid CC-CEDICT");
+		for entry in &mut entries {
+			entry.domain = Some(*domain);
+			entry.source = SourceId::Terminology(*domain);
+		}
+		(*domain, entries)
+	}).collect()
+}
@@ -0,0 +1,50 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Checking text for consistent use of preferred terminology
+
+use crate::chinese_dictionary::Dictionary;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// A use of a non-preferred term found by `check_terminology`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation<'a> {
+	/// The byte range of the term within the text
+	pub range: Range<usize>,
+	/// The non-preferred term as it appears in the text
+	pub found: &'a str,
+	/// The term that should be used instead
+	pub preferred: &'a str,
+}
+
+impl Dictionary {
+	/// # Check Terminology
+	/// Find every use of a non-preferred term in `text`, where `glossary` maps each non-preferred term to the preferred one,
+	/// e.g. 计算机 to 电脑 for a style guide that prefers 电脑. Violations are returned in order of position.
+	///
+	/// Like `replace_word`, a term only counts where it starts and ends on word boundaries, so a glossary entry for 天 is not flagged inside 天气.
+	/// When terms overlap, the one starting first is reported, and the longer one when they start at the same position.
+	pub fn check_terminology<'a>(&self, text: &'a str, glossary: &'a HashMap<String, String>) -> Vec<Violation<'a>> {
+		let inside_word = self.word_interiors(text);
+		let mut candidates: Vec<Violation<'a>> = Vec::new();
+		for (term, preferred) in glossary.iter().filter(|(term, preferred)| !term.is_empty() && term != preferred) {
+			for (start, found) in text.match_indices(term.as_str()) {
+				let end = start + found.len();
+				if !inside_word.contains(&start) && !inside_word.contains(&end) {
+					candidates.push(Violation { range: start..end, found, preferred });
+				}
+			}
+		}
+		candidates.sort_by_key(|violation| (violation.range.start, Reverse(violation.range.end)));
+
+		let mut violations: Vec<Violation<'a>> = Vec::new();
+		for candidate in candidates {
+			if violations.last().is_none_or(|last: &Violation| candidate.range.start >= last.range.end) {
+				violations.push(candidate);
+			}
+		}
+
+		violations
+	}
+}
@@ -0,0 +1,312 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Tokenization with diagnostics for unrecognized content
+
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+use crate::sentences::split_sentences;
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+use std::str;
+
+/// The largest input, in bytes, accepted by `tokenize_checked` by default.
+pub const MAX_TOKENIZE_BYTES: usize = 1 << 20;
+
+/// The kind of a `Token`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+	/// A word found in the dictionary
+	Word,
+	/// A word written in the Latin alphabet, such as the `apple` in 我喜欢apple
+	Foreign,
+	/// A single emoji, including sequences joined with zero width joiners, skin tones, flags, and keycaps, such as 👍🏽 or 👨‍👩‍👧
+	Emoji,
+	/// A kaomoji emoticon, such as (＾▽＾) or (╯°□°)╯
+	Kaomoji,
+	/// A single mathematical symbol, such as `+`, `≠`, or `∑`
+	Symbol,
+	/// A run of text that did not match any dictionary word or any of the other kinds, such as punctuation or whitespace
+	Unknown,
+}
+
+/// A piece of tokenized text along with its byte range in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'a> {
+	pub text: &'a str,
+	pub range: Range<usize>,
+	pub kind: TokenKind,
+}
+
+/// An error encountered by `tokenize_checked`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenizeError {
+	/// The input is not valid UTF-8. `position` is the byte offset of the first invalid byte
+	InvalidUtf8 { position: usize },
+	/// The input is longer than `limit` bytes
+	InputTooLarge { length: usize, limit: usize },
+}
+
+impl fmt::Display for TokenizeError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			TokenizeError::InvalidUtf8 { position } => write!(f, "invalid UTF-8 at byte {}", position),
+			TokenizeError::InputTooLarge { length, limit } => write!(f, "input of {} bytes exceeds the limit of {} bytes", length, limit),
+		}
+	}
+}
+
+impl Error for TokenizeError {}
+
+impl Dictionary {
+	/// # Tokenize Checked
+	/// Split raw bytes of text into tokens, the same way as `segment`, without dropping anything.
+	/// Text that is not part of a dictionary word is split into `Foreign`, `Emoji`, `Kaomoji`, and `Symbol` tokens, with everything else returned
+	/// as `Unknown` tokens. The tokens cover the whole input, so joining them gives back the original text and the position of everything that was not understood can be logged.
+	///
+	/// Returns an error when the input is not valid UTF-8 or is longer than `InputLimits::max_tokenize_bytes`, which is `MAX_TOKENIZE_BYTES` by default.
+	pub fn tokenize_checked<'a>(&self, raw: &'a [u8]) -> Result<Vec<Token<'a>>, TokenizeError> {
+		let limit = self.input_limits().max_tokenize_bytes;
+		if raw.len() > limit {
+			return Err(TokenizeError::InputTooLarge { length: raw.len(), limit });
+		}
+		let text = str::from_utf8(raw).map_err(|error| TokenizeError::InvalidUtf8 { position: error.valid_up_to() })?;
+
+		// Emoji and kaomoji are split out first, so that dictionary words like □ cannot break them apart
+		let mut tokens: Vec<Token> = Vec::new();
+		let mut position = 0;
+		for (range, kind) in pictographs(text) {
+			self.push_segmented(text, position..range.start, &mut tokens);
+			position = range.end;
+			tokens.push(Token { text: &text[range.clone()], range, kind });
+		}
+		self.push_segmented(text, position..text.len(), &mut tokens);
+
+		Ok(tokens)
+	}
+
+	/// # Tokenize Chunked
+	/// Tokenize text of any length the same way as `tokenize_checked`, in chunks of at most `chunk_size` bytes, or `InputLimits::max_tokenize_bytes` if that is smaller.
+	/// Chunks end at sentence boundaries, found the same way as `split_sentences`, so no word is split between two chunks,
+	/// except when a single sentence is longer than a chunk and has to be cut at a character boundary.
+	/// The ranges of the tokens are relative to the whole text, so the result is the same as tokenizing it at once.
+	pub fn tokenize_chunked<'a>(&self, text: &'a str, chunk_size: usize) -> Vec<Token<'a>> {
+		let chunk_size = chunk_size.clamp(4, self.input_limits().max_tokenize_bytes.max(4));
+		let mut tokens: Vec<Token<'a>> = Vec::new();
+		let mut start = 0;
+		while start < text.len() {
+			let end = chunk_end(text, start, chunk_size);
+			let chunk = self.tokenize_checked(&text.as_bytes()[start..end]).unwrap_or_default();
+			tokens.extend(chunk.into_iter().map(|token| {
+				let range = start + token.range.start..start + token.range.end;
+				Token { text: &text[range.clone()], range, kind: token.kind }
+			}));
+			start = end;
+		}
+
+		tokens
+	}
+
+	/// Adds the tokens for a range of text without emoji or kaomoji: its dictionary words, and the text between them.
+	fn push_segmented<'a>(&self, text: &'a str, range: Range<usize>, tokens: &mut Vec<Token<'a>>) {
+		let mut position = range.start;
+		for word in self.scan(&text[range.clone()]) {
+			let word = range.start + word.start..range.start + word.end;
+			push_unmatched(text, position..word.start, tokens);
+			position = word.end;
+			tokens.push(Token { text: &text[word.clone()], range: word, kind: TokenKind::Word });
+		}
+		push_unmatched(text, position..range.end, tokens);
+	}
+
+	/// # Foreign Candidates
+	/// The entries with an English gloss matching a `TokenKind::Foreign` token, for code-switched text like 我喜欢apple,
+	/// where `apple` is matched with 苹果. Returns an empty `Vec` for tokens of any other kind.
+	pub fn foreign_candidates(&self, token: &Token) -> Vec<&WordEntry> {
+		match token.kind {
+			TokenKind::Foreign => self.query_by_english(&token.text.to_lowercase()),
+			_ => Vec::new(),
+		}
+	}
+}
+
+/// Where the chunk of `text` starting at `start` ends: after the last whole sentence that fits in `chunk_size` bytes,
+/// or at the last character boundary that fits when not even one sentence does. `chunk_size` is at least 4, the length of any character.
+fn chunk_end(text: &str, start: usize, chunk_size: usize) -> usize {
+	if text.len() - start <= chunk_size {
+		return text.len();
+	}
+	let mut end = start + chunk_size;
+	while !text.is_char_boundary(end) {
+		end -= 1;
+	}
+	let window = &text[start..end];
+	// The last sentence of the window may have been cut off, so the chunk ends after the sentence before it
+	let sentences = split_sentences(window);
+	match sentences.len().checked_sub(2).map(|last| sentences[last]) {
+		Some(sentence) => start + (sentence.as_ptr() as usize - window.as_ptr() as usize) + sentence.len(),
+		None => end,
+	}
+}
+
+/// Whether a character is a letter of the Latin alphabet, including accented letters like `é`.
+fn is_latin(character: char) -> bool {
+	character.is_ascii_alphabetic() || (('\u{00C0}'..='\u{024F}').contains(&character) && character.is_alphabetic())
+}
+
+/// Whether a character is a pictographic emoji that can start an emoji sequence.
+fn is_pictograph(character: char) -> bool {
+	matches!(character, '\u{1F000}'..='\u{1FAFF}' | '\u{2600}'..='\u{27BF}' | '\u{2B00}'..='\u{2BFF}' | '\u{231A}'..='\u{231B}' | '\u{23E9}'..='\u{23FA}' | '\u{3030}' | '\u{303D}')
+		&& !is_regional_indicator(character) && !is_skin_tone(character)
+}
+
+fn is_regional_indicator(character: char) -> bool {
+	('\u{1F1E6}'..='\u{1F1FF}').contains(&character)
+}
+
+fn is_skin_tone(character: char) -> bool {
+	('\u{1F3FB}'..='\u{1F3FF}').contains(&character)
+}
+
+/// Whether a character modifies the emoji before it: variation selectors, skin tones, the keycap mark, and tag characters.
+fn is_emoji_modifier(character: char) -> bool {
+	matches!(character, '\u{FE0E}' | '\u{FE0F}' | '\u{20E3}' | '\u{E0020}'..='\u{E007F}') || is_skin_tone(character)
+}
+
+/// Whether a character is a mathematical symbol, such as `+`, `≠`, or `∑`.
+fn is_math_symbol(character: char) -> bool {
+	matches!(character, '+' | '<' | '=' | '>' | '|' | '~' | '¬' | '±' | '×' | '÷' | '√' | '∞' | '＋' | '＜' | '＝' | '＞' | '～')
+		|| matches!(character, '\u{2200}'..='\u{22FF}' | '\u{27C0}'..='\u{27EF}' | '\u{2980}'..='\u{2AFF}')
+}
+
+/// Characters that make up the faces of kaomoji, such as the eyes and mouth of (＾▽＾).
+static KAOMOJI_FACE: &str = "＾^▽ω°□◕‿ಠ・´`︶╥ツシ≧≦∀Д益皿ｰ﹏ˇ◡∇゜ᴗ•σ；;＿_‸ಥ◉⊙ε∪￣´ー~×o";
+/// Characters that may surround the parentheses of kaomoji, such as the arms of (╯°□°)╯ or \(^o^)/.
+static KAOMOJI_ARMS: &str = "╯╰╮╭ノﾉヽ丿〃ゞ┻━┳┛┗┐┌/\\ლ٩۶୧୨ง✧*☆★♪♡~ᕙᕗ¯_ｏ";
+
+/// The length in characters of the emoji sequence at the start of `characters`, if there is one.
+/// A sequence is a pictograph with its modifiers, several of them joined with zero width joiners, a pair of regional indicators making up a flag,
+/// or a digit, `#`, or `*` followed by the keycap mark.
+fn emoji_length(characters: &[char]) -> Option<usize> {
+	let first = *characters.first()?;
+	if is_regional_indicator(first) {
+		return Some(if characters.get(1).is_some_and(|second| is_regional_indicator(*second)) { 2 } else { 1 });
+	}
+	if first.is_ascii_digit() || first == '#' || first == '*' {
+		return match (characters.get(1), characters.get(2)) {
+			(Some('\u{20E3}'), _) => Some(2),
+			(Some('\u{FE0F}'), Some('\u{20E3}')) => Some(3),
+			_ => None,
+		};
+	}
+	if !is_pictograph(first) {
+		return None;
+	}
+
+	let mut length = 1;
+	loop {
+		while characters.get(length).is_some_and(|character| is_emoji_modifier(*character)) {
+			length += 1;
+		}
+		match (characters.get(length), characters.get(length + 1)) {
+			(Some('\u{200D}'), Some(next)) if is_pictograph(*next) => length += 2,
+			_ => return Some(length),
+		}
+	}
+}
+
+/// The length in characters of the kaomoji at the start of `characters`, if there is one: a short parenthesized face
+/// made of face characters, without any CJK ideographs, along with its arms.
+fn kaomoji_length(characters: &[char]) -> Option<usize> {
+	let leading = characters.iter().take_while(|character| KAOMOJI_ARMS.contains(**character)).count();
+	let open = characters.get(leading)?;
+	let close = match open {
+		'(' => ')',
+		'（' => '）',
+		_ => return None,
+	};
+	let inside = characters[leading + 1..].iter().take(12).position(|character| *character == close)?;
+	let face = &characters[leading + 1..leading + 1 + inside];
+	let is_face = face.iter().any(|character| KAOMOJI_FACE.contains(*character) && !character.is_ascii_alphanumeric() && *character != '_' && *character != '~')
+		&& face.iter().all(|character| !character.is_whitespace() && !('\u{4E00}'..='\u{9FFF}').contains(character));
+	if !is_face {
+		return None;
+	}
+
+	let end = leading + inside + 2;
+	let trailing = characters[end..].iter().take_while(|character| KAOMOJI_ARMS.contains(**character)).count();
+	Some(end + trailing)
+}
+
+/// The byte ranges of the emoji and kaomoji in a string of text.
+fn pictographs(text: &str) -> Vec<(Range<usize>, TokenKind)> {
+	let characters: Vec<char> = text.chars().collect();
+	let mut offsets: Vec<usize> = text.char_indices().map(|(index, _)| index).collect();
+	offsets.push(text.len());
+
+	let mut pictographs: Vec<(Range<usize>, TokenKind)> = Vec::new();
+	let mut index = 0;
+	while index < characters.len() {
+		let found = emoji_length(&characters[index..]).map(|length| (length, TokenKind::Emoji))
+			.or_else(|| kaomoji_length(&characters[index..]).map(|length| (length, TokenKind::Kaomoji)));
+		match found {
+			Some((length, kind)) => {
+				pictographs.push((offsets[index]..offsets[index + length], kind));
+				index += length;
+			},
+			None => index += 1,
+		}
+	}
+
+	pictographs
+}
+
+/// The length in characters and kind of the symbol or Latin word at the start of `characters`, if there is one.
+fn typed_token(characters: &[char]) -> Option<(usize, TokenKind)> {
+	if is_math_symbol(characters[0]) {
+		return Some((1, TokenKind::Symbol));
+	}
+	if is_latin(characters[0]) || characters[0].is_ascii_digit() {
+		// A Latin word may contain digits, and apostrophes or hyphens between letters, e.g. `don't`, `e-mail`, `mp3`, or `3D`
+		let mut length = 1;
+		while let Some(character) = characters.get(length) {
+			let joins = (*character == '\'' || *character == '-') && characters.get(length + 1).is_some_and(|next| is_latin(*next));
+			if is_latin(*character) || character.is_ascii_digit() || joins {
+				length += 1;
+			} else {
+				break;
+			}
+		}
+		if characters[..length].iter().any(|character| is_latin(*character)) {
+			return Some((length, TokenKind::Foreign));
+		}
+	}
+
+	None
+}
+
+/// Adds the tokens for a range of text that is not part of a dictionary word, emoji, or kaomoji.
+/// Latin words and math symbols are split out into their own tokens, and everything between them is `TokenKind::Unknown`.
+fn push_unmatched<'a>(text: &'a str, range: Range<usize>, tokens: &mut Vec<Token<'a>>) {
+	let characters: Vec<char> = text[range.clone()].chars().collect();
+	let mut offsets: Vec<usize> = text[range.clone()].char_indices().map(|(index, _)| range.start + index).collect();
+	offsets.push(range.end);
+
+	let mut unknown_start = 0;
+	let mut index = 0;
+	while index < characters.len() {
+		match typed_token(&characters[index..]) {
+			Some((length, kind)) => {
+				if unknown_start < index {
+					tokens.push(Token { text: &text[offsets[unknown_start]..offsets[index]], range: offsets[unknown_start]..offsets[index], kind: TokenKind::Unknown });
+				}
+				tokens.push(Token { text: &text[offsets[index]..offsets[index + length]], range: offsets[index]..offsets[index + length], kind });
+				index += length;
+				unknown_start = index;
+			},
+			None => index += 1,
+		}
+	}
+	if unknown_start < characters.len() {
+		tokens.push(Token { text: &text[offsets[unknown_start]..range.end], range: offsets[unknown_start]..range.end, kind: TokenKind::Unknown });
+	}
+}
@@ -0,0 +1,273 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Transliteration of Chinese text into pinyin, zhuyin, Wade-Giles, and IPA
+
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+use crate::pinyin::{format_pinyin_words, is_valid_pinyin_syllable, split_syllable, split_tone_number};
+use crate::punctuation::{is_han, normalize_punctuation, Style};
+use std::cmp::Reverse;
+
+/// A romanization or phonetic script that `transliterate` writes Chinese text in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Target {
+	/// Pinyin with tone marks, with the syllables of a word written together, e.g. `xuésheng`
+	PinyinMarks,
+	/// Pinyin with tone numbers, with the syllables of a word written together, e.g. `xue2sheng5`
+	PinyinNumbers,
+	/// Zhuyin (bopomofo), with the syllables of a word written together, e.g. `ㄒㄩㄝˊ˙ㄕㄥ`
+	Zhuyin,
+	/// Wade-Giles, with superscript tone numbers and the syllables of a word joined by hyphens, e.g. `hsüeh²-sheng`
+	WadeGiles,
+	/// The International Phonetic Alphabet, with Chao tone letters and the syllables of a word separated by periods, e.g. `ɕɥɛ˧˥.ʂəŋ`
+	Ipa,
+}
+
+/// Structural particles and the neutral tone reading they are written with when they stand alone, which is far more common in running text
+/// than their other readings, such as the liǎo of 了 in 了解.
+static PARTICLES: [(&str, &str); 5] = [("了", "le5"), ("着", "zhe5"), ("得", "de5"), ("地", "de5"), ("的", "de5")];
+
+/// The entry whose reading a word is written with when it has several: the first that is not a proper noun or a variant, then the one with the most glosses.
+pub(crate) fn preferred_entry<'a>(entries: impl IntoIterator<Item = &'a WordEntry>) -> Option<&'a WordEntry> {
+	entries.into_iter().min_by_key(|entry| {
+		let proper_noun = entry.pinyin_numbers.starts_with(char::is_uppercase);
+		let variant = entry.english.iter().all(|gloss| gloss.contains("variant of"));
+		(proper_noun, variant, Reverse(entry.english.len()), entry.word_id)
+	})
+}
+
+fn zhuyin_initial(initial: &str) -> &'static str {
+	match initial {
+		"b" => "ㄅ", "p" => "ㄆ", "m" => "ㄇ", "f" => "ㄈ", "d" => "ㄉ", "t" => "ㄊ", "n" => "ㄋ", "l" => "ㄌ",
+		"g" => "ㄍ", "k" => "ㄎ", "h" => "ㄏ", "j" => "ㄐ", "q" => "ㄑ", "x" => "ㄒ", "zh" => "ㄓ", "ch" => "ㄔ",
+		"sh" => "ㄕ", "r" => "ㄖ", "z" => "ㄗ", "c" => "ㄘ", "s" => "ㄙ",
+		_ => "",
+	}
+}
+
+fn zhuyin_final(fin: &str) -> Option<&'static str> {
+	let zhuyin = match fin {
+		"a" => "ㄚ", "o" => "ㄛ", "e" => "ㄜ", "ai" => "ㄞ", "ei" => "ㄟ", "ao" => "ㄠ", "ou" => "ㄡ", "an" => "ㄢ",
+		"en" => "ㄣ", "ang" => "ㄤ", "eng" => "ㄥ", "er" => "ㄦ", "ong" => "ㄨㄥ", "i" => "ㄧ", "ia" => "ㄧㄚ", "io" => "ㄧㄛ",
+		"ie" => "ㄧㄝ", "iao" => "ㄧㄠ", "iu" => "ㄧㄡ", "ian" => "ㄧㄢ", "in" => "ㄧㄣ", "iang" => "ㄧㄤ", "ing" => "ㄧㄥ",
+		"iong" => "ㄩㄥ", "u" => "ㄨ", "ua" => "ㄨㄚ", "uo" => "ㄨㄛ", "uai" => "ㄨㄞ", "ui" => "ㄨㄟ", "uan" => "ㄨㄢ",
+		"un" => "ㄨㄣ", "uang" => "ㄨㄤ", "ueng" => "ㄨㄥ", "ü" => "ㄩ", "üe" => "ㄩㄝ", "üan" => "ㄩㄢ", "ün" => "ㄩㄣ",
+		_ => return None,
+	};
+
+	Some(zhuyin)
+}
+
+fn ipa_initial(initial: &str) -> &'static str {
+	match initial {
+		"b" => "p", "p" => "pʰ", "m" => "m", "f" => "f", "d" => "t", "t" => "tʰ", "n" => "n", "l" => "l",
+		"g" => "k", "k" => "kʰ", "h" => "x", "j" => "tɕ", "q" => "tɕʰ", "x" => "ɕ", "zh" => "ʈʂ", "ch" => "ʈʂʰ",
+		"sh" => "ʂ", "r" => "ʐ", "z" => "ts", "c" => "tsʰ", "s" => "s",
+		_ => "",
+	}
+}
+
+fn ipa_final(fin: &str) -> Option<&'static str> {
+	let ipa = match fin {
+		"a" => "a", "o" => "o", "e" => "ɤ", "ai" => "ai", "ei" => "ei", "ao" => "au", "ou" => "ou", "an" => "an",
+		"en" => "ən", "ang" => "ɑŋ", "eng" => "əŋ", "er" => "ɚ", "ong" => "ʊŋ", "i" => "i", "ia" => "ja", "io" => "jo",
+		"ie" => "jɛ", "iao" => "jau", "iu" => "jou", "ian" => "jɛn", "in" => "in", "iang" => "jɑŋ", "ing" => "iŋ",
+		"iong" => "jʊŋ", "u" => "u", "ua" => "wa", "uo" => "wo", "uai" => "wai", "ui" => "wei", "uan" => "wan",
+		"un" => "wən", "uang" => "wɑŋ", "ueng" => "wəŋ", "ü" => "y", "üe" => "ɥɛ", "üan" => "ɥɛn", "ün" => "yn",
+		_ => return None,
+	};
+
+	Some(ipa)
+}
+
+/// Whether the `i` after an initial is the apical vowel of 资 zī or 知 zhī rather than the `i` of 鸡 jī.
+fn is_apical(initial: &str, fin: &str) -> bool {
+	fin == "i" && matches!(initial, "z" | "c" | "s" | "zh" | "ch" | "sh" | "r")
+}
+
+fn zhuyin(syllable: &str) -> Option<String> {
+	let (initial, fin, tone) = split_syllable(syllable);
+	// The erhua `r5` is written as the final ㄦ after the syllable it changes
+	if initial == "r" && fin.is_empty() {
+		return Some(String::from("ㄦ"));
+	}
+	let fin = if is_apical(&initial, &fin) { "" } else { zhuyin_final(&fin)? };
+	let zhuyin = format!("{}{}", zhuyin_initial(&initial), fin);
+
+	Some(match tone.unwrap_or(5) {
+		2 => format!("{}ˊ", zhuyin),
+		3 => format!("{}ˇ", zhuyin),
+		4 => format!("{}ˋ", zhuyin),
+		5 => format!("˙{}", zhuyin),
+		_ => zhuyin,
+	})
+}
+
+fn wade_giles(syllable: &str) -> String {
+	let (initial, fin, tone) = split_syllable(syllable);
+	let letters = format!("{}{}", initial, fin);
+	let romanized = match initial.as_str() {
+		// Syllables without an initial are mostly spelled as in pinyin
+		"" => match split_tone_number(syllable).0.to_lowercase().replace("u:", "ü").replace('v', "ü").as_str() {
+			"yi" => String::from("i"),
+			"ye" => String::from("yeh"),
+			"yan" => String::from("yen"),
+			"you" => String::from("yu"),
+			"yu" => String::from("yü"),
+			"yue" => String::from("yüeh"),
+			"yuan" => String::from("yüan"),
+			"yun" => String::from("yün"),
+			"yong" => String::from("yung"),
+			"e" => String::from("o"),
+			"en" => String::from("ên"),
+			"eng" => String::from("êng"),
+			"er" => String::from("êrh"),
+			"wen" => String::from("wên"),
+			"weng" => String::from("wêng"),
+			spelled => spelled.to_string(),
+		},
+		"r" if fin.is_empty() => String::from("erh"),
+		_ if letters == "zi" => String::from("tzu"),
+		_ if letters == "ci" => String::from("tz'u"),
+		_ if letters == "si" => String::from("ssu"),
+		_ => {
+			let wade_giles_initial = match initial.as_str() {
+				"b" => "p", "p" => "p'", "d" => "t", "t" => "t'", "g" => "k", "k" => "k'", "j" | "zh" => "ch", "q" | "ch" => "ch'",
+				"x" => "hs", "r" => "j", "z" => "ts", "c" => "ts'",
+				other => other,
+			};
+			let wade_giles_final = match fin.as_str() {
+				_ if is_apical(&initial, &fin) => "ih",
+				"e" if matches!(initial.as_str(), "g" | "k" | "h") => "o",
+				"e" => "ê",
+				"en" => "ên",
+				"eng" => "êng",
+				"ie" => "ieh",
+				"üe" => "üeh",
+				"ian" => "ien",
+				"ong" => "ung",
+				"iong" => "iung",
+				"ui" if matches!(initial.as_str(), "g" | "k") => "uei",
+				"uo" if matches!(initial.as_str(), "d" | "t" | "n" | "l" | "z" | "c" | "s") => "o",
+				other => other,
+			};
+			format!("{}{}", wade_giles_initial, wade_giles_final)
+		}
+	};
+
+	match tone.unwrap_or(5) {
+		1 => format!("{}¹", romanized),
+		2 => format!("{}²", romanized),
+		3 => format!("{}³", romanized),
+		4 => format!("{}⁴", romanized),
+		_ => romanized,
+	}
+}
+
+/// # Pinyin to IPA
+/// Write a pinyin syllable without a tone, such as `"xue"`, in the International Phonetic Alphabet with the Chao tone letters for `tone`,
+/// such as `"ɕɥɛ˧˥"` for tone 2, e.g. for linguistics tools or the front-end of a text-to-speech engine.
+/// `ü` may be written as `v` or `u:`, the erhua `"r"` is written as `"ɚ"`, and the neutral tone `5` has no tone letters.
+/// Tones are the citation tones, without tone sandhi. Returns `None` when the syllable is not a syllable of Standard Mandarin or the tone is not 1 through 5.
+pub fn pinyin_to_ipa(syllable: &str, tone: u8) -> Option<String> {
+	if !(1..=5).contains(&tone) || !(is_valid_pinyin_syllable(syllable) || syllable.eq_ignore_ascii_case("r")) {
+		return None;
+	}
+	let (initial, fin, _) = split_syllable(syllable);
+	let fin = match fin.as_str() {
+		"i" if matches!(initial.as_str(), "z" | "c" | "s") => "ɹ̩",
+		"i" if matches!(initial.as_str(), "zh" | "ch" | "sh" | "r") => "ɻ̩",
+		"o" if matches!(initial.as_str(), "b" | "p" | "m" | "f") => "wo",
+		"" if initial == "r" => "ɚ",
+		_ => ipa_final(&fin)?,
+	};
+	// The erhua `r5` only adds rhotacization, so it has no initial of its own
+	let initial = if fin == "ɚ" { "" } else { ipa_initial(&initial) };
+	let tone = match tone {
+		1 => "˥",
+		2 => "˧˥",
+		3 => "˨˩˦",
+		4 => "˥˩",
+		_ => "",
+	};
+
+	Some(format!("{}{}{}", initial, fin, tone))
+}
+
+fn ipa(syllable: &str) -> Option<String> {
+	let (letters, tone) = split_tone_number(syllable);
+	pinyin_to_ipa(letters, tone.unwrap_or(5))
+}
+
+/// Capitalizes the first letter of a romanized syllable when the pinyin was capitalized, as CC-CEDICT does for proper nouns.
+fn keep_case(syllable: &str, transliterated: String) -> String {
+	if !syllable.starts_with(char::is_uppercase) {
+		return transliterated;
+	}
+	let mut characters = transliterated.chars();
+	characters.next().map_or(transliterated.clone(), |first| first.to_uppercase().chain(characters).collect())
+}
+
+/// Writes each syllable of space separated pinyin with `convert`, keeping anything that is not a pinyin syllable,
+/// such as the `·` in names or the `B` of B超, as it is written, and joins them with `separator`.
+fn convert_syllables(pinyin_numbers: &str, convert: impl Fn(&str) -> Option<String>, separator: &str) -> String {
+	pinyin_numbers.split_whitespace().map(|syllable| {
+		let erhua = split_tone_number(syllable).0.eq_ignore_ascii_case("r");
+		(is_valid_pinyin_syllable(syllable) || erhua).then(|| convert(syllable)).flatten().unwrap_or_else(|| syllable.to_string())
+	}).collect::<Vec<String>>().join(separator)
+}
+
+/// Writes the space separated syllables of a word with tone numbers in `target`.
+fn transliterate_word(pinyin_numbers: &str, target: Target) -> String {
+	match target {
+		Target::PinyinMarks => format_pinyin_words(&[pinyin_numbers]),
+		Target::PinyinNumbers => pinyin_numbers.split_whitespace().map(|syllable| syllable.replace("u:", "ü").replace("U:", "Ü")).collect(),
+		Target::Zhuyin => convert_syllables(pinyin_numbers, zhuyin, ""),
+		Target::WadeGiles => convert_syllables(pinyin_numbers, |syllable| Some(keep_case(syllable, wade_giles(syllable))), "-"),
+		Target::Ipa => convert_syllables(pinyin_numbers, ipa, "."),
+	}
+}
+
+impl WordEntry {
+	/// # IPA
+	/// The pronunciation of this entry in the International Phonetic Alphabet, derived from its pinyin, with Chao tone letters
+	/// and the syllables separated by periods, e.g. `ɕɥɛ˧˥.ʂəŋ` for 学生. Anything in the pinyin that is not a syllable is kept as it is.
+	pub fn ipa(&self) -> String {
+		transliterate_word(&self.pinyin_numbers, Target::Ipa)
+	}
+}
+
+impl Dictionary {
+	/// # Transliterate
+	/// Write Chinese text in a romanization or phonetic script, e.g. 我是学生 as `wǒ shì xuésheng` in pinyin or `wo³ shih⁴ hsüeh²-sheng` in Wade-Giles.
+	/// The text is segmented, and each word is written with the reading of its entry that is not a proper noun or a variant, with the most glosses,
+	/// except for particles such as 了 and 着, which are written with their neutral tone reading.
+	/// Words are separated by spaces, punctuation is written in its Western form, and anything that is not a dictionary word is kept as it is.
+	pub fn transliterate(&self, text: &str, target: Target) -> String {
+		let mut transliterated = String::with_capacity(text.len() * 2);
+		let mut position = 0;
+		let mut after_word = false;
+		for range in self.scan(text) {
+			let word = &text[range.clone()];
+			let entries = self.entries_for_headword(word);
+			let particle = PARTICLES.iter().find(|(particle, _)| *particle == word)
+				.and_then(|(_, reading)| entries.iter().find(|entry| entry.pinyin_numbers == *reading).copied());
+			let Some(entry) = particle.or_else(|| preferred_entry(entries)).filter(|_| word.chars().any(is_han)) else {
+				continue;
+			};
+
+			let between = normalize_punctuation(&text[position..range.start], Style::Western);
+			transliterated.push_str(&between);
+			// Words are separated by a space, as is a word from the punctuation before it, unless it opens a quotation or parenthesis
+			let opening = between.ends_with(|c: char| c.is_whitespace() || "\"'(“‘《".contains(c));
+			if !opening && (after_word || !between.is_empty()) {
+				transliterated.push(' ');
+			}
+			transliterated.push_str(&transliterate_word(&entry.pinyin_numbers, target));
+			position = range.end;
+			after_word = true;
+		}
+		transliterated.push_str(&normalize_punctuation(&text[position..], Style::Western));
+
+		transliterated
+	}
+}
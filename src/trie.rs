@@ -0,0 +1,141 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: A character trie over headwords for prefix completion and longest-match scanning
+
+use crate::chinese_dictionary::{Dictionary, WordEntry};
+use crate::index::KeyIndex;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// A trie keyed by character over the simplified and traditional headwords.
+///
+/// The children of each node are stored next to each other in one flat list of edges sorted by character,
+/// and the word ids of the headwords ending at each node in one flat list of postings, as in `KeyIndex`,
+/// so the trie can be written to a data file at build time and loaded with a single deserialization.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub(crate) struct HeadwordTrie {
+	/// Where the edges of each node start in `edges`, with one more offset at the end
+	edge_offsets: Vec<u32>,
+	/// Where the postings of each node start in `postings`, with one more offset at the end
+	posting_offsets: Vec<u32>,
+	edges: Vec<(char, u32)>,
+	postings: Vec<u32>,
+}
+
+/// Collects headwords into a `HeadwordTrie`. Inserting the same keys in the same order always builds the same trie.
+pub(crate) struct TrieBuilder {
+	children: Vec<BTreeMap<char, u32>>,
+	ids: Vec<Vec<u32>>,
+}
+
+impl TrieBuilder {
+	pub(crate) fn new() -> TrieBuilder {
+		TrieBuilder { children: vec![BTreeMap::new()], ids: vec![Vec::new()] }
+	}
+
+	/// Adds a headword and the ids of its entries, merging them with any ids already added for it.
+	pub(crate) fn insert(&mut self, key: &str, items: &[u32]) {
+		let mut node = 0;
+		for character in key.chars() {
+			let next = self.children.len() as u32;
+			node = *self.children[node].entry(character).or_insert(next) as usize;
+			if node == self.children.len() {
+				self.children.push(BTreeMap::new());
+				self.ids.push(Vec::new());
+			}
+		}
+		self.ids[node].extend_from_slice(items);
+	}
+
+	pub(crate) fn build(self) -> HeadwordTrie {
+		let mut trie = HeadwordTrie { edge_offsets: vec![0], posting_offsets: vec![0], edges: Vec::new(), postings: Vec::new() };
+		for (children, mut ids) in self.children.into_iter().zip(self.ids) {
+			ids.sort_unstable();
+			ids.dedup();
+			trie.edges.extend(children);
+			trie.postings.extend(ids);
+			trie.edge_offsets.push(trie.edges.len() as u32);
+			trie.posting_offsets.push(trie.postings.len() as u32);
+		}
+
+		trie
+	}
+}
+
+impl HeadwordTrie {
+	/// Builds the trie from the keys of the given indexes.
+	pub(crate) fn new(indexes: &[&KeyIndex]) -> HeadwordTrie {
+		let mut builder = TrieBuilder::new();
+		for index in indexes {
+			index.for_each(|key, items| builder.insert(key, items));
+		}
+
+		builder.build()
+	}
+
+	fn edges(&self, node: u32) -> &[(char, u32)] {
+		&self.edges[self.edge_offsets[node as usize] as usize..self.edge_offsets[node as usize + 1] as usize]
+	}
+
+	fn child(&self, node: u32, character: char) -> Option<u32> {
+		let edges = self.edges(node);
+		edges.binary_search_by_key(&character, |(edge, _)| *edge).ok().map(|position| edges[position].1)
+	}
+
+	fn ids(&self, node: u32) -> &[u32] {
+		&self.postings[self.posting_offsets[node as usize] as usize..self.posting_offsets[node as usize + 1] as usize]
+	}
+
+	/// The byte lengths of the headwords that `text` starts with, shortest first, up to `max_length` characters long.
+	pub(crate) fn prefixes(&self, text: &str, max_length: usize) -> Vec<usize> {
+		let mut lengths: Vec<usize> = Vec::new();
+		let mut node = 0;
+		for (index, character) in text.char_indices().take(max_length) {
+			match self.child(node, character) {
+				Some(next) => node = next,
+				None => break,
+			}
+			if !self.ids(node).is_empty() {
+				lengths.push(index + character.len_utf8());
+			}
+		}
+
+		lengths
+	}
+
+	/// Calls `visit` with the ids of every headword that starts with `prefix` and is longer than it.
+	pub(crate) fn continuations(&self, prefix: &str, mut visit: impl FnMut(&[u32])) {
+		let Some(start) = prefix.chars().try_fold(0, |node, character| self.child(node, character)) else {
+			return;
+		};
+		let mut stack: Vec<u32> = self.edges(start).iter().map(|(_, child)| *child).collect();
+		while let Some(node) = stack.pop() {
+			visit(self.ids(node));
+			stack.extend(self.edges(node).iter().map(|(_, child)| *child));
+		}
+	}
+}
+
+impl Dictionary {
+	/// # Complete Chinese
+	/// Up to `limit` words that continue `prefix`, in simplified or traditional characters, e.g. 天气 and 天空 for 天, for autocompleting a search box.
+	/// The prefix itself is left out. Words are ordered from the most to the least frequent, then from the shortest to the longest.
+	pub fn complete_chinese(&self, prefix: &str, limit: usize) -> Vec<&WordEntry> {
+		let mut seen: HashSet<u32> = HashSet::new();
+		let mut entries: Vec<&WordEntry> = Vec::new();
+		if prefix.is_empty() {
+			return entries;
+		}
+		self.headword_trie().continuations(prefix, |ids| {
+			for id in ids {
+				if let Some(entry) = self.entry_by_id(*id).filter(|_| seen.insert(*id)) {
+					entries.push(entry);
+				}
+			}
+		});
+		entries.sort_by_key(|entry| (std::cmp::Reverse(self.frequency(&entry.simplified)), entry.simplified.chars().count(), entry.word_id));
+		entries.truncate(limit);
+
+		entries
+	}
+}
@@ -0,0 +1,58 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Reports of Chinese text missing from the dictionary
+
+use crate::chinese_dictionary::Dictionary;
+use crate::punctuation::is_han;
+use std::ops::Range;
+
+/// The number of characters of context kept on each side of an unknown span.
+const CONTEXT_CHARACTERS: usize = 8;
+
+/// # Unknown Span
+/// A run of Chinese characters that matched no dictionary word, as returned by `unknown_words`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownSpan<'a> {
+	pub text: &'a str,
+	/// The byte range of `text` within the original string
+	pub range: Range<usize>,
+	/// The span along with up to eight characters on either side, for showing where it was found
+	pub context: &'a str,
+}
+
+impl Dictionary {
+	/// # Unknown Words
+	/// List the runs of Chinese characters in a string of text that were not part of any dictionary word after segmentation, in order.
+	/// These are often names, new words, or typos, and collecting them is a quick way to find candidate additions for a user dictionary.
+	/// Text other than Chinese characters, such as punctuation and Latin letters, is never reported.
+	pub fn unknown_words<'a>(&self, text: &'a str) -> Vec<UnknownSpan<'a>> {
+		let mut spans: Vec<UnknownSpan<'a>> = Vec::new();
+		let mut position = 0;
+		for range in self.scan(text).into_iter().chain(std::iter::once(text.len()..text.len())) {
+			let mut start: Option<usize> = None;
+			for (index, character) in text[position..range.start].char_indices().map(|(index, c)| (position + index, c)) {
+				match (start, is_han(character)) {
+					(None, true) => start = Some(index),
+					(Some(run), false) => {
+						spans.push(unknown_span(text, run..index));
+						start = None;
+					},
+					_ => {},
+				}
+			}
+			if let Some(run) = start {
+				spans.push(unknown_span(text, run..range.start));
+			}
+			position = range.end;
+		}
+
+		spans
+	}
+}
+
+fn unknown_span(text: &str, range: Range<usize>) -> UnknownSpan<'_> {
+	let before = text[..range.start].char_indices().rev().take(CONTEXT_CHARACTERS).last().map_or(range.start, |(index, _)| index);
+	let after = text[range.end..].char_indices().nth(CONTEXT_CHARACTERS).map_or(text.len(), |(index, _)| range.end + index);
+
+	UnknownSpan { text: &text[range.clone()], range, context: &text[before..after] }
+}
@@ -0,0 +1,199 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Building and validating entries for user dictionaries
+
+use crate::chinese_dictionary::{Dictionary, MeasureWord, WordEntry};
+use crate::hash::{allocate_word_id, entry_hash};
+use crate::pinyin::{is_valid_pinyin_syllable, numbers_to_marks, same_syllable, split_tone_number};
+use crate::punctuation::is_han;
+use crate::source::SourceId;
+
+/// A problem with a `WordEntry` found by `validate_entry`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+	/// The traditional or simplified headword is empty
+	EmptyHeadword,
+	/// The traditional and simplified headwords have a different number of characters
+	ScriptLengthMismatch,
+	/// A traditional character does not convert to the simplified character at the same position, found by `Dictionary::validate_entry`
+	ScriptMismatch { traditional: char, simplified: char },
+	/// A syllable of `pinyin_numbers` is not a syllable of Standard Mandarin
+	InvalidSyllable(String),
+	/// `pinyin_numbers` has a different number of syllables than the headword has characters
+	SyllableCountMismatch { characters: usize, syllables: usize },
+	/// `tone_marks` do not match the tone numbers of `pinyin_numbers`
+	ToneMarksMismatch,
+	/// `pinyin_marks` is not `pinyin_numbers` written with tone marks. The placement of the mark is not checked, so `lù:e` and `lüè` are both accepted for `lu:e4`
+	PinyinMarksMismatch,
+	/// The entry has no English glosses
+	NoGlosses,
+	/// The English gloss at this position is blank
+	EmptyGloss(usize),
+	/// `hash` is not the hash of the headwords and pinyin
+	HashMismatch,
+}
+
+/// # Validate Entry
+/// Check that the fields of an entry are consistent with each other, such as for entries of a user dictionary before passing them to `Dictionary::from_entries`.
+/// The pinyin syllables of characters that are not Chinese, such as the `A` of `A型`, are not checked.
+/// Returns every issue found, or an empty list for a valid entry. Use `Dictionary::validate_entry` to also check that the scripts convert to each other.
+pub fn validate_entry(entry: &WordEntry) -> Vec<ValidationIssue> {
+	let mut issues = Vec::new();
+	if entry.traditional.is_empty() || entry.simplified.is_empty() {
+		issues.push(ValidationIssue::EmptyHeadword);
+	}
+	let characters: Vec<char> = entry.simplified.chars().collect();
+	if entry.traditional.chars().count() != characters.len() {
+		issues.push(ValidationIssue::ScriptLengthMismatch);
+	}
+
+	let syllables: Vec<&str> = entry.pinyin_numbers.split(' ').filter(|syllable| !syllable.is_empty()).collect();
+	if syllables.len() != characters.len() {
+		issues.push(ValidationIssue::SyllableCountMismatch { characters: characters.len(), syllables: syllables.len() });
+	}
+	for (position, syllable) in syllables.iter().enumerate() {
+		let chinese = syllables.len() != characters.len() || is_han(characters[position]);
+		// CC-CEDICT writes the erhua suffix 儿 as `r5`
+		let erhua = split_tone_number(syllable).0.eq_ignore_ascii_case("r");
+		if chinese && !erhua && !is_valid_pinyin_syllable(syllable) {
+			issues.push(ValidationIssue::InvalidSyllable(syllable.to_string()));
+		}
+	}
+	let tones: Vec<u8> = syllables.iter().filter_map(|syllable| split_tone_number(syllable).1).collect();
+	if tones != entry.tone_marks {
+		issues.push(ValidationIssue::ToneMarksMismatch);
+	}
+	let marked: Vec<&str> = entry.pinyin_marks.split(' ').filter(|syllable| !syllable.is_empty()).collect();
+	if marked.len() != syllables.len() || !marked.iter().zip(&syllables).all(|(marked, numbered)| same_syllable(marked, numbered)) {
+		issues.push(ValidationIssue::PinyinMarksMismatch);
+	}
+
+	if entry.english.is_empty() {
+		issues.push(ValidationIssue::NoGlosses);
+	}
+	for (position, gloss) in entry.english.iter().enumerate() {
+		if gloss.trim().is_empty() {
+			issues.push(ValidationIssue::EmptyGloss(position));
+		}
+	}
+	if entry_hash(entry) != entry.hash {
+		issues.push(ValidationIssue::HashMismatch);
+	}
+
+	issues
+}
+
+impl Dictionary {
+	/// # Validate Entry
+	/// Check an entry like the `validate_entry` function, and also that each character of the traditional headword is the same as,
+	/// or converts to, the character at the same position of the simplified headword.
+	pub fn validate_entry(&self, entry: &WordEntry) -> Vec<ValidationIssue> {
+		let mut issues = validate_entry(entry);
+		if !issues.contains(&ValidationIssue::ScriptLengthMismatch) {
+			for (traditional, simplified) in entry.traditional.chars().zip(entry.simplified.chars()) {
+				if traditional != simplified && !self.convert_to_simplified(&traditional.to_string()).starts_with(simplified) {
+					issues.push(ValidationIssue::ScriptMismatch { traditional, simplified });
+				}
+			}
+		}
+		issues
+	}
+}
+
+/// # Word Entry Builder
+/// Builds a consistent `WordEntry` for a user dictionary, filling in `pinyin_marks`, `tone_marks`, and `hash` from the headwords and `pinyin_numbers`.
+///
+/// ```
+/// use chinese_dictionary::WordEntryBuilder;
+///
+/// let entry = WordEntryBuilder::new("電腦", "电脑", "dian4 nao3")
+///     .gloss("computer")
+///     .measure_word("臺", "台", "tai2")
+///     .build()
+///     .unwrap();
+/// assert_eq!("diàn nǎo", entry.pinyin_marks);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WordEntryBuilder {
+	traditional: String,
+	simplified: String,
+	pinyin_numbers: String,
+	english: Vec<String>,
+	measure_words: Vec<MeasureWord>,
+	hsk: u8,
+	word_id: Option<u32>,
+}
+
+impl WordEntryBuilder {
+	/// # New
+	/// Start an entry with its headwords and pinyin with tone numbers, such as `"dian4 nao3"`.
+	pub fn new(traditional: &str, simplified: &str, pinyin_numbers: &str) -> WordEntryBuilder {
+		WordEntryBuilder {
+			traditional: traditional.to_string(),
+			simplified: simplified.to_string(),
+			pinyin_numbers: pinyin_numbers.split_whitespace().collect::<Vec<&str>>().join(" "),
+			english: Vec::new(),
+			measure_words: Vec::new(),
+			hsk: 0,
+			word_id: None,
+		}
+	}
+
+	/// # Gloss
+	/// Add an English gloss.
+	pub fn gloss(mut self, gloss: &str) -> WordEntryBuilder {
+		self.english.push(gloss.to_string());
+		self
+	}
+
+	/// # Measure Word
+	/// Add a measure word with its headwords and pinyin with tone numbers.
+	pub fn measure_word(mut self, traditional: &str, simplified: &str, pinyin_numbers: &str) -> WordEntryBuilder {
+		self.measure_words.push(MeasureWord {
+			traditional: traditional.to_string(),
+			simplified: simplified.to_string(),
+			pinyin_marks: numbers_to_marks(pinyin_numbers),
+			pinyin_numbers: pinyin_numbers.to_string(),
+		});
+		self
+	}
+
+	/// # HSK
+	/// Set the HSK level, which is `0` by default.
+	pub fn hsk(mut self, hsk: u8) -> WordEntryBuilder {
+		self.hsk = hsk;
+		self
+	}
+
+	/// # Word ID
+	/// Set the `word_id`, which by default is allocated from the traditional headword and pinyin with `allocate_word_id`.
+	pub fn word_id(mut self, word_id: u32) -> WordEntryBuilder {
+		self.word_id = Some(word_id);
+		self
+	}
+
+	/// # Build
+	/// Build the entry with `SourceId::User` as its source, or return every issue `validate_entry` finds with it.
+	pub fn build(self) -> Result<WordEntry, Vec<ValidationIssue>> {
+		let word_id = self.word_id.unwrap_or_else(|| allocate_word_id(&self.traditional, &self.pinyin_numbers));
+		let mut entry = WordEntry {
+			pinyin_marks: numbers_to_marks(&self.pinyin_numbers),
+			tone_marks: self.pinyin_numbers.split(' ').filter_map(|syllable| split_tone_number(syllable).1).collect(),
+			traditional: self.traditional,
+			simplified: self.simplified,
+			pinyin_numbers: self.pinyin_numbers,
+			english: self.english,
+			hash: 0,
+			measure_words: self.measure_words,
+			hsk: self.hsk,
+			word_id,
+			domain: None,
+			year: None,
+			source: SourceId::User,
+		};
+		entry.hash = entry_hash(&entry);
+
+		let issues = validate_entry(&entry);
+		if issues.is_empty() { Ok(entry) } else { Err(issues) }
+	}
+}
@@ -0,0 +1,98 @@
+// @author		:: Preston Wang-Stosur-Bassett <p.wanstobas@gmail.com>
+// @date		:: October 16, 2026
+// @description		:: Regional vocabulary used when converting simplified text to traditional
+
+use std::collections::HashMap;
+
+static TAIWAN: &str = include_str!("../data/vocabulary/taiwan.u8");
+static HONG_KONG: &str = include_str!("../data/vocabulary/hongkong.u8");
+
+/// A region that writes with traditional characters and has its own vocabulary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Region {
+	Taiwan,
+	HongKong,
+}
+
+/// Characters with more than one traditional form, and the form Taiwan's Ministry of Education standard uses.
+static TAIWAN_VARIANTS: &[(char, char)] = &[
+	('台', '臺'), ('裏', '裡'), ('着', '著'), ('衞', '衛'), ('綫', '線'), ('爲', '為'), ('僞', '偽'), ('啓', '啟'),
+	('衆', '眾'), ('峯', '峰'), ('牀', '床'), ('羣', '群'), ('脣', '唇'), ('麪', '麵'), ('説', '說'), ('税', '稅'),
+	('脱', '脫'), ('閲', '閱'), ('鋭', '銳'), ('兑', '兌'), ('悦', '悅'), ('蜕', '蛻'),
+];
+
+/// Characters with more than one traditional form, and the form common in Hong Kong.
+static HONG_KONG_VARIANTS: &[(char, char)] = &[
+	('臺', '台'), ('裡', '裏'), ('衛', '衞'), ('線', '綫'), ('說', '説'), ('稅', '税'), ('脫', '脱'),
+	('閱', '閲'), ('銳', '鋭'), ('兌', '兑'), ('悅', '悦'), ('蛻', '蜕'),
+];
+
+impl Region {
+	/// # Apply Variants
+	/// Rewrite traditional text to the character variants this region prefers, e.g. 台灣 becomes 臺灣 for Taiwan and 臺北 becomes 台北 for Hong Kong.
+	/// Only characters whose variants mean the same thing are replaced, so the text keeps its words and only the written form changes.
+	pub fn apply_variants(&self, text: &str) -> String {
+		let variants = match self {
+			Region::Taiwan => TAIWAN_VARIANTS,
+			Region::HongKong => HONG_KONG_VARIANTS,
+		};
+		text.chars().map(|character| {
+			variants.iter().find(|(from, _)| *from == character).map_or(character, |(_, to)| *to)
+		}).collect()
+	}
+}
+
+/// A table of simplified phrases and the words a region uses for them.
+#[derive(Clone)]
+pub(crate) struct Vocabulary {
+	phrases: HashMap<String, String>,
+	max_length: usize,
+}
+
+impl Vocabulary {
+	/// The bundled vocabulary for a region.
+	pub(crate) fn bundled(region: Region) -> Vocabulary {
+		let source = match region {
+			Region::Taiwan => TAIWAN,
+			Region::HongKong => HONG_KONG,
+		};
+		let mut vocabulary = Vocabulary { phrases: HashMap::new(), max_length: 0 };
+		for line in source.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+			if let Some((simplified, traditional)) = line.split_once(' ') {
+				vocabulary.add(simplified, traditional.trim());
+			}
+		}
+
+		vocabulary
+	}
+
+	pub(crate) fn add(&mut self, simplified: &str, traditional: &str) {
+		self.max_length = self.max_length.max(simplified.chars().count());
+		self.phrases.insert(simplified.to_string(), traditional.to_string());
+	}
+
+	/// Replaces the longest phrase in the table at each position, converting the text between phrases with `convert`.
+	pub(crate) fn convert<F: Fn(&str) -> String>(&self, text: &str, convert: F) -> String {
+		let boundaries: Vec<usize> = text.char_indices().map(|(index, _)| index).chain(std::iter::once(text.len())).collect();
+		let mut output = String::with_capacity(text.len());
+		let mut unmatched = 0;
+		let mut start = 0;
+
+		while start < boundaries.len() - 1 {
+			let longest = (1..=self.max_length.min(boundaries.len() - 1 - start)).rev()
+				.find_map(|length| self.phrases.get(&text[boundaries[start]..boundaries[start + length]]).map(|phrase| (length, phrase)));
+			match longest {
+				Some((length, phrase)) => {
+					output.push_str(&convert(&text[boundaries[unmatched]..boundaries[start]]));
+					output.push_str(phrase);
+					start += length;
+					unmatched = start;
+				},
+				None => start += 1,
+			}
+		}
+		output.push_str(&convert(&text[boundaries[unmatched]..]));
+
+		output
+	}
+}